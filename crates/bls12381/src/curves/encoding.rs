@@ -0,0 +1,58 @@
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use ff::PrimeFieldBits;
+
+/// Returns the bytes of the big-endian `bits` if all of them have a value
+pub(crate) fn bits_to_bytes_be(bits: &[Boolean]) -> Option<Vec<u8>> {
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter().try_fold(0u8, |acc, b| {
+                b.get_value().map(|b| (acc << 1) | u8::from(b))
+            })
+        })
+        .collect()
+}
+
+/// Enforces the compression, infinity and sort flags of a compressed encoding in the ZCash
+/// serialization format, given as big-endian bits, and returns the infinity and sort flags.
+/// The compression flag must be set, and the point at infinity must be encoded with the sort
+/// flag unset and `x == 0`.
+pub(crate) fn enforce_compression_flags<F, CS>(
+    cs: &mut CS,
+    flags: &[Boolean],
+    x_is_zero: &AllocatedBit,
+) -> Result<(Boolean, Boolean), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(flags.len(), 3);
+    let (compression, infinity, sort) = (&flags[0], &flags[1], &flags[2]);
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "compression flag is set"),
+        compression,
+        &Boolean::constant(true),
+    )?;
+    cs.enforce(
+        || "infinity implies sort flag is unset",
+        |_| infinity.lc(CS::one(), F::ONE),
+        |_| sort.lc(CS::one(), F::ONE),
+        |lc| lc,
+    );
+    cs.enforce(
+        || "infinity implies x == 0",
+        |_| infinity.lc(CS::one(), F::ONE),
+        |lc| lc + CS::one() - x_is_zero.get_variable(),
+        |lc| lc,
+    );
+    Ok((infinity.clone(), sort.clone()))
+}
+
+/// Returns the flags of a compressed encoding as big-endian bits
+pub(crate) fn compression_flags(infinity: &AllocatedBit, sort: &Boolean) -> Vec<Boolean> {
+    vec![
+        Boolean::constant(true),
+        Boolean::from(infinity.clone()),
+        sort.clone(),
+    ]
+}
@@ -1,13 +1,23 @@
 use bellpepper_core::boolean::{AllocatedBit, Boolean};
 use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::field_element::EmulatedFieldParams;
+use bellpepper_emulated::weierstrass::WeierstrassPoint;
+use bls12_381::hash_to_curve::Sgn0;
 use bls12_381::G1Affine;
 use bls12_381::{fp::Fp as BlsFp, G1Projective};
 use ff::PrimeFieldBits;
 use num_bigint::BigInt;
+use num_integer::Integer;
 
-use crate::fields::fp::FpElement;
+use crate::fields::fp::{Bls12381FpParams, Bls12381Fr, Bls12381FrParams, FpElement};
+use crate::hash_to_field::{hash_to_field_fp, XmdHash};
 
-use super::params::{Bls12381G1Params, EmulatedCurveParams};
+use super::encoding::{bits_to_bytes_be, compression_flags, enforce_compression_flags};
+use super::msm;
+use super::params::Bls12381G1Params;
+
+/// Number of bits of each half of a scalar decomposed with `G1Point::glv_decompose`
+const GLV_SCALAR_BITS: usize = 128;
 
 /// Represents an affine point on BLS12-381's G1 curve. Point at infinity is represented with (0, 0)
 #[derive(Clone)]
@@ -16,6 +26,27 @@ pub struct G1Point<F: PrimeFieldBits> {
     pub y: FpElement<F>,
 }
 
+/// `G1Point` seen as a generic short Weierstrass point, which implements its arithmetic
+pub type G1Weierstrass<F> = WeierstrassPoint<F, Bls12381FpParams, Bls12381G1Params<F>>;
+
+impl<F: PrimeFieldBits> From<&G1Point<F>> for G1Weierstrass<F> {
+    fn from(value: &G1Point<F>) -> Self {
+        Self::new(value.x.0.clone(), value.y.0.clone())
+    }
+}
+
+impl<F: PrimeFieldBits> From<G1Weierstrass<F>> for G1Point<F> {
+    fn from(value: G1Weierstrass<F>) -> Self {
+        Self {
+            x: FpElement(value.x),
+            y: FpElement(value.y),
+        }
+    }
+}
+
+/// Represents a point on the curve E' isogenous to E specified in section 8.8.1 of [RFC 9380](https://datatracker.ietf.org/doc/rfc9380/)
+pub struct G1IsoPoint<F: PrimeFieldBits>(pub G1Point<F>);
+
 impl<F> From<&G1Affine> for G1Point<F>
 where
     F: PrimeFieldBits,
@@ -233,89 +264,21 @@ impl<F: PrimeFieldBits> G1Point<F> {
     where
         CS: ConstraintSystem<F>,
     {
-        let (p, q) = (self, value);
-        let cs = &mut cs.namespace(|| "G1::add(p, q)");
-        // compute λ = (q.y-p.y)/(q.x-p.x)
-        let qypy = q.y.sub(&mut cs.namespace(|| "qypy <- q.y - p.y"), &p.y)?;
-        let qxpx = q.x.sub(&mut cs.namespace(|| "qxpx <- q.x - p.x"), &p.x)?;
-        let lambda = qypy.div_unchecked(&mut cs.namespace(|| "lambda <- qypy div qxpx"), &qxpx)?;
-
-        // xr = λ²-p.x-q.x
-        let lambda_sq = lambda.square(&mut cs.namespace(|| "lambda_sq <- lambda.square()"))?;
-        let qxpx = p.x.add(&mut cs.namespace(|| "qxpx <- p.x + q.x"), &q.x)?;
-        let xr = lambda_sq.sub(&mut cs.namespace(|| "xr <- lambda_sq - qxpx"), &qxpx)?;
-
-        // p.y = λ(p.x-r.x) - p.y
-        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
-        let lambdapxrx = lambda.mul(&mut cs.namespace(|| "lambdapxrx <- lambda * pxrx"), &pxrx)?;
-        let yr = lambdapxrx.sub(&mut cs.namespace(|| "yr <- lambdapxrx - p.y"), &p.y)?;
-
-        Ok(Self { x: xr, y: yr })
+        Ok(G1Weierstrass::from(self)
+            .add(cs, &G1Weierstrass::from(value))?
+            .into())
     }
 
-    /// Returns the EC addition between `self` and `value`. Supports `p == q` and either point can be the identity
+    /// Returns the EC addition between `self` and `value`. Supports `p == q`, either point can be the identity
+    /// and one of them can be a constant
     /// It uses the unified formulas of Brier and Joye from [BriJoy02 (Corollary 1)](https://link.springer.com/content/pdf/10.1007/3-540-45664-3_24.pdf)
     pub fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
     {
-        let (p, q) = (self, value);
-        let cs = &mut cs.namespace(|| "G1::add_unified(p, q)");
-        let sel1 = p.alloc_is_identity(&mut cs.namespace(|| "sel1 <- p.is_identity()"))?;
-        let sel2 = q.alloc_is_identity(&mut cs.namespace(|| "sel2 <- q.is_identity()"))?;
-
-        // λ = ((p.x+q.x)² - p.x*q.x + a)/(p.y + q.y)
-        let pxqx = p.x.mul(&mut cs.namespace(|| "pxqx <- p.x * q.x"), &q.x)?;
-        let pxplusqx =
-            p.x.add(&mut cs.namespace(|| "pxplusqx <- p.x + q.x"), &q.x)?;
-        let num = pxplusqx.square(&mut cs.namespace(|| "num <- pxplusqx^2"))?;
-        let num = num.sub(&mut cs.namespace(|| "num <- num - pxqx"), &pxqx)?;
-        let denum = p.y.add(&mut cs.namespace(|| "denum <- p.y + q.y"), &q.y)?;
-        // if p.y + q.y = 0, assign dummy 1 to denum and continue
-        let sel3 = denum.alloc_is_zero(&mut cs.namespace(|| "sel3 <- denum.is_zero()"))?;
-        let denum = FpElement::conditionally_select(
-            &mut cs.namespace(|| "denum <- select(denum, 1, sel3)"),
-            &denum,
-            &FpElement::one(),
-            &Boolean::from(sel3.clone()),
-        )?;
-        let lambda = num.div_unchecked(&mut cs.namespace(|| "lamda <- num div denum"), &denum)?;
-
-        // x = λ^2 - p.x - q.x
-        let xr = lambda.square(&mut cs.namespace(|| "xr <- lambda.square()"))?;
-        let xr = xr.sub(&mut cs.namespace(|| "xr <- xr - pxplusqx"), &pxplusqx)?;
-
-        // y = λ(p.x - xr) - p.y
-        let yr = p.x.sub(&mut cs.namespace(|| "yr <- p.x - xr"), &xr)?;
-        let yr = yr.mul(&mut cs.namespace(|| "yr <- yr * lambda"), &lambda)?;
-        let yr = yr.sub(&mut cs.namespace(|| "yr <- yr - p.y"), &p.y)?;
-        let xr = xr.reduce(&mut cs.namespace(|| "xr <- xr.reduce()"))?;
-        let yr = yr.reduce(&mut cs.namespace(|| "yr <- yr.reduce()"))?;
-        let res = Self { x: xr, y: yr };
-
-        // if p=(0,0) return q
-        let res = Self::conditionally_select(
-            &mut cs.namespace(|| "res <- select(res, q, sel1)"),
-            &res,
-            q,
-            &Boolean::from(sel1),
-        )?;
-        // if q=(0,0) return p
-        let res = Self::conditionally_select(
-            &mut cs.namespace(|| "res <- select(res, p, sel2)"),
-            &res,
-            p,
-            &Boolean::from(sel2),
-        )?;
-        // if p.y + q.y = 0, return (0, 0)
-        let res = Self::conditionally_select(
-            &mut cs.namespace(|| "res <- select(res, 0, sel3)"),
-            &res,
-            &Self::identity(),
-            &Boolean::from(sel3),
-        )?;
-
-        Ok(res)
+        Ok(G1Weierstrass::from(self)
+            .add_unified(cs, &G1Weierstrass::from(value))?
+            .into())
     }
 
     /// Returns `-P`
@@ -344,25 +307,7 @@ impl<F: PrimeFieldBits> G1Point<F> {
     where
         CS: ConstraintSystem<F>,
     {
-        let cs = &mut cs.namespace(|| "G1::double(p)");
-        let p = self.reduce(&mut cs.namespace(|| "p <- p.reduce()"))?;
-        // compute λ = (3p.x²)/2*p.y
-        let xx3a = p.x.square(&mut cs.namespace(|| "xx3a <- p.x.square()"))?;
-        let xx3a = xx3a.mul_const(&mut cs.namespace(|| "xx3a <- xx3a * 3"), &BigInt::from(3))?;
-        let y2 = p.y.double(&mut cs.namespace(|| "y2 <- p.y.double()"))?;
-        let lambda = xx3a.div_unchecked(&mut cs.namespace(|| "lambda <- xx3a div y2"), &y2)?;
-
-        // xr = λ²-2p.x
-        let x2 = p.x.double(&mut cs.namespace(|| "x2 <- p.x.double()"))?;
-        let lambda_sq = lambda.square(&mut cs.namespace(|| "lambda_sq <- lambda.square()"))?;
-        let xr = lambda_sq.sub(&mut cs.namespace(|| "xr <- lambda_sq - x2"), &x2)?;
-
-        // yr = λ(p-xr) - p.y
-        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
-        let lambdapxrx = lambda.mul(&mut cs.namespace(|| "lambdapxrx <- lambda * pxrx"), &pxrx)?;
-        let yr = lambdapxrx.sub(&mut cs.namespace(|| "yr <- lambdapxrx - p.y"), &p.y)?;
-
-        Ok(Self { x: xr, y: yr })
+        Ok(G1Weierstrass::from(self).double(cs)?.into())
     }
 
     /// Calls `self.double()` repeated `n` times
@@ -393,37 +338,7 @@ impl<F: PrimeFieldBits> G1Point<F> {
     where
         CS: ConstraintSystem<F>,
     {
-        let p = self;
-        let cs = &mut cs.namespace(|| "G1::triple(p)");
-        // compute λ1 = (3p.x²)/2p.y
-        let xx = p.x.square(&mut cs.namespace(|| "xx <- p.x.square()"))?;
-        let xx = xx.mul_const(&mut cs.namespace(|| "xx <- xx * 3"), &BigInt::from(3))?;
-        let y2 = p.y.double(&mut cs.namespace(|| "y2 <- p.y.double()"))?;
-        let l1 = xx.div_unchecked(&mut cs.namespace(|| "l1 <- xx div y2"), &y2)?;
-
-        // xr = λ1²-2p.x
-        let x2 =
-            p.x.mul_const(&mut cs.namespace(|| "x2 <- p.x * 2"), &BigInt::from(2))?;
-        let l1l1 = l1.square(&mut cs.namespace(|| "l1l1 <- l1 * l1"))?;
-        let x2 = l1l1.sub(&mut cs.namespace(|| "x2 <- l1l1 - x2"), &x2)?;
-
-        // ommit y2 computation, and
-        // compute λ2 = 2p.y/(x2 − p.x) − λ1.
-        let x1x2 = p.x.sub(&mut cs.namespace(|| "x1x2 <- p.x - x2"), &x2)?;
-        let l2 = y2.div_unchecked(&mut cs.namespace(|| "l2 <- y2 div x1x2"), &x1x2)?;
-        let l2 = l2.sub(&mut cs.namespace(|| "l2 <- l2 - l1"), &l1)?;
-
-        // xr = λ²-p.x-x2
-        let l2l2 = l2.square(&mut cs.namespace(|| "l2l2 <- l2 * l2"))?;
-        let qxrx = x2.add(&mut cs.namespace(|| "qxrx <- x2 + p.x"), &p.x)?;
-        let xr = l2l2.sub(&mut cs.namespace(|| "xr <- l2l2 - qxrx"), &qxrx)?;
-
-        // yr = λ(p.x-xr) - p.y
-        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
-        let l2pxrx = l2.mul(&mut cs.namespace(|| "l2pxrx <- l2 * pxrx"), &pxrx)?;
-        let yr = l2pxrx.sub(&mut cs.namespace(|| "yr <- l2pxrx - p.y"), &p.y)?;
-
-        Ok(Self { x: xr, y: yr })
+        Ok(G1Weierstrass::from(self).triple(cs)?.into())
     }
 
     /// Returns `2*self + value`
@@ -431,37 +346,9 @@ impl<F: PrimeFieldBits> G1Point<F> {
     where
         CS: ConstraintSystem<F>,
     {
-        let (p, q) = (self, value);
-        let cs = &mut cs.namespace(|| "G1::double_and_add(p, q)");
-        // compute λ1 = (q.y-p.y)/(q.x-p.x)
-        let yqyp = q.y.sub(&mut cs.namespace(|| "yqyp <- q.y - p.y"), &p.y)?;
-        let xqxp = q.x.sub(&mut cs.namespace(|| "xqxp <- q.x - p.x"), &p.x)?;
-        let l1 = yqyp.div_unchecked(&mut cs.namespace(|| "l1 <- yqyp div xqxp"), &xqxp)?;
-
-        // compute x2 = l1²-p.x-q.x
-        let l1l1 = l1.square(&mut cs.namespace(|| "l1l1 <- l1.square()"))?;
-        let xqxp = p.x.add(&mut cs.namespace(|| "xqxp <- p.x + q.x"), &q.x)?;
-        let x2 = l1l1.sub(&mut cs.namespace(|| "x2 <- l1l1 - xqxp"), &xqxp)?;
-
-        // ommit y2 computation
-        // compute l2 = -l1-2*p.y/(x2-p.x)
-        let ypyp = p.y.add(&mut cs.namespace(|| "ypyp <- p.y + p.y"), &p.y)?;
-        let x2xp = x2.sub(&mut cs.namespace(|| "x2xp <- x2 - p.x"), &p.x)?;
-        let l2 = ypyp.div_unchecked(&mut cs.namespace(|| "l2 <- ypyp div x2xp"), &x2xp)?;
-        let l2 = l1.add(&mut cs.namespace(|| "l2 <- l1 + l2"), &l2)?;
-        let l2 = l2.neg(&mut cs.namespace(|| "l2 <- l2.neg()"))?;
-
-        // compute x3 =l2²-p.x-x3
-        let l2l2 = l2.square(&mut cs.namespace(|| "l2l2 <- l2.square()"))?;
-        let x3 = l2l2.sub(&mut cs.namespace(|| "x3 <- l2l2 - p.x"), &p.x)?;
-        let x3 = x3.sub(&mut cs.namespace(|| "x3 <- x3 - x2"), &x2)?;
-
-        // compute y3 = l2*(p.x - x3)-p.y
-        let y3 = p.x.sub(&mut cs.namespace(|| "y3 <- p.x - x3"), &x3)?;
-        let y3 = l2.mul(&mut cs.namespace(|| "y3 <- l2 * y3"), &y3)?;
-        let y3 = y3.sub(&mut cs.namespace(|| "y3 <- y3 - p.y"), &p.y)?;
-
-        Ok(Self { x: x3, y: y3 })
+        Ok(G1Weierstrass::from(self)
+            .double_and_add(cs, &G1Weierstrass::from(value))?
+            .into())
     }
 
     pub fn conditionally_select<CS>(
@@ -488,6 +375,236 @@ impl<F: PrimeFieldBits> G1Point<F> {
         Ok(Self { x, y })
     }
 
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    pub fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let inputs_x = inputs.iter().map(|i| i.x.clone()).collect::<Vec<_>>();
+        let inputs_y = inputs.iter().map(|i| i.y.clone()).collect::<Vec<_>>();
+        let x = FpElement::mux_tree(
+            &mut cs.namespace(|| "mux x"),
+            select_bits.clone(),
+            &inputs_x,
+        )?;
+        let y = FpElement::mux_tree(&mut cs.namespace(|| "mux y"), select_bits, &inputs_y)?;
+        Ok(Self { x, y })
+    }
+
+    /// Returns `[a]P + [b]Q` using the interleaved (Straus-Shamir) method, where `a` and `b` are
+    /// given as little-endian bits. Scalars of different lengths are padded with zeros.
+    ///
+    /// The doublings are shared between both scalars and every window of `window_size` bits
+    /// selects `[i]P + [j]Q` from a joint lookup table of `2^(2*window_size)` points. All the
+    /// additions use `add_unified`, so `P`, `Q` and any intermediate value can be the identity.
+    pub fn double_scalar_mul_windowed<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+        window_size: usize,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(G1Weierstrass::double_scalar_mul_windowed(
+            cs,
+            &G1Weierstrass::from(p),
+            a,
+            &G1Weierstrass::from(q),
+            b,
+            window_size,
+        )?
+        .into())
+    }
+
+    /// Returns `[a]P + [b]Q` using `double_scalar_mul_windowed` with windows of 2 bits
+    pub fn double_scalar_mul<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::double_scalar_mul_windowed(cs, p, a, q, b, 2)
+    }
+
+    /// Returns `[k]P` where `k` is given as little-endian bits, using a lookup table of
+    /// `[i]P` for `i` in `0..2^window_size`. Supports `P` being the identity or outside of
+    /// the prime-order subgroup, as all the additions use `add_unified`.
+    pub fn scalar_mul_windowed<CS>(
+        &self,
+        cs: &mut CS,
+        scalar: &[Boolean],
+        window_size: usize,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if window_size == 0 {
+            eprintln!("Window size must be positive");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        if scalar.is_empty() {
+            eprintln!("Scalar bit vector cannot be empty");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut cs = cs.namespace(|| format!("G1::scalar_mul_windowed(p, k, {window_size})"));
+        let p = self.reduce(&mut cs.namespace(|| "p <- p.reduce()"))?;
+
+        let mut table: Vec<Self> = vec![Self::identity(), p.clone()];
+        for i in 2..(1usize << window_size) {
+            let point = table[i - 1].add_unified(&mut cs.namespace(|| format!("{i}P")), &p)?;
+            table.push(point);
+        }
+
+        let mut res: Option<Self> = None;
+        for (w, window_bits) in scalar.chunks(window_size).enumerate().rev() {
+            // the last window can be shorter than `window_size`
+            let tmp = Self::mux_tree(
+                &mut cs.namespace(|| format!("tmp <- table[window {w}]")),
+                window_bits.iter().rev(), // mux_tree requires MSB first
+                &table[..(1usize << window_bits.len())],
+            )?;
+
+            res = match res {
+                None => Some(tmp),
+                Some(mut acc) => {
+                    for k in 0..window_bits.len() {
+                        acc = acc.add_unified(
+                            &mut cs.namespace(|| format!("res <- res + res ({w}, {k})")),
+                            &acc,
+                        )?;
+                    }
+                    Some(acc.add_unified(
+                        &mut cs.namespace(|| format!("res <- res + tmp ({w})")),
+                        &tmp,
+                    )?)
+                }
+            };
+        }
+
+        Ok(res.unwrap())
+    }
+
+    /// Returns the cube root of unity `λ = x^2 - 1` of the scalar field, where `x` is the
+    /// BLS parameter for BLS12-381. For `P` in G1, `phi(phi(P)) = [λ]P`.
+    fn glv_lambda() -> BigInt {
+        BigInt::parse_bytes(b"ac45a4010001a40200000000ffffffff", 16).unwrap()
+    }
+
+    /// Decomposes `k` into `k1 + λ*k2 mod r` with `k1` and `k2` of 128 bits each, returned as
+    /// little-endian bits. The decomposition is computed outside the circuit and enforced.
+    fn glv_decompose<CS>(
+        cs: &mut CS,
+        scalar: &Bls12381Fr<F>,
+    ) -> Result<(Vec<Boolean>, Vec<Boolean>), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let lambda = Self::glv_lambda();
+        let (k1, k2) = match BigInt::try_from(scalar) {
+            Ok(k) => {
+                let k = k.mod_floor(&Bls12381FrParams::modulus());
+                let (k2, k1) = k.div_mod_floor(&lambda);
+                (Some(k1), Some(k2))
+            }
+            Err(_) => (None, None),
+        };
+        let alloc_bits = |cs: &mut CS, name: &str, value: &Option<BigInt>| {
+            (0..GLV_SCALAR_BITS)
+                .map(|i| {
+                    Ok(Boolean::from(AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc bit {i} of {name}")),
+                        value.as_ref().map(|v| v.bit(i as u64)),
+                    )?))
+                })
+                .collect::<Result<Vec<_>, SynthesisError>>()
+        };
+        let k1_bits = alloc_bits(cs, "k1", &k1)?;
+        let k2_bits = alloc_bits(cs, "k2", &k2)?;
+
+        let k1 = Bls12381Fr::<F>::from_bits_le(CS::one(), &k1_bits);
+        let k2 = Bls12381Fr::<F>::from_bits_le(CS::one(), &k2_bits);
+        let lambda_k2 = k2.mul_const(&mut cs.namespace(|| "lambda_k2 <- k2 * lambda"), &lambda)?;
+        let res = k1.add(&mut cs.namespace(|| "res <- k1 + lambda_k2"), &lambda_k2)?;
+        Bls12381Fr::<F>::assert_is_equal(&mut cs.namespace(|| "res =? k"), &res, scalar)?;
+
+        Ok((k1_bits, k2_bits))
+    }
+
+    /// Returns `[k]P` using the GLV method with the endomorphism `phi`. The scalar is split
+    /// as `k = k1 + λ*k2 mod r` and `[k1]P + [k2]phi(phi(P))` is computed with `double_scalar_mul`,
+    /// so only 128 doublings are needed. Requires `P` to be in the prime-order subgroup G1,
+    /// but `P` and `k` can be zero.
+    pub fn glv_scalar_mul<CS>(
+        &self,
+        cs: &mut CS,
+        scalar: &Bls12381Fr<F>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "G1::glv_scalar_mul(p, k)");
+        let (k1, k2) =
+            Self::glv_decompose(&mut cs.namespace(|| "(k1, k2) <- decompose(k)"), scalar)?;
+        let q = self.phi(&mut cs.namespace(|| "q <- p.phi()"))?;
+        let q = q.phi(&mut cs.namespace(|| "q <- q.phi()"))?;
+        let res =
+            Self::double_scalar_mul(&mut cs.namespace(|| "[k1]p + [k2]q"), self, &k1, &q, &k2)?;
+        Ok(res)
+    }
+
+    /// Returns `[k]P` where `k` is given as little-endian bits, using `glv_scalar_mul`.
+    /// Requires `P` to be in the prime-order subgroup G1, use `scalar_mul_windowed` otherwise.
+    pub fn scalar_mul<CS>(&self, cs: &mut CS, scalar: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let k = Bls12381Fr::<F>::from_bits_le(CS::one(), scalar);
+        self.glv_scalar_mul(cs, &k)
+    }
+
+    /// Returns `sum([k_i]P_i)` where the scalars `k_i` are given as little-endian bits, using
+    /// the Straus or Pippenger method depending on the number of points and scalar bits.
+    /// Supports points outside of the prime-order subgroup and the identity.
+    pub fn msm<CS>(
+        cs: &mut CS,
+        points: &[Self],
+        scalars: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        msm::msm(&mut cs.namespace(|| "G1::msm(p, k)"), points, scalars)
+    }
+
+    /// Returns `sum([k_i]B_i)` for constant bases `B_i`, where the scalars `k_i` are given as
+    /// little-endian bits
+    pub fn msm_constant_bases<CS>(
+        cs: &mut CS,
+        bases: &[G1Affine],
+        scalars: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let bases = bases.iter().map(G1Projective::from).collect::<Vec<_>>();
+        msm::msm_constant_bases(
+            &mut cs.namespace(|| "G1::msm_constant_bases(b, k)"),
+            &bases,
+            scalars,
+        )
+    }
+
     /// Returns `[x^2]P` where `x` is the BLS parameter for BLS12-381, `-15132376222941642752`
     pub fn scalar_mul_by_seed_square<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
     where
@@ -519,6 +636,367 @@ impl<F: PrimeFieldBits> G1Point<F> {
         Ok(z)
     }
 
+    /// Implementation of the simplified SWU map to BLS12-381 G1.
+    ///
+    /// Takes an input `u` in Fp and returns a point on the 11-isogenous curve E1'.
+    ///
+    /// References:
+    ///   * [Section 6.6.2 and 8.8.1 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-bls12-381-g1)
+    ///   * [Section 4 of Wahby-Boneh](https://eprint.iacr.org/2019/403.pdf)
+    ///   * [Reference python code from bls_sigs_ref](https://github.com/algorand/bls_sigs_ref/blob/master/python-impl/opt_swu_g1.py)
+    pub fn opt_simple_swu1<CS>(
+        cs: &mut CS,
+        u: &FpElement<F>,
+    ) -> Result<G1IsoPoint<F>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let cs = &mut cs.namespace(|| "G1::opt_simple_swu1(u)");
+
+        // curve equation parameters for E1'
+        let a = FpElement::from_dec("12190336318893619529228877361869031420615612348429846051986726275283378313155663745811710833465465981901188123677").unwrap();
+        let a_neg = a.neg(&mut cs.namespace(|| "a_neg <- -a"))?;
+        let b = FpElement::from_dec("2906670324641927570491258158026293881577086121416628140204402091718288198173574630967936031029026176254968826637280").unwrap();
+        let z = FpElement::from_dec("11").unwrap();
+
+        let u2 = u.square(&mut cs.namespace(|| "u2 <- u.square()"))?;
+        let z_u2 = z.mul(&mut cs.namespace(|| "z_u2 <- z * u2"), &u2)?;
+        let z2_u4 = z_u2.square(&mut cs.namespace(|| "z2_u4 <- z_u2.square()"))?;
+
+        let num_den_common = z2_u4.add(&mut cs.namespace(|| "ndc <- z2_u4 + z_u2"), &z_u2)?;
+
+        let x0_den = num_den_common.mul(&mut cs.namespace(|| "x0_den <- ndc * -a"), &a_neg)?;
+        let x0_den = x0_den.reduce(&mut cs.namespace(|| "x0_den <- x0_den.reduce()"))?;
+        // if X0_den = 0, replace with X1_den = z * a; this way X1(u) = X0_num / X1_den = b / (z * a)
+        let is_den_0 = x0_den.alloc_is_zero(&mut cs.namespace(|| "is_den_0"))?;
+        let x1_den = FpElement::from_dec("134093699507829814821517650980559345626771735832728306571853989028117161444712301203928819168120125800913069360447").unwrap();
+
+        let num_den_common =
+            num_den_common.add(&mut cs.namespace(|| "ndc <- ndc + 1"), &FpElement::one())?;
+        let x0_num = b.mul(&mut cs.namespace(|| "x0_num <- b * ndc"), &num_den_common)?;
+
+        let x0_den = FpElement::conditionally_select(
+            &mut cs.namespace(|| "x0_den <- select(x0_den, x1_den, is_den_0)"),
+            &x0_den,
+            &x1_den,
+            &Boolean::Is(is_den_0),
+        )?;
+
+        let x0 = x0_num.div_unchecked(&mut cs.namespace(|| "x0 <- x0_num div x0_den"), &x0_den)?;
+
+        // g(x) = x^3 + a x + b
+        // Compute g(X0(u))
+        let x0_2 = x0.square(&mut cs.namespace(|| "x0_2 <- x0.square()"))?;
+        let x0_3 = x0_2.mul(&mut cs.namespace(|| "x0_3 <- x0_2 * x0"), &x0)?;
+        let ax0 = x0.mul(&mut cs.namespace(|| "ax0 <- x0 * a"), &a)?;
+        let gx0 = x0_3.add(&mut cs.namespace(|| "g <- x0_3 + b"), &b)?;
+        let gx0 = gx0.add(&mut cs.namespace(|| "g <- g + ax0"), &ax0)?;
+
+        // X1(u) = z * u^2 * X0(u)
+        let x1 = x0.mul(&mut cs.namespace(|| "x1 <- x0 * z_u2"), &z_u2)?;
+
+        let z3_u6 = z2_u4.mul(&mut cs.namespace(|| "z3_u6 <- z2_u4 * z_u2"), &z_u2)?;
+        // g(X1(u)) = z^3 * u^6 * g(X0(u))
+        let gx1 = z3_u6.mul(&mut cs.namespace(|| "gx1 <- z3_u6 * gx0"), &gx0)?;
+
+        // z^3 is not a square, so one of gX0, gX1 must be a square
+        // since p = 3 mod 4, the square roots are computed out of circuit as g^((p + 1) / 4)
+        let gx0_n = BlsFp::try_from(&gx0)?;
+        let gx1_n = BlsFp::try_from(&gx1)?;
+        let sqrt0 = gx0_n.sqrt();
+        let is_square0_val: bool = sqrt0.is_some().into();
+        let mut outy_val = if is_square0_val {
+            sqrt0.unwrap()
+        } else {
+            let sqrt1 = gx1_n.sqrt();
+            assert!(
+                bool::from(sqrt1.is_some()),
+                "one of gX0 or gX1 must be a square"
+            );
+            sqrt1.unwrap()
+        };
+        let is_square0 = Boolean::from(AllocatedBit::alloc(
+            &mut cs.namespace(|| "is_square0"),
+            Some(is_square0_val),
+        )?);
+
+        let x0 = x0.reduce(&mut cs.namespace(|| "x0 <- x0.reduce()"))?;
+        let x1 = x1.reduce(&mut cs.namespace(|| "x1 <- x1.reduce()"))?;
+
+        // X = X0 if isSquare == 1, else X = X1
+        let outx = FpElement::conditionally_select(
+            &mut cs.namespace(|| "outx <- select(x1, x0, is_square0)"),
+            &x1,
+            &x0,
+            &is_square0,
+        )?;
+
+        let sgn_u = u.sgn0(&mut cs.namespace(|| "sgn_u <- u.sgn0()"))?;
+
+        let u_native = BlsFp::try_from(u)?;
+        let y_sgn0: bool = outy_val.sgn0().into();
+        let u_sgn0: bool = u_native.sgn0().into();
+        if y_sgn0 != u_sgn0 {
+            outy_val = outy_val.neg();
+        }
+        let outy = FpElement::alloc_element(
+            &mut cs.namespace(|| "alloc outy <- outy_val"),
+            &Some(outy_val),
+        )?;
+
+        // enforce that Y^2 = g(X)
+        let y_sq = outy.square(&mut cs.namespace(|| "y_sq <- outy.square()"))?;
+        let gx0 = gx0.reduce(&mut cs.namespace(|| "gx0 <- gx0.reduce()"))?;
+        let gx1 = gx1.reduce(&mut cs.namespace(|| "gx1 <- gx1.reduce()"))?;
+        let gx = FpElement::conditionally_select(
+            &mut cs.namespace(|| "gx <- select(gx1, gx0, is_square0)"),
+            &gx1,
+            &gx0,
+            &is_square0,
+        )?;
+        FpElement::assert_is_equal(&mut cs.namespace(|| "y_sq == gx"), &y_sq, &gx)?;
+
+        // sgn0(Y) == sgn0(u)
+        let sgn_y = outy.sgn0(&mut cs.namespace(|| "sgn_y <- outy.sgn0()"))?;
+        Boolean::enforce_equal(&mut cs.namespace(|| "sgn_y == sgn_u"), &sgn_y, &sgn_u)?;
+
+        Ok(G1IsoPoint(Self { x: outx, y: outy }))
+    }
+
+    /// Maps points from the 11-isogenous curve E1' to the main curve E1.
+    ///
+    /// Assumes that the point is not the point at infinity. Returns the point
+    /// at infinity if the input is one of the isogeny poles.
+    ///
+    /// References:
+    ///   * [Appendix E.2 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-11-isogeny-map-for-bls12-381)
+    ///   * [Section 4.3 of Wahby-Boneh](https://eprint.iacr.org/2019/403.pdf)
+    pub fn iso11_map<CS>(cs: &mut CS, p: &G1IsoPoint<F>) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        // list of coefficients from appendix E.2 of the RFC, without the leading 1 of the
+        // denominators
+        let iso11_coeffs: [&[&str]; 4] = [
+            &[
+                "2712959285290305970661081772124144179193819192423276218370281158706191519995889425075952244140278856085036081760695",
+                "3564859427549639835253027846704205725951033235539816243131874237388832081954622352624080767121604606753339903542203",
+                "2051387046688339481714726479723076305756384619135044672831882917686431912682625619320120082313093891743187631791280",
+                "3612713941521031012780325893181011392520079402153354595775735142359240110423346445050803899623018402874731133626465",
+                "2247053637822768981792833880270996398470828564809439728372634811976089874056583714987807553397615562273407692740057",
+                "3415427104483187489859740871640064348492611444552862448295571438270821994900526625562705192993481400731539293415811",
+                "2067521456483432583860405634125513059912765526223015704616050604591207046392807563217109432457129564962571408764292",
+                "3650721292069012982822225637849018828271936405382082649291891245623305084633066170122780668657208923883092359301262",
+                "1239271775787030039269460763652455868148971086016832054354147730155061349388626624328773377658494412538595239256855",
+                "3479374185711034293956731583912244564891370843071137483962415222733470401948838363051960066766720884717833231600798",
+                "2492756312273161536685660027440158956721981129429869601638362407515627529461742974364729223659746272460004902959995",
+                "1058488477413994682556770863004536636444795456512795473806825292198091015005841418695586811009326456605062948114985",
+            ],
+            &[
+                "1353092447850172218905095041059784486169131709710991428415161466575141675351394082965234118340787683181925558786844",
+                "2822220997908397120956501031591772354860004534930174057793539372552395729721474912921980407622851861692773516917759",
+                "1717937747208385987946072944131378949849282930538642983149296304709633281382731764122371874602115081850953846504985",
+                "501624051089734157816582944025690868317536915684467868346388760435016044027032505306995281054569109955275640941784",
+                "3025903087998593826923738290305187197829899948335370692927241015584233559365859980023579293766193297662657497834014",
+                "2224140216975189437834161136818943039444741035168992629437640302964164227138031844090123490881551522278632040105125",
+                "1146414465848284837484508420047674663876992808692209238763293935905506532411661921697047880549716175045414621825594",
+                "3179090966864399634396993677377903383656908036827452986467581478509513058347781039562481806409014718357094150199902",
+                "1549317016540628014674302140786462938410429359529923207442151939696344988707002602944342203885692366490121021806145",
+                "1442797143427491432630626390066422021593505165588630398337491100088557278058060064930663878153124164818522816175370",
+            ],
+            &[
+                "1393399195776646641963150658816615410692049723305861307490980409834842911816308830479576739332720113414154429643571",
+                "2968610969752762946134106091152102846225411740689724909058016729455736597929366401532929068084731548131227395540630",
+                "122933100683284845219599644396874530871261396084070222155796123161881094323788483360414289333111221370374027338230",
+                "303251954782077855462083823228569901064301365507057490567314302006681283228886645653148231378803311079384246777035",
+                "1353972356724735644398279028378555627591260676383150667237975415318226973994509601413730187583692624416197017403099",
+                "3443977503653895028417260979421240655844034880950251104724609885224259484262346958661845148165419691583810082940400",
+                "718493410301850496156792713845282235942975872282052335612908458061560958159410402177452633054233549648465863759602",
+                "1466864076415884313141727877156167508644960317046160398342634861648153052436926062434809922037623519108138661903145",
+                "1536886493137106337339531461344158973554574987550750910027365237255347020572858445054025958480906372033954157667719",
+                "2171468288973248519912068884667133903101171670397991979582205855298465414047741472281361964966463442016062407908400",
+                "3915937073730221072189646057898966011292434045388986394373682715266664498392389619761133407846638689998746172899634",
+                "3802409194827407598156407709510350851173404795262202653149767739163117554648574333789388883640862266596657730112910",
+                "1707589313757812493102695021134258021969283151093981498394095062397393499601961942449581422761005023512037430861560",
+                "349697005987545415860583335313370109325490073856352967581197273584891698473628451945217286148025358795756956811571",
+                "885704436476567581377743161796735879083481447641210566405057346859953524538988296201011389016649354976986251207243",
+                "3370924952219000111210625390420697640496067348723987858345031683392215988129398381698161406651860675722373763741188",
+            ],
+            &[
+                "3396434800020507717552209507749485772788165484415495716688989613875369612529138640646200921379825018840894888371137",
+                "3907278185868397906991868466757978732688957419873771881240086730384895060595583602347317992689443299391009456758845",
+                "854914566454823955479427412036002165304466268547334760894270240966182605542146252771872707010378658178126128834546",
+                "3496628876382137961119423566187258795236027183112131017519536056628828830323846696121917502443333849318934945158166",
+                "1828256966233331991927609917644344011503610008134915752990581590799656305331275863706710232159635159092657073225757",
+                "1362317127649143894542621413133849052553333099883364300946623208643344298804722863920546222860227051989127113848748",
+                "3443845896188810583748698342858554856823966611538932245284665132724280883115455093457486044009395063504744802318172",
+                "3484671274283470572728732863557945897902920439975203610275006103818288159899345245633896492713412187296754791689945",
+                "3755735109429418587065437067067640634211015783636675372165599470771975919172394156249639331555277748466603540045130",
+                "3459661102222301807083870307127272890283709299202626530836335779816726101522661683404130556379097384249447658110805",
+                "742483168411032072323733249644347333168432665415341249073150659015707795549260947228694495111018381111866512337576",
+                "1662231279858095762833829698537304807741442669992646287950513237989158777254081548205552083108208170765474149568658",
+                "1668238650112823419388205992952852912407572045257706138925379268508860023191233729074751042562151098884528280913356",
+                "369162719928976119195087327055926326601627748362769544198813069133429557026740823593067700396825489145575282378487",
+                "2164195715141237148945939585099633032390257748382945597506236650132835917087090097395995817229686247227784224263055",
+            ],
+        ];
+        let iso11_coeffs = iso11_coeffs
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .map(|v| FpElement::<F>::from_dec(v).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // x = x_num / x_den
+        // y = y' * y_num / y_den
+        // x_num = sum_{i=0}^11 coeffs[0][i] * x'^i
+        // x_den = x'^10 + sum_{i=0}^9 coeffs[1][i] * x'^i
+        // y_num = sum_{i=0}^15 coeffs[2][i] * x'^i
+        // y_den = x'^15 + sum_{i=0}^14 coeffs[3][i] * x'^i
+
+        let mut xp_pow = vec![p.0.x.clone()];
+        for i in 2..=15 {
+            let xp = xp_pow[i - 2].mul(
+                &mut cs.namespace(|| format!("xp{i} <- xp{} * P.x", i - 1)),
+                &p.0.x,
+            )?;
+            xp_pow.push(xp);
+        }
+
+        let mut x_frac = vec![];
+        for (i, coeffs) in iso11_coeffs.iter().enumerate() {
+            let mut x_f = coeffs[0].clone();
+            for (j, (coeff, xp)) in coeffs[1..].iter().zip(&xp_pow).enumerate() {
+                let coeff_xp = coeff.mul(
+                    &mut cs.namespace(|| {
+                        format!("coeff_xp_{i}_{j} <- coeffs[{i}][{}] * xp_pow[{j}]", j + 1)
+                    }),
+                    xp,
+                )?;
+                x_f = x_f.add(
+                    &mut cs.namespace(|| format!("x_f_{i} <- x_f_{i} + coeff_xp_{i}_{j}")),
+                    &coeff_xp,
+                )?;
+            }
+            x_frac.push(x_f);
+        }
+        x_frac[1] = x_frac[1].add(&mut cs.namespace(|| "x_f_1 <- x_f_1 + xp10"), &xp_pow[9])?;
+        x_frac[3] = x_frac[3].add(&mut cs.namespace(|| "x_f_3 <- x_f_3 + xp15"), &xp_pow[14])?;
+
+        let den_0 = x_frac[1].reduce(&mut cs.namespace(|| "den_0 <- x_f_1.reduce()"))?;
+        let den_1 = x_frac[3].reduce(&mut cs.namespace(|| "den_1 <- x_f_3.reduce()"))?;
+        let den_0_is_zero = den_0.alloc_is_zero(&mut cs.namespace(|| "den_0_is_zero"))?;
+        let den_1_is_zero = den_1.alloc_is_zero(&mut cs.namespace(|| "den_1_is_zero"))?;
+        let is_infinity = Boolean::or(
+            &mut cs.namespace(|| "is_infinity <- or(den_0_is_zero, den_1_is_zero)"),
+            &Boolean::from(den_0_is_zero),
+            &Boolean::from(den_1_is_zero),
+        )?;
+        let den_0 = FpElement::conditionally_select(
+            &mut cs.namespace(|| "den_0 <- select(den_0, 1, is_infinity)"),
+            &den_0,
+            &FpElement::one(),
+            &is_infinity,
+        )?;
+        let den_1 = FpElement::conditionally_select(
+            &mut cs.namespace(|| "den_1 <- select(den_1, 1, is_infinity)"),
+            &den_1,
+            &FpElement::one(),
+            &is_infinity,
+        )?;
+
+        // num / den if den != 0, else num / 1
+        let x = x_frac[0].div_unchecked(&mut cs.namespace(|| "x <- num_0 div den_0"), &den_0)?;
+        let y_frac =
+            x_frac[2].div_unchecked(&mut cs.namespace(|| "y_frac <- num_1 div den_1"), &den_1)?;
+        let y =
+            p.0.y
+                .mul(&mut cs.namespace(|| "y <- P.y * y_frac"), &y_frac)?;
+
+        let res = Self { x, y }.reduce(&mut cs.namespace(|| "res <- res.reduce()"))?;
+        Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, 0, is_infinity)"),
+            &res,
+            &Self::identity(),
+            &is_infinity,
+        )
+    }
+
+    /// Clears the cofactor of a point to ensure it lies in the proper E1
+    /// subgroup. Returns `[1 - x]P` where `x` is the BLS parameter for BLS12-381,
+    /// `-15132376222941642752`, i.e. `[h_eff]P` with `h_eff = 0xd201000000010001`
+    pub fn clear_cofactor<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let cs = &mut cs.namespace(|| "G1::clear_cofactor(p)");
+        // 0xd201 = 0b1101001 << 9 + 1
+        let z = self.double(&mut cs.namespace(|| "z <- p.double()"))?;
+        let z = self.add(&mut cs.namespace(|| "z <- p + z"), &z)?;
+        let z = z.double(&mut cs.namespace(|| "z <- z.double()"))?;
+        let z = z.double_and_add(&mut cs.namespace(|| "z <- z.double_and_add(p) 1"), self)?;
+        let z = z.double_n(&mut cs.namespace(|| "z <- z.double_n(2)"), 2)?;
+        let z = z.double_and_add(&mut cs.namespace(|| "z <- z.double_and_add(p) 2"), self)?;
+        let z = z.double_n(&mut cs.namespace(|| "z <- z.double_n(8)"), 8)?;
+        let z = z.double_and_add(&mut cs.namespace(|| "z <- z.double_and_add(p) 3"), self)?;
+        // 0xd201000000010001 = 0xd201 << 48 + 1 << 16 + 1
+        let z = z.double_n(&mut cs.namespace(|| "z <- z.double_n(31)"), 31)?;
+        let z = z.double_and_add(&mut cs.namespace(|| "z <- z.double_and_add(p) 4"), self)?;
+        let z = z.double_n(&mut cs.namespace(|| "z <- z.double_n(15)"), 15)?;
+        let z = z.double_and_add(&mut cs.namespace(|| "z <- z.double_and_add(p) 5"), self)?;
+
+        Ok(z)
+    }
+
+    /// Given two Fp field elements `u0` and `u1`, calculate a point in G1 of BLS12-381.
+    /// Corresponds to the steps of `hash_to_curve` after `hash_to_field` from
+    /// [section 3 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-encoding-byte-strings-to-el).
+    pub fn map_to_g1<CS>(
+        cs: &mut CS,
+        u0: &FpElement<F>,
+        u1: &FpElement<F>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let q0 = Self::opt_simple_swu1(&mut cs.namespace(|| "q0 <- u0.opt_simple_swu1()"), u0)?;
+        let q1 = Self::opt_simple_swu1(&mut cs.namespace(|| "q1 <- u1.opt_simple_swu1()"), u1)?;
+        // we can use the regular addition function before calling iso11_map
+        let z = G1IsoPoint(q0.0.add(&mut cs.namespace(|| "z <- q0 + q1"), &q1.0)?);
+
+        let z = Self::iso11_map(&mut cs.namespace(|| "z <- z.iso11_map()"), &z)?;
+
+        let z = z.clear_cofactor(&mut cs.namespace(|| "z <- z.clear_cofactor()"))?;
+
+        Ok(z)
+    }
+
+    /// Hashes `msg` to a point in G1 of BLS12-381 using the `BLS12381G1_XMD:SHA-256_SSWU_RO_`
+    /// suite from [section 8.8.1 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-bls12-381-g1),
+    /// with the domain separation tag `dst`.
+    ///
+    /// The bits of the message are given in the order expected by the SHA-256 gadget, i.e. the
+    /// bytes of the message with the bits of each byte in big-endian order.
+    pub fn hash_to_g1<CS>(cs: &mut CS, msg: &[Boolean], dst: &[u8]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let cs = &mut cs.namespace(|| "G1::hash_to_g1(msg, dst)");
+        let u = hash_to_field_fp(
+            &mut cs.namespace(|| "u <- hash_to_field(msg, 2)"),
+            XmdHash::Sha256,
+            msg,
+            dst,
+            2,
+        )?;
+        let res = Self::map_to_g1(&mut cs.namespace(|| "map_to_g1(u[0], u[1])"), &u[0], &u[1])?;
+        // the coordinates are reduced for the point to be usable in the Miller loop
+        let res = res.reduce(&mut cs.namespace(|| "res <- res.reduce()"))?;
+        Ok(res)
+    }
+
     /// Asserts that `phi(P) == [-x^2]P`
     pub fn assert_subgroup_check<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
     where
@@ -538,25 +1016,88 @@ impl<F: PrimeFieldBits> G1Point<F> {
     where
         CS: ConstraintSystem<F>,
     {
-        let y_2 = self.y.square(&mut cs.namespace(|| "y_2 <- p.y.square()"))?;
-        let x_2 = self.x.square(&mut cs.namespace(|| "x_2 <- p.x.square()"))?;
-        let x_3 = self.x.mul(&mut cs.namespace(|| "x_3 <- x * x_2"), &x_2)?;
-        let ax = self
-            .x
-            .mul(&mut cs.namespace(|| "ax <- x * a"), &Bls12381G1Params::a())?;
-        let rhs = x_3.add(&mut cs.namespace(|| "rhs <- x_3 + ax"), &ax)?;
-        let rhs = rhs.add(
-            &mut cs.namespace(|| "rhs <- rhs + b"),
-            &Bls12381G1Params::b(),
+        G1Weierstrass::from(self).assert_is_on_curve(cs)
+    }
+
+    /// Returns the point of the 48-byte compressed encoding in the ZCash serialization format,
+    /// given as its 384 big-endian bits. The flags and the canonicity of `x` are enforced and `y`
+    /// is the square root of `x^3 + 4` whose lexicographic sign matches the sort flag. Like
+    /// `alloc_element`, the point is not checked to be in the prime order subgroup.
+    pub fn from_compressed_bits<CS>(cs: &mut CS, bits: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if bits.len() != 384 {
+            eprintln!("Compressed G1 points are encoded with 384 bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let x_bits = bits[3..].iter().rev().cloned().collect::<Vec<_>>();
+        let x = FpElement::from_canonical_bits_le(&mut cs.namespace(|| "x <- bits"), &x_bits)?;
+        let x_is_zero = x.alloc_is_zero(&mut cs.namespace(|| "x_is_zero <- x.is_zero()"))?;
+        let (infinity, sort) = enforce_compression_flags(
+            &mut cs.namespace(|| "enforce flags"),
+            &bits[..3],
+            &x_is_zero,
         )?;
-        FpElement::assert_is_equal(&mut cs.namespace(|| "y_2 == rhs"), &y_2, &rhs)?;
-        Ok(())
+
+        // invalid encodings get y = 0, which does not satisfy the constraints below
+        let y_value = bits_to_bytes_be(bits).map(|bytes| {
+            let bytes: [u8; 48] = bytes.try_into().unwrap();
+            Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(&bytes))
+                .filter(|p| !bool::from(p.is_identity()))
+                .map_or(BlsFp::zero(), |p| p.y)
+        });
+        let y = FpElement::alloc_element(&mut cs.namespace(|| "alloc y"), &y_value)?;
+        let y = FpElement::conditionally_select(
+            &mut cs.namespace(|| "y <- select(y, 0, infinity)"),
+            &y,
+            &FpElement::zero(),
+            &infinity,
+        )?;
+        let p = Self { x, y };
+
+        // the point at infinity is not on the curve, so the check is done on the generator
+        let q = Self::conditionally_select(
+            &mut cs.namespace(|| "q <- select(p, g1, infinity)"),
+            &p,
+            &Self::from(&G1Affine::generator()),
+            &infinity,
+        )?;
+        q.assert_is_on_curve(&mut cs.namespace(|| "q.assert_is_on_curve()"))?;
+        let largest =
+            p.y.lexicographically_largest(&mut cs.namespace(|| "y.lexicographically_largest()"))?;
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "sort flag == y.lexicographically_largest()"),
+            &sort,
+            &largest,
+        )?;
+
+        Ok(p)
+    }
+
+    /// Returns the 384 big-endian bits of the 48-byte compressed encoding of the point in the
+    /// ZCash serialization format
+    pub fn to_compressed_bits<CS>(&self, cs: &mut CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let infinity = self.alloc_is_identity(&mut cs.namespace(|| "p.is_identity()"))?;
+        let sort = self
+            .y
+            .lexicographically_largest(&mut cs.namespace(|| "y.lexicographically_largest()"))?;
+        let x_bits = self.x.to_bits_le(&mut cs.namespace(|| "x.to_bits_le()"))?;
+
+        let mut bits = compression_flags(&infinity, &sort);
+        bits.extend(x_bits.into_iter().rev());
+        Ok(bits)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fields::fp::bigint_to_fpelem;
     use bellpepper_core::test_cs::TestConstraintSystem;
     use bls12_381::Scalar;
     use ff::Field;
@@ -634,8 +1175,61 @@ mod tests {
         }
         assert!(cs.is_satisfied());
         expect_eq(cs.num_inputs(), &expect!["1"]);
-        expect_eq(cs.scalar_aux().len(), &expect!["24354"]);
-        expect_eq(cs.num_constraints(), &expect!["24568"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["31414"]);
+        expect_eq(cs.num_constraints(), &expect!["31696"]);
+    }
+
+    #[test]
+    fn test_random_add_unified_constant() {
+        let mut rng = rand::thread_rng();
+        let a = G1Projective::random(&mut rng);
+        let b = G1Projective::random(&mut rng);
+        let c = a + b;
+        let a = G1Affine::from(a);
+        let b = G1Affine::from(b);
+        let c = G1Affine::from(c);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_const = G1Point::<Fp>::from(&a);
+        let b_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res1_alloc = a_const
+            .add_unified(&mut cs.namespace(|| "a+b"), &b_alloc)
+            .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "a+b = c"), &res1_alloc, &c_alloc).unwrap();
+        let res2_alloc = b_alloc
+            .add_unified(&mut cs.namespace(|| "b+0"), &G1Point::identity())
+            .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "b+0 = b"), &res2_alloc, &b_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["15592"]);
+        expect_eq(cs.num_constraints(), &expect!["15712"]);
+    }
+
+    #[test]
+    fn test_add_unified_opposite_y() {
+        let mut rng = rand::thread_rng();
+        let a = G1Affine::from(G1Projective::random(&mut rng));
+        // b = -phi(a) has the opposite y coordinate of a but b != -a
+        let b = -bls12_381::g1::endomorphism(&a);
+        let c = G1Affine::from(G1Projective::from(a) + G1Projective::from(b));
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let b_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a+b"), &b_alloc)
+            .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "a+b = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
     }
 
     #[test]
@@ -862,8 +1456,8 @@ mod tests {
         }
         assert!(cs.is_satisfied());
         expect_eq(cs.num_inputs(), &expect!["1"]);
-        expect_eq(cs.scalar_aux().len(), &expect!["7808"]);
-        expect_eq(cs.num_constraints(), &expect!["7881"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["9578"]);
+        expect_eq(cs.num_constraints(), &expect!["9668"]);
     }
 
     #[test]
@@ -919,7 +1513,8 @@ mod tests {
     #[test]
     fn test_random_subgroup_check_negative() {
         use crate::curves::params::EmulatedCurveParams;
-        let b = BlsFp::try_from(&Bls12381G1Params::<Fp>::b()).unwrap();
+        let b: FpElement<Fp> = Bls12381G1Params::b();
+        let b = BlsFp::try_from(&b).unwrap();
         use rand::RngCore;
         let mut rng = rand::thread_rng();
         let mut random_point = || loop {
@@ -970,4 +1565,577 @@ mod tests {
         expect_eq(cs.scalar_aux().len(), &expect!["986"]);
         expect_eq(cs.num_constraints(), &expect!["968"]);
     }
+
+    fn scalar_to_bits(n: &Scalar, num_bits: usize) -> Vec<bool> {
+        let bytes = n.to_bytes();
+        (0..num_bits)
+            .map(|i| (bytes[i / 8] >> (i % 8)) & 1 == 1)
+            .collect()
+    }
+
+    fn alloc_bits(cs: &mut TestConstraintSystem<Fp>, name: &str, bits: &[bool]) -> Vec<Boolean> {
+        bits.iter()
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("alloc {name} bit {i}")), Some(*b))
+                        .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_random_double_scalar_mul() {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        let q = G1Projective::random(&mut rng);
+        // 64-bit scalars keep the size of the test constraint system manageable
+        let a = Scalar::from(rng.next_u64());
+        let b = Scalar::from(rng.next_u64());
+        let c = G1Affine::from(p * a + q * b);
+        let p = G1Affine::from(p);
+        let q = G1Affine::from(q);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_bits = alloc_bits(&mut cs, "a", &scalar_to_bits(&a, 64));
+        let b_bits = alloc_bits(&mut cs, "b", &scalar_to_bits(&b, 64));
+        let p_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+        let q_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc q"), &Some(q)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = G1Point::double_scalar_mul(
+            &mut cs.namespace(|| "[a]p + [b]q"),
+            &p_alloc,
+            &a_bits,
+            &q_alloc,
+            &b_bits,
+        )
+        .unwrap();
+        G1Point::assert_is_equal(
+            &mut cs.namespace(|| "[a]p + [b]q = c"),
+            &res_alloc,
+            &c_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["790676"]);
+        expect_eq(cs.num_constraints(), &expect!["799122"]);
+    }
+
+    #[test]
+    fn test_double_scalar_mul_exceptional_cases() {
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        let cases = [
+            // P == Q
+            (p, p, 0x9c, 0x5b),
+            // [a]P == [b]Q
+            (p, p.double(), 6, 3),
+            // [a]P == -[b]Q
+            (p, -p, 5, 5),
+            // P is the identity
+            (G1Projective::identity(), p, 0xff, 0x17),
+            // zero scalars
+            (p, p.double(), 0, 0),
+        ];
+
+        for (i, (p, q, a, b)) in cases.into_iter().enumerate() {
+            let c = G1Affine::from(p * Scalar::from(a) + q * Scalar::from(b));
+            let (p, q) = (G1Affine::from(p), G1Affine::from(q));
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let a_bits = alloc_bits(&mut cs, "a", &scalar_to_bits(&Scalar::from(a), 8));
+            let b_bits = alloc_bits(&mut cs, "b", &scalar_to_bits(&Scalar::from(b), 8));
+            let p_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+            let q_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc q"), &Some(q)).unwrap();
+            let c_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc = G1Point::double_scalar_mul_windowed(
+                &mut cs.namespace(|| "[a]p + [b]q"),
+                &p_alloc,
+                &a_bits,
+                &q_alloc,
+                &b_bits,
+                1 + i % 2,
+            )
+            .unwrap();
+            G1Point::assert_is_equal(
+                &mut cs.namespace(|| "[a]p + [b]q = c"),
+                &res_alloc,
+                &c_alloc,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("case {i}: {:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_random_scalar_mul_windowed() {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        // 64-bit scalars keep the size of the test constraint system manageable
+        let k = Scalar::from(rng.next_u64());
+        let c = G1Affine::from(p * k);
+        let p = G1Affine::from(p);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_bits(&mut cs, "k", &scalar_to_bits(&k, 64));
+        let p_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = p_alloc
+            .scalar_mul_windowed(&mut cs.namespace(|| "[k]p"), &k_bits, 4)
+            .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "[k]p = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["661608"]);
+        expect_eq(cs.num_constraints(), &expect!["668708"]);
+    }
+
+    #[test]
+    fn test_scalar_mul_windowed_exceptional_cases() {
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        let cases = [
+            (p, 0u64, 7, 3),
+            (p, 1, 1, 2),
+            (p, 0x5c, 7, 3),
+            (G1Projective::identity(), 0x5c, 7, 3),
+            (p, 2, 2, 1),
+        ];
+
+        for (i, (p, k, num_bits, window_size)) in cases.into_iter().enumerate() {
+            let c = G1Affine::from(p * Scalar::from(k));
+            let p = G1Affine::from(p);
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let k_bits = alloc_bits(&mut cs, "k", &scalar_to_bits(&Scalar::from(k), num_bits));
+            let p_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+            let c_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc = p_alloc
+                .scalar_mul_windowed(&mut cs.namespace(|| "[k]p"), &k_bits, window_size)
+                .unwrap();
+            G1Point::assert_is_equal(&mut cs.namespace(|| "[k]p = c"), &res_alloc, &c_alloc)
+                .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("case {i}: {:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_random_glv_scalar_mul() {
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        let k = Scalar::random(&mut rng);
+        let c = G1Affine::from(p * k);
+        let p = G1Affine::from(p);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_bits(&mut cs, "k", &scalar_to_bits(&k, 255));
+        let p_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = p_alloc
+            .scalar_mul(&mut cs.namespace(|| "[k]p"), &k_bits)
+            .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "[k]p = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1510692"]);
+        expect_eq(cs.num_constraints(), &expect!["1526851"]);
+    }
+
+    #[test]
+    fn test_glv_scalar_mul_wrong_decomposition() {
+        let mut rng = rand::thread_rng();
+        let p = G1Affine::from(G1Projective::random(&mut rng));
+        let k = Scalar::random(&mut rng);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_bits(&mut cs, "k", &scalar_to_bits(&k, 255));
+        let p_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+        p_alloc
+            .scalar_mul(&mut cs.namespace(|| "[k]p"), &k_bits)
+            .unwrap();
+        assert!(cs.is_satisfied());
+
+        // flipping a bit of k1 breaks k = k1 + lambda*k2
+        let path =
+            "[k]p/G1::glv_scalar_mul(p, k)/(k1, k2) <- decompose(k)/alloc bit 0 of k1/boolean";
+        let bit = cs.get(path);
+        cs.set(path, Fp::ONE - bit);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_opt_simple_swu1() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp::random(&mut rng);
+        let c = bls12_381::hash_to_curve::map_g1::map_to_curve_simple_swu(&a);
+        let c = G1Affine::from(c);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = FpElement::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc =
+            G1Point::opt_simple_swu1(&mut cs.namespace(|| "opt_simple_swu1(a)"), &a_alloc).unwrap();
+        G1Point::assert_is_equal(
+            &mut cs.namespace(|| "opt_simple_swu1(a) = c"),
+            &res_alloc.0,
+            &c_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["48152"]);
+        expect_eq(cs.num_constraints(), &expect!["48356"]);
+    }
+
+    #[test]
+    fn test_random_iso11_map() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp::random(&mut rng);
+        let a = bls12_381::hash_to_curve::map_g1::map_to_curve_simple_swu(&a); // this ensures a is in E1'
+        let c = bls12_381::hash_to_curve::map_g1::iso_map(&a);
+        let a = G1Affine::from(a);
+        let c = G1Affine::from(c);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            G1IsoPoint(G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap());
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = G1Point::iso11_map(&mut cs.namespace(|| "iso11_map(a)"), &a_alloc).unwrap();
+        G1Point::assert_is_equal(
+            &mut cs.namespace(|| "iso11_map(a) = c"),
+            &res_alloc,
+            &c_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["140659"]);
+        expect_eq(cs.num_constraints(), &expect!["141268"]);
+    }
+
+    #[test]
+    fn test_random_clear_cofactor() {
+        use bls12_381::hash_to_curve::MapToCurve;
+        let mut rng = rand::thread_rng();
+        // a point on E1 which is not necessarily in G1
+        let a = G1Projective::map_to_curve(&BlsFp::random(&mut rng));
+        let c = G1Affine::from(a.clear_h());
+        let a = G1Affine::from(a);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .clear_cofactor(&mut cs.namespace(|| "a.clear_cofactor()"))
+            .unwrap();
+        G1Point::assert_is_equal(
+            &mut cs.namespace(|| "a.clear_cofactor() = c"),
+            &res_alloc,
+            &c_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["314509"]);
+        expect_eq(cs.num_constraints(), &expect!["316029"]);
+    }
+
+    #[test]
+    fn test_random_map_to_g1() {
+        use bls12_381::hash_to_curve::MapToCurve;
+        let mut rng = rand::thread_rng();
+        let x = BlsFp::random(&mut rng);
+        let y = BlsFp::random(&mut rng);
+        let p1 = G1Projective::map_to_curve(&x);
+        let p2 = G1Projective::map_to_curve(&y);
+        let c = (p1 + p2).clear_h();
+        let c = G1Affine::from(c);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let x_alloc = FpElement::alloc_element(&mut cs.namespace(|| "alloc x"), &Some(x)).unwrap();
+        let y_alloc = FpElement::alloc_element(&mut cs.namespace(|| "alloc y"), &Some(y)).unwrap();
+        let c_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc =
+            G1Point::map_to_g1(&mut cs.namespace(|| "map_to_g1(x, y)"), &x_alloc, &y_alloc)
+                .unwrap();
+        G1Point::assert_is_equal(
+            &mut cs.namespace(|| "map_to_g1(x, y) = c"),
+            &res_alloc,
+            &c_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["508492"]);
+        expect_eq(cs.num_constraints(), &expect!["510900"]);
+    }
+
+    // Test vectors for BLS12381G1_XMD:SHA-256_SSWU_RO_ from appendix J.9.1 of RFC 9380
+    // (u[0], u[1], P.x, P.y) with DST = "QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_"
+    const RFC9380_G1_VECTORS: [(&str, &str, &str, &str); 5] = [
+        // msg = ""
+        (
+            "0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f",
+            "019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9",
+            "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+            "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+        ),
+        // msg = "abc"
+        (
+            "0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951",
+            "003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139",
+            "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+            "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+        ),
+        // msg = "abcdef0123456789"
+        (
+            "062d1865eb80ebfa73dcfc45db1ad4266b9f3a93219976a3790ab8d52d3e5f1e62f3b01795e36834b17b70e7b76246d4",
+            "0cdc3e2f271f29c4ff75020857ce6c5d36008c9b48385ea2f2bf6f96f428a3deb798aa033cd482d1cdc8b30178b08e3a",
+            "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98",
+            "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709",
+        ),
+        // msg = "q128_" followed by 128 "q"
+        (
+            "010476f6a060453c0b1ad0b628f3e57c23039ee16eea5e71bb87c3b5419b1255dc0e5883322e563b84a29543823c0e86",
+            "0b1a912064fb0554b180e07af7e787f1f883a0470759c03c1b6509eb8ce980d1670305ae7b928226bb58fdc0a419f46e",
+            "15f68eaa693b95ccb85215dc65fa81038d69629f70aeee0d0f677cf22285e7bf58d7cb86eefe8f2e9bc3f8cb84fac488",
+            "1807a1d50c29f430b8cafc4f8638dfeeadf51211e1602a5f184443076715f91bb90a48ba1e370edce6ae1062f5e6dd38",
+        ),
+        // msg = "a512_" followed by 512 "a"
+        (
+            "0a8ffa7447f6be1c5a2ea4b959c9454b431e29ccc0802bc052413a9c5b4f9aac67a93431bd480d15be1e057c8a08e8c6",
+            "05d487032f602c90fa7625dbafe0f4a49ef4a6b0b33d7bb349ff4cf5410d297fd6241876e3e77b651cfc8191e40a68b7",
+            "082aabae8b7dedb0e78aeb619ad3bfd9277a2f77ba7fad20ef6aabdc6c31d19ba5a6d12283553294c1825c4b3ca2dcfe",
+            "05b84ae5a942248eea39e1d91030458c40153f3b654ab7872d779ad1e942856a20c438e8d99bc8abfbf74729ce1f7ac8",
+        ),
+    ];
+
+    fn fp_from_hex(v: &str) -> BlsFp {
+        bigint_to_fpelem(&BigInt::parse_bytes(v.as_bytes(), 16).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_map_to_g1_rfc9380_vectors() {
+        for (i, (u0, u1, px, py)) in RFC9380_G1_VECTORS.iter().enumerate() {
+            let c = G1Affine {
+                x: fp_from_hex(px),
+                y: fp_from_hex(py),
+                infinity: 0.into(),
+            };
+            assert!(bool::from(c.is_on_curve()));
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let u0_alloc =
+                FpElement::alloc_element(&mut cs.namespace(|| "alloc u0"), &Some(fp_from_hex(u0)))
+                    .unwrap();
+            let u1_alloc =
+                FpElement::alloc_element(&mut cs.namespace(|| "alloc u1"), &Some(fp_from_hex(u1)))
+                    .unwrap();
+            let c_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc = G1Point::map_to_g1(
+                &mut cs.namespace(|| "map_to_g1(u0, u1)"),
+                &u0_alloc,
+                &u1_alloc,
+            )
+            .unwrap();
+            G1Point::assert_is_equal(
+                &mut cs.namespace(|| "map_to_g1(u0, u1) = c"),
+                &res_alloc,
+                &c_alloc,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("vector {i}: {:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_hash_to_g1_rfc9380_vectors() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        for (msg, (_, _, px, py)) in ["", "abc"].iter().zip(RFC9380_G1_VECTORS.iter()) {
+            let c = G1Affine {
+                x: fp_from_hex(px),
+                y: fp_from_hex(py),
+                infinity: 0.into(),
+            };
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let msg_bits = msg
+                .bytes()
+                .enumerate()
+                .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+                .map(|(i, j, b)| {
+                    Boolean::from(
+                        AllocatedBit::alloc(
+                            cs.namespace(|| format!("alloc bit {j} of msg byte {i}")),
+                            Some(b),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let c_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc =
+                G1Point::hash_to_g1(&mut cs.namespace(|| "hash_to_g1(msg)"), &msg_bits, DST)
+                    .unwrap();
+            G1Point::assert_is_equal(
+                &mut cs.namespace(|| "hash_to_g1(msg) = c"),
+                &res_alloc,
+                &c_alloc,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+            if msg.is_empty() {
+                expect_eq(cs.num_inputs(), &expect!["1"]);
+                expect_eq(cs.scalar_aux().len(), &expect!["511840"]);
+                expect_eq(cs.num_constraints(), &expect!["514584"]);
+            }
+        }
+    }
+
+    fn alloc_bytes_be(cs: &mut TestConstraintSystem<Fp>, bytes: &[u8]) -> Vec<Boolean> {
+        bytes
+            .iter()
+            .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1))
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("alloc bit {i}")), Some(b))
+                        .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn compressed_helper(bytes: &[u8; 48]) -> (TestConstraintSystem<Fp>, G1Point<Fp>) {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = alloc_bytes_be(&mut cs, bytes);
+        let p = G1Point::from_compressed_bits(&mut cs.namespace(|| "from_compressed_bits"), &bits)
+            .unwrap();
+        (cs, p)
+    }
+
+    #[test]
+    fn test_random_compressed_encoding() {
+        let mut rng = rand::thread_rng();
+        let a = G1Affine::from(G1Projective::random(&mut rng));
+        let bytes = a.to_compressed();
+
+        let (mut cs, a_alloc) = compressed_helper(&bytes);
+        assert_eq!(G1Affine::try_from(&a_alloc).unwrap(), a);
+        let bits = a_alloc
+            .to_compressed_bits(&mut cs.namespace(|| "to_compressed_bits"))
+            .unwrap();
+        assert_eq!(bits.len(), 384);
+        assert_eq!(bits_to_bytes_be(&bits).unwrap(), bytes.to_vec());
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["5387"]);
+        expect_eq(cs.num_constraints(), &expect!["6037"]);
+    }
+
+    #[test]
+    fn test_compressed_encoding_identity() {
+        let bytes = G1Affine::identity().to_compressed();
+        let (mut cs, a_alloc) = compressed_helper(&bytes);
+        assert_eq!(G1Affine::try_from(&a_alloc).unwrap(), G1Affine::identity());
+        let bits = a_alloc
+            .to_compressed_bits(&mut cs.namespace(|| "to_compressed_bits"))
+            .unwrap();
+        assert_eq!(bits_to_bytes_be(&bits).unwrap(), bytes.to_vec());
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_compressed_encoding_flags() {
+        let mut rng = rand::thread_rng();
+        let bytes = G1Affine::from(G1Projective::random(&mut rng)).to_compressed();
+
+        // compression flag unset
+        let mut invalid = bytes;
+        invalid[0] &= 0x7f;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // the other sort flag encodes the opposite point
+        let mut neg = bytes;
+        neg[0] ^= 0x20;
+        let (cs, a_alloc) = compressed_helper(&neg);
+        assert!(cs.is_satisfied());
+        assert_eq!(
+            G1Affine::try_from(&a_alloc).unwrap(),
+            -G1Affine::from_compressed(&bytes).unwrap()
+        );
+
+        // infinity flag set with a non-zero x
+        let mut invalid = bytes;
+        invalid[0] |= 0x40;
+        invalid[0] &= !0x20;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // infinity flag set with the sort flag
+        let mut invalid = G1Affine::identity().to_compressed();
+        invalid[0] |= 0x20;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // x not reduced modulo p
+        let mut invalid = [0xff; 48];
+        invalid[0] = 0x9f;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // x of a point that is not on the curve
+        let mut invalid = bytes;
+        loop {
+            invalid[48 - 1] = invalid[48 - 1].wrapping_add(1);
+            if bool::from(G1Affine::from_compressed_unchecked(&invalid).is_none()) {
+                break;
+            }
+        }
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+    }
 }
@@ -6,14 +6,24 @@ use bls12_381::hash_to_curve::Sgn0;
 use bls12_381::{G2Affine, G2Projective};
 use ff::PrimeFieldBits;
 use num_bigint::BigInt;
+use num_integer::Integer;
 use num_traits::Zero;
 
 use crate::curves::params::Bls12381G2Params;
-use crate::fields::fp::{big_from_dec, bigint_to_fpelem, fp_from_dec, Bls12381FpParams, FpElement};
+use crate::fields::fp::{
+    big_from_dec, bigint_to_fpelem, fp_from_dec, Bls12381FpParams, Bls12381Fr, Bls12381FrParams,
+    FpElement,
+};
 use crate::fields::fp2::{fp2_from_dec, fp2_pow_vartime, Fp2Element};
+use crate::hash_to_field::{hash_to_field_fp2, XmdHash};
 
+use super::encoding::{bits_to_bytes_be, compression_flags, enforce_compression_flags};
+use super::msm;
 use super::params::EmulatedCurveParams;
 
+/// Number of bits of each part of a scalar decomposed with `G2Point::gls_decompose`
+const GLS_SCALAR_BITS: usize = 64;
+
 /// Represents an affine point on BLS12-381's G2 curve. Point at infinity is represented with (0, 0)
 #[derive(Clone)]
 pub struct G2Point<F: PrimeFieldBits> {
@@ -55,19 +65,43 @@ where
 }
 
 impl<F: PrimeFieldBits> G2Point<F> {
+    pub fn identity() -> Self {
+        // (0,0) is the point at infinity
+        Self {
+            x: Fp2Element::zero(),
+            y: Fp2Element::zero(),
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.x.is_constant() && self.y.is_constant()
+    }
+
     pub fn alloc_element<CS>(cs: &mut CS, value: &Option<G2Affine>) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
     {
-        let vx = value.map(|v| v.x);
-        let vy = value.map(|v| v.y);
         // (0,0) is the point at infinity
+        let (vx, vy) = match value {
+            Some(v) if bool::from(v.is_identity()) => (Some(BlsFp2::zero()), Some(BlsFp2::zero())),
+            Some(v) => (Some(v.x), Some(v.y)),
+            None => (None, None),
+        };
         let x = Fp2Element::<F>::alloc_element(&mut cs.namespace(|| "allocate x (g2)"), &vx)?;
         let y = Fp2Element::<F>::alloc_element(&mut cs.namespace(|| "allocate y (g2)"), &vy)?;
 
         Ok(Self { x, y })
     }
 
+    pub fn alloc_is_identity<CS>(&self, cs: &mut CS) -> Result<AllocatedBit, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self.x.alloc_is_zero(&mut cs.namespace(|| "x =? 0"))?;
+        let y = self.y.alloc_is_zero(&mut cs.namespace(|| "y =? 0"))?;
+        AllocatedBit::and(&mut cs.namespace(|| "and(x, y)"), &x, &y)
+    }
+
     pub fn assert_is_equal<CS>(cs: &mut CS, a: &Self, b: &Self) -> Result<(), SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -392,6 +426,294 @@ impl<F: PrimeFieldBits> G2Point<F> {
         Ok(Self { x: x3, y: y3 })
     }
 
+    /// Returns `p1` if `condition` is true and `p0` otherwise
+    pub fn conditionally_select<CS>(
+        cs: &mut CS,
+        p0: &Self,
+        p1: &Self,
+        condition: &Boolean,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "cond x"),
+            &p0.x,
+            &p1.x,
+            condition,
+        )?;
+        let y = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "cond y"),
+            &p0.y,
+            &p1.y,
+            condition,
+        )?;
+        Ok(Self { x, y })
+    }
+
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    pub fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let inputs_x = inputs.iter().map(|i| i.x.clone()).collect::<Vec<_>>();
+        let inputs_y = inputs.iter().map(|i| i.y.clone()).collect::<Vec<_>>();
+        let x = Fp2Element::mux_tree(
+            &mut cs.namespace(|| "mux x"),
+            select_bits.clone(),
+            &inputs_x,
+        )?;
+        let y = Fp2Element::mux_tree(&mut cs.namespace(|| "mux y"), select_bits, &inputs_y)?;
+        Ok(Self { x, y })
+    }
+
+    /// Returns the EC addition between `self` and `value`. Supports `p == q` and either point can be the identity
+    /// It uses the unified formulas of Brier and Joye from [BriJoy02 (Corollary 1)](https://link.springer.com/content/pdf/10.1007/3-540-45664-3_24.pdf)
+    pub fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let (p, q) = (self, value);
+        let cs = &mut cs.namespace(|| "G2::add_unified(p, q)");
+        let sel1 = p.alloc_is_identity(&mut cs.namespace(|| "sel1 <- p.is_identity()"))?;
+        let sel2 = q.alloc_is_identity(&mut cs.namespace(|| "sel2 <- q.is_identity()"))?;
+
+        // λ = ((p.x+q.x)² - p.x*q.x + a)/(p.y + q.y)
+        let pxqx = p.x.mul(&mut cs.namespace(|| "pxqx <- p.x * q.x"), &q.x)?;
+        let pxplusqx =
+            p.x.add(&mut cs.namespace(|| "pxplusqx <- p.x + q.x"), &q.x)?;
+        let num = pxplusqx.square(&mut cs.namespace(|| "num <- pxplusqx^2"))?;
+        let num = num.sub(&mut cs.namespace(|| "num <- num - pxqx"), &pxqx)?;
+        let denum = p.y.add(&mut cs.namespace(|| "denum <- p.y + q.y"), &q.y)?;
+        let sel3 = denum.alloc_is_zero(&mut cs.namespace(|| "sel3 <- denum.is_zero()"))?;
+        // if p.y + q.y = 0 and p.x != q.x, the unified formula is undefined (this happens when
+        // q = -psi2(p) or q = -psi2(psi2(p))), so use λ = (q.y - p.y)/(q.x - p.x) instead
+        let qypy = q.y.sub(&mut cs.namespace(|| "qypy <- q.y - p.y"), &p.y)?;
+        let qxpx = q.x.sub(&mut cs.namespace(|| "qxpx <- q.x - p.x"), &p.x)?;
+        let sel4 = qxpx.alloc_is_zero(&mut cs.namespace(|| "sel4 <- qxpx.is_zero()"))?;
+        let num = num.reduce(&mut cs.namespace(|| "num <- num.reduce()"))?;
+        let num = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "num <- select(num, qypy, sel3)"),
+            &num,
+            &qypy,
+            &Boolean::from(sel3.clone()),
+        )?;
+        let denum = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "denum <- select(denum, qxpx, sel3)"),
+            &denum,
+            &qxpx,
+            &Boolean::from(sel3.clone()),
+        )?;
+        // if p = -q, assign dummy 1 to denum and continue
+        let sel5 = AllocatedBit::and(
+            &mut cs.namespace(|| "sel5 <- and(sel3, sel4)"),
+            &sel3,
+            &sel4,
+        )?;
+        let denum = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "denum <- select(denum, 1, sel5)"),
+            &denum,
+            &Fp2Element::one(),
+            &Boolean::from(sel5.clone()),
+        )?;
+        let lambda = num.div_unchecked(&mut cs.namespace(|| "lamda <- num div denum"), &denum)?;
+
+        // x = λ^2 - p.x - q.x
+        let xr = lambda.square(&mut cs.namespace(|| "xr <- lambda.square()"))?;
+        let xr = xr.sub(&mut cs.namespace(|| "xr <- xr - pxplusqx"), &pxplusqx)?;
+
+        // y = λ(p.x - xr) - p.y
+        let yr = p.x.sub(&mut cs.namespace(|| "yr <- p.x - xr"), &xr)?;
+        let yr = yr.mul(&mut cs.namespace(|| "yr <- yr * lambda"), &lambda)?;
+        let yr = yr.sub(&mut cs.namespace(|| "yr <- yr - p.y"), &p.y)?;
+        let xr = xr.reduce(&mut cs.namespace(|| "xr <- xr.reduce()"))?;
+        let yr = yr.reduce(&mut cs.namespace(|| "yr <- yr.reduce()"))?;
+        let res = Self { x: xr, y: yr };
+
+        // if p=(0,0) return q
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, q, sel1)"),
+            &res,
+            q,
+            &Boolean::from(sel1),
+        )?;
+        // if q=(0,0) return p
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, p, sel2)"),
+            &res,
+            p,
+            &Boolean::from(sel2),
+        )?;
+        // if p = -q, return (0, 0)
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, 0, sel5)"),
+            &res,
+            &Self::identity(),
+            &Boolean::from(sel5),
+        )?;
+
+        Ok(res)
+    }
+
+    /// Decomposes `k` into `k0 + k1*u + k2*u^2 + k3*u^3 mod r` with `u = -x = 15132376222941642752`
+    /// and each `ki` of 64 bits, returned as little-endian bits. The decomposition is computed
+    /// outside the circuit and enforced.
+    fn gls_decompose<CS>(
+        cs: &mut CS,
+        scalar: &Bls12381Fr<F>,
+    ) -> Result<Vec<Vec<Boolean>>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let u = BigInt::from(15132376222941642752u64);
+        let digits = BigInt::try_from(scalar).ok().map(|k| {
+            let mut k = k.mod_floor(&Bls12381FrParams::modulus());
+            (0..4)
+                .map(|_| {
+                    let (q, d) = k.div_mod_floor(&u);
+                    k = q;
+                    d
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut res = vec![];
+        for i in 0..4 {
+            let bits = (0..GLS_SCALAR_BITS)
+                .map(|j| {
+                    Ok(Boolean::from(AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc bit {j} of k{i}")),
+                        digits.as_ref().map(|d| d[i].bit(j as u64)),
+                    )?))
+                })
+                .collect::<Result<Vec<_>, SynthesisError>>()?;
+            res.push(bits);
+        }
+
+        // Horner's rule: ((k3*u + k2)*u + k1)*u + k0
+        let mut acc = Bls12381Fr::<F>::from_bits_le(CS::one(), &res[3]);
+        for i in (0..3).rev() {
+            let ki = Bls12381Fr::<F>::from_bits_le(CS::one(), &res[i]);
+            acc = acc.mul_const(&mut cs.namespace(|| format!("acc <- acc * u ({i})")), &u)?;
+            acc = acc.add(&mut cs.namespace(|| format!("acc <- acc + k{i}")), &ki)?;
+        }
+        Bls12381Fr::<F>::assert_is_equal(&mut cs.namespace(|| "acc =? k"), &acc, scalar)?;
+
+        Ok(res)
+    }
+
+    /// Returns `[k]P` using the GLS method with the endomorphism `psi`, which acts on G2 as
+    /// multiplication by the BLS parameter `x`. The scalar is split as
+    /// `k = k0 + k1*u + k2*u^2 + k3*u^3 mod r` with `u = -x`, and
+    /// `[k0]P + [k1](-psi(P)) + [k2]psi2(P) + [k3](-psi(psi2(P)))` is computed with a joint lookup
+    /// table of 16 points and 64 shared doublings. Requires `P` to be in the prime-order
+    /// subgroup G2, but `P` and `k` can be zero.
+    pub fn gls_scalar_mul<CS>(
+        &self,
+        cs: &mut CS,
+        scalar: &Bls12381Fr<F>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "G2::gls_scalar_mul(p, k)");
+        let k = Self::gls_decompose(&mut cs.namespace(|| "k <- decompose(k)"), scalar)?;
+
+        let p = self.reduce(&mut cs.namespace(|| "p <- p.reduce()"))?;
+        let p1 = p.psi(&mut cs.namespace(|| "p1 <- p.psi()"))?;
+        let p1 = p1.neg(&mut cs.namespace(|| "p1 <- p1.neg()"))?;
+        let p2 = p.psi2(&mut cs.namespace(|| "p2 <- p.psi2()"))?;
+        let p3 = p2.psi(&mut cs.namespace(|| "p3 <- p2.psi()"))?;
+        let p3 = p3.neg(&mut cs.namespace(|| "p3 <- p3.neg()"))?;
+        let p1 = p1.reduce(&mut cs.namespace(|| "p1 <- p1.reduce()"))?;
+        let p2 = p2.reduce(&mut cs.namespace(|| "p2 <- p2.reduce()"))?;
+        let p3 = p3.reduce(&mut cs.namespace(|| "p3 <- p3.reduce()"))?;
+        let bases = [p, p1, p2, p3];
+
+        // the entry at index m is the sum of the bases[i] for which bit i of m is set
+        let mut table: Vec<Self> = vec![Self::identity()];
+        for (i, base) in bases.iter().enumerate() {
+            table.push(base.clone());
+            for m in 1..(1usize << i) {
+                let point = table[m]
+                    .add_unified(&mut cs.namespace(|| format!("table[{m}] + p{i}")), base)?;
+                table.push(point);
+            }
+        }
+
+        let mut res: Option<Self> = None;
+        for j in (0..GLS_SCALAR_BITS).rev() {
+            let index_bits = [&k[3][j], &k[2][j], &k[1][j], &k[0][j]];
+            let tmp = Self::mux_tree(
+                &mut cs.namespace(|| format!("tmp <- table[bit {j}]")),
+                index_bits.into_iter(), // mux_tree requires MSB first
+                &table,
+            )?;
+
+            res = match res {
+                None => Some(tmp),
+                Some(acc) => {
+                    let acc = acc.add_unified(
+                        &mut cs.namespace(|| format!("res <- res + res ({j})")),
+                        &acc,
+                    )?;
+                    Some(acc.add_unified(
+                        &mut cs.namespace(|| format!("res <- res + tmp ({j})")),
+                        &tmp,
+                    )?)
+                }
+            };
+        }
+
+        Ok(res.unwrap())
+    }
+
+    /// Returns `[k]P` where `k` is given as little-endian bits, using `gls_scalar_mul`.
+    /// Requires `P` to be in the prime-order subgroup G2.
+    pub fn scalar_mul<CS>(&self, cs: &mut CS, scalar: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let k = Bls12381Fr::<F>::from_bits_le(CS::one(), scalar);
+        self.gls_scalar_mul(cs, &k)
+    }
+
+    /// Returns `sum([k_i]P_i)` where the scalars `k_i` are given as little-endian bits, using
+    /// the Straus or Pippenger method depending on the number of points and scalar bits.
+    /// Supports points outside of the prime-order subgroup and the identity.
+    pub fn msm<CS>(
+        cs: &mut CS,
+        points: &[Self],
+        scalars: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        msm::msm(&mut cs.namespace(|| "G2::msm(p, k)"), points, scalars)
+    }
+
+    /// Returns `sum([k_i]B_i)` for constant bases `B_i`, where the scalars `k_i` are given as
+    /// little-endian bits
+    pub fn msm_constant_bases<CS>(
+        cs: &mut CS,
+        bases: &[G2Affine],
+        scalars: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let bases = bases.iter().map(G2Projective::from).collect::<Vec<_>>();
+        msm::msm_constant_bases(
+            &mut cs.namespace(|| "G2::msm_constant_bases(b, k)"),
+            &bases,
+            scalars,
+        )
+    }
+
     /// Implementation of the optimized simple SWU map to BLS12-381 G2.
     /// Following [circom-pairing's implementation](https://github.com/yi-sun/circom-pairing/blob/107c316223a08ac577522c54edd81f0fc4c03130/circuits/bls12_381_hash_to_G2.circom#L11-L29).
     ///
@@ -784,6 +1106,30 @@ impl<F: PrimeFieldBits> G2Point<F> {
         Ok(z)
     }
 
+    /// Hashes `msg` to a point in G2 of BLS12-381 using the `BLS12381G2_XMD:SHA-256_SSWU_RO_`
+    /// suite from [section 8.8.2 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-bls12-381-g2),
+    /// with the domain separation tag `dst`.
+    ///
+    /// The bits of the message are given in the order expected by the SHA-256 gadget, i.e. the
+    /// bytes of the message with the bits of each byte in big-endian order.
+    pub fn hash_to_g2<CS>(cs: &mut CS, msg: &[Boolean], dst: &[u8]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let cs = &mut cs.namespace(|| "G2::hash_to_g2(msg, dst)");
+        let u = hash_to_field_fp2(
+            &mut cs.namespace(|| "u <- hash_to_field(msg, 2)"),
+            XmdHash::Sha256,
+            msg,
+            dst,
+            2,
+        )?;
+        let res = Self::map_to_g2(&mut cs.namespace(|| "map_to_g2(u[0], u[1])"), &u[0], &u[1])?;
+        // the coordinates are reduced for the point to be usable in the Miller loop
+        let res = res.reduce(&mut cs.namespace(|| "res <- res.reduce()"))?;
+        Ok(res)
+    }
+
     /// Asserts that y^2 = x^3 + ax + b
     pub fn assert_is_on_curve<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
     where
@@ -804,6 +1150,93 @@ impl<F: PrimeFieldBits> G2Point<F> {
         Ok(())
     }
 
+    /// Returns the point of the 96-byte compressed encoding in the ZCash serialization format,
+    /// given as its 768 big-endian bits, where `x.c1` comes first and carries the flags. The
+    /// flags and the canonicity of `x` are enforced and `y` is the square root of `x^3 + 4(u+1)`
+    /// whose lexicographic sign matches the sort flag. Like `alloc_element`, the point is not
+    /// checked to be in the prime order subgroup.
+    pub fn from_compressed_bits<CS>(cs: &mut CS, bits: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if bits.len() != 768 {
+            eprintln!("Compressed G2 points are encoded with 768 bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let c1_bits = bits[3..384].iter().rev().cloned().collect::<Vec<_>>();
+        let c0_bits = bits[384..].iter().rev().cloned().collect::<Vec<_>>();
+        let a0 = FpElement::from_canonical_bits_le(&mut cs.namespace(|| "x.c0 <- bits"), &c0_bits)?;
+        let a1 = FpElement::from_canonical_bits_le(&mut cs.namespace(|| "x.c1 <- bits"), &c1_bits)?;
+        let x = Fp2Element { a0, a1 };
+        let x_is_zero = x.alloc_is_zero(&mut cs.namespace(|| "x_is_zero <- x.is_zero()"))?;
+        let (infinity, sort) = enforce_compression_flags(
+            &mut cs.namespace(|| "enforce flags"),
+            &bits[..3],
+            &x_is_zero,
+        )?;
+
+        // invalid encodings get y = 0, which does not satisfy the constraints below
+        let y_value = bits_to_bytes_be(bits).map(|bytes| {
+            let bytes: [u8; 96] = bytes.try_into().unwrap();
+            Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(&bytes))
+                .filter(|p| !bool::from(p.is_identity()))
+                .map_or(BlsFp2::zero(), |p| p.y)
+        });
+        let y = Fp2Element::alloc_element(&mut cs.namespace(|| "alloc y"), &y_value)?;
+        let y = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "y <- select(y, 0, infinity)"),
+            &y,
+            &Fp2Element::zero(),
+            &infinity,
+        )?;
+        let p = Self { x, y };
+
+        // the point at infinity is not on the curve, so the check is done on the generator
+        let q = Self::conditionally_select(
+            &mut cs.namespace(|| "q <- select(p, g2, infinity)"),
+            &p,
+            &Self::from(&G2Affine::generator()),
+            &infinity,
+        )?;
+        q.assert_is_on_curve(&mut cs.namespace(|| "q.assert_is_on_curve()"))?;
+        let largest =
+            p.y.lexicographically_largest(&mut cs.namespace(|| "y.lexicographically_largest()"))?;
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "sort flag == y.lexicographically_largest()"),
+            &sort,
+            &largest,
+        )?;
+
+        Ok(p)
+    }
+
+    /// Returns the 768 big-endian bits of the 96-byte compressed encoding of the point in the
+    /// ZCash serialization format
+    pub fn to_compressed_bits<CS>(&self, cs: &mut CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let infinity = self.alloc_is_identity(&mut cs.namespace(|| "p.is_identity()"))?;
+        let sort = self
+            .y
+            .lexicographically_largest(&mut cs.namespace(|| "y.lexicographically_largest()"))?;
+        let c1_bits = self
+            .x
+            .a1
+            .to_bits_le(&mut cs.namespace(|| "x.c1.to_bits_le()"))?;
+        let c0_bits = self
+            .x
+            .a0
+            .to_bits_le(&mut cs.namespace(|| "x.c0.to_bits_le()"))?;
+
+        let mut bits = compression_flags(&infinity, &sort);
+        bits.extend(c1_bits.into_iter().rev());
+        bits.extend(vec![Boolean::constant(false); 3]);
+        bits.extend(c0_bits.into_iter().rev());
+        Ok(bits)
+    }
+
     /// Asserts that `psi(P) == [x]P`
     pub fn assert_subgroup_check<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
     where
@@ -1274,4 +1707,420 @@ mod tests {
         expect_eq(cs.scalar_aux().len(), &expect!["1779724"]);
         expect_eq(cs.num_constraints(), &expect!["1786554"]);
     }
+
+    #[test]
+    fn test_random_add_unified() {
+        let mut rng = rand::thread_rng();
+        let a = G2Projective::random(&mut rng);
+        let b = G2Projective::random(&mut rng);
+        let c = a + b;
+        let d = a + a;
+        // e = -psi2(a) has the opposite y coordinate of a but e != -a
+        let e = -a.psi2();
+        let f = a + e;
+        let a = G2Affine::from(a);
+        let b = G2Affine::from(b);
+        let c = G2Affine::from(c);
+        let d = G2Affine::from(d);
+        let e = G2Affine::from(e);
+        let f = G2Affine::from(f);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let b_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
+        let c_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let d_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc d"), &Some(d)).unwrap();
+        let e_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc e"), &Some(e)).unwrap();
+        let f_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc f"), &Some(f)).unwrap();
+        let z_alloc =
+            G2Point::alloc_element(&mut cs.namespace(|| "alloc z"), &Some(G2Affine::identity()))
+                .unwrap();
+        let neg_a_alloc = a_alloc.neg(&mut cs.namespace(|| "-a")).unwrap();
+        let res1_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a+b"), &b_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "a+b = c"), &res1_alloc, &c_alloc).unwrap();
+        let res2_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a+a"), &a_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "a+a = d"), &res2_alloc, &d_alloc).unwrap();
+        let res3_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a+0"), &z_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "a+0 = a"), &res3_alloc, &a_alloc).unwrap();
+        let res4_alloc = z_alloc
+            .add_unified(&mut cs.namespace(|| "0+a"), &a_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "0+a = a"), &res4_alloc, &a_alloc).unwrap();
+        let res5_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a+e"), &e_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "a+e = f"), &res5_alloc, &f_alloc).unwrap();
+        let res6_alloc = a_alloc
+            .add_unified(&mut cs.namespace(|| "a-a"), &neg_a_alloc)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "a-a = 0"), &res6_alloc, &z_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["92260"]);
+        expect_eq(cs.num_constraints(), &expect!["93044"]);
+    }
+
+    fn alloc_scalar_bits(cs: &mut TestConstraintSystem<Fp>, k: &Scalar) -> Vec<Boolean> {
+        let bytes = k.to_bytes();
+        (0..255)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc k bit {i}")),
+                        Some((bytes[i / 8] >> (i % 8)) & 1 == 1),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_random_gls_scalar_mul() {
+        let mut rng = rand::thread_rng();
+        let a = G2Projective::random(&mut rng);
+        let k = Scalar::random(&mut rng);
+        let c = G2Affine::from(a * k);
+        let a = G2Affine::from(a);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_scalar_bits(&mut cs, &k);
+        let a_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .scalar_mul(&mut cs.namespace(|| "[k]a"), &k_bits)
+            .unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "[k]a = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1997601"]);
+        expect_eq(cs.num_constraints(), &expect!["2017654"]);
+
+        // flipping a bit of k0 breaks the decomposition
+        let path = "[k]a/G2::gls_scalar_mul(p, k)/k <- decompose(k)/alloc bit 0 of k0/boolean";
+        let bit = cs.get(path);
+        cs.set(path, Fp::ONE - bit);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_gls_scalar_mul_exceptional_cases() {
+        let mut rng = rand::thread_rng();
+        let a = G2Projective::random(&mut rng);
+        let cases = [
+            (a, Scalar::zero()),
+            (a, Scalar::one()),
+            (a, -Scalar::one()),
+            (G2Projective::identity(), Scalar::random(&mut rng)),
+        ];
+
+        for (i, (a, k)) in cases.into_iter().enumerate() {
+            let c = G2Affine::from(a * k);
+            let a = G2Affine::from(a);
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let k_bits = alloc_scalar_bits(&mut cs, &k);
+            let a_alloc =
+                G2Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+            let c_alloc =
+                G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc = a_alloc
+                .scalar_mul(&mut cs.namespace(|| "[k]a"), &k_bits)
+                .unwrap();
+            G2Point::assert_is_equal(&mut cs.namespace(|| "[k]a = c"), &res_alloc, &c_alloc)
+                .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("case {i}: {:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_map_to_g2_rfc9380_vectors() {
+        // Test vectors for BLS12381G2_XMD:SHA-256_SSWU_RO_ from appendix J.10.1 of RFC 9380
+        // (u[0], u[1], P.x, P.y) with each element of Fp2 given as (c0, c1)
+        let vectors = [
+            (
+                [
+                    "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8",
+                    "05a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+                ],
+                [
+                    "02f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee94",
+                    "145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+                ],
+                [
+                    "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+                    "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+                ],
+                [
+                    "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+                    "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+                ],
+            ),
+            (
+                [
+                    "15f7c0aa8f6b296ab5ff9c2c7581ade64f4ee6f1bf18f55179ff44a2cf355fa53dd2a2158c5ecb17d7c52f63e7195771",
+                    "01c8067bf4c0ba709aa8b9abc3d1cef589a4758e09ef53732d670fd8739a7274e111ba2fcaa71b3d33df2a3a0c8529dd",
+                ],
+                [
+                    "187111d5e088b6b9acfdfad078c4dacf72dcd17ca17c82be35e79f8c372a693f60a033b461d81b025864a0ad051a06e4",
+                    "08b852331c96ed983e497ebc6dee9b75e373d923b729194af8e72a051ea586f3538a6ebb1e80881a082fa2b24df9f566",
+                ],
+                [
+                    "02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6",
+                    "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8",
+                ],
+                [
+                    "1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+                    "00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16",
+                ],
+            ),
+        ];
+        let fp2_from_hex = |v: &[&str; 2]| {
+            let c0 = BigInt::parse_bytes(v[0].as_bytes(), 16).unwrap();
+            let c1 = BigInt::parse_bytes(v[1].as_bytes(), 16).unwrap();
+            BlsFp2 {
+                c0: bigint_to_fpelem(&c0).unwrap(),
+                c1: bigint_to_fpelem(&c1).unwrap(),
+            }
+        };
+
+        for (u0, u1, px, py) in vectors.iter() {
+            let c = G2Affine {
+                x: fp2_from_hex(px),
+                y: fp2_from_hex(py),
+                infinity: 0.into(),
+            };
+            assert!(bool::from(c.is_on_curve()));
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let u0_alloc = Fp2Element::alloc_element(
+                &mut cs.namespace(|| "alloc u0"),
+                &Some(fp2_from_hex(u0)),
+            )
+            .unwrap();
+            let u1_alloc = Fp2Element::alloc_element(
+                &mut cs.namespace(|| "alloc u1"),
+                &Some(fp2_from_hex(u1)),
+            )
+            .unwrap();
+            let c_alloc =
+                G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc = G2Point::map_to_g2(
+                &mut cs.namespace(|| "map_to_g2(u0, u1)"),
+                &u0_alloc,
+                &u1_alloc,
+            )
+            .unwrap();
+            G2Point::assert_is_equal(
+                &mut cs.namespace(|| "map_to_g2(u0, u1) = c"),
+                &res_alloc,
+                &c_alloc,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    // The two halves of `hash_to_g2` are also checked against the same vectors by
+    // `test_hash_to_field_fp2` and `test_map_to_g2_rfc9380_vectors`, with smaller circuits.
+    // NOTE: this test currently takes more than 6GB of ram to run
+    #[test]
+    fn test_hash_to_g2_rfc9380_vectors() {
+        // Test vectors for BLS12381G2_XMD:SHA-256_SSWU_RO_ from appendix J.10.1 of RFC 9380
+        // (msg, P.x, P.y) with each element of Fp2 given as (c0, c1)
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let vectors = [
+            (
+                "",
+                [
+                    "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+                    "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+                ],
+                [
+                    "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+                    "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+                ],
+            ),
+            (
+                "abc",
+                [
+                    "02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6",
+                    "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8",
+                ],
+                [
+                    "1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+                    "00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16",
+                ],
+            ),
+        ];
+        let fp2_from_hex = |v: &[&str; 2]| {
+            let c0 = BigInt::parse_bytes(v[0].as_bytes(), 16).unwrap();
+            let c1 = BigInt::parse_bytes(v[1].as_bytes(), 16).unwrap();
+            BlsFp2 {
+                c0: bigint_to_fpelem(&c0).unwrap(),
+                c1: bigint_to_fpelem(&c1).unwrap(),
+            }
+        };
+
+        for (msg, px, py) in vectors.iter() {
+            let c = G2Affine {
+                x: fp2_from_hex(px),
+                y: fp2_from_hex(py),
+                infinity: 0.into(),
+            };
+            assert!(bool::from(c.is_on_curve()));
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let msg_bits = msg
+                .bytes()
+                .enumerate()
+                .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+                .map(|(i, j, b)| {
+                    Boolean::from(
+                        AllocatedBit::alloc(
+                            cs.namespace(|| format!("alloc bit {j} of msg byte {i}")),
+                            Some(b),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let c_alloc =
+                G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+            let res_alloc =
+                G2Point::hash_to_g2(&mut cs.namespace(|| "hash_to_g2(msg)"), &msg_bits, DST)
+                    .unwrap();
+            G2Point::assert_is_equal(
+                &mut cs.namespace(|| "hash_to_g2(msg) = c"),
+                &res_alloc,
+                &c_alloc,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    fn alloc_bytes_be(cs: &mut TestConstraintSystem<Fp>, bytes: &[u8]) -> Vec<Boolean> {
+        bytes
+            .iter()
+            .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1))
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("alloc bit {i}")), Some(b))
+                        .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn compressed_helper(bytes: &[u8; 96]) -> (TestConstraintSystem<Fp>, G2Point<Fp>) {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = alloc_bytes_be(&mut cs, bytes);
+        let p = G2Point::from_compressed_bits(&mut cs.namespace(|| "from_compressed_bits"), &bits)
+            .unwrap();
+        (cs, p)
+    }
+
+    #[test]
+    fn test_random_compressed_encoding() {
+        let mut rng = rand::thread_rng();
+        let a = G2Affine::from(G2Projective::random(&mut rng));
+        let bytes = a.to_compressed();
+
+        let (mut cs, a_alloc) = compressed_helper(&bytes);
+        assert_eq!(G2Affine::try_from(&a_alloc).unwrap(), a);
+        let bits = a_alloc
+            .to_compressed_bits(&mut cs.namespace(|| "to_compressed_bits"))
+            .unwrap();
+        assert_eq!(bits.len(), 768);
+        assert_eq!(bits_to_bytes_be(&bits).unwrap(), bytes.to_vec());
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["10960"]);
+        expect_eq(cs.num_constraints(), &expect!["12273"]);
+    }
+
+    #[test]
+    fn test_compressed_encoding_identity() {
+        let bytes = G2Affine::identity().to_compressed();
+        let (mut cs, a_alloc) = compressed_helper(&bytes);
+        assert_eq!(G2Affine::try_from(&a_alloc).unwrap(), G2Affine::identity());
+        let bits = a_alloc
+            .to_compressed_bits(&mut cs.namespace(|| "to_compressed_bits"))
+            .unwrap();
+        assert_eq!(bits_to_bytes_be(&bits).unwrap(), bytes.to_vec());
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_compressed_encoding_flags() {
+        let mut rng = rand::thread_rng();
+        let bytes = G2Affine::from(G2Projective::random(&mut rng)).to_compressed();
+
+        // compression flag unset
+        let mut invalid = bytes;
+        invalid[0] &= 0x7f;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // the other sort flag encodes the opposite point
+        let mut neg = bytes;
+        neg[0] ^= 0x20;
+        let (cs, a_alloc) = compressed_helper(&neg);
+        assert!(cs.is_satisfied());
+        assert_eq!(
+            G2Affine::try_from(&a_alloc).unwrap(),
+            -G2Affine::from_compressed(&bytes).unwrap()
+        );
+
+        // infinity flag set with a non-zero x
+        let mut invalid = bytes;
+        invalid[0] |= 0x40;
+        invalid[0] &= !0x20;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // infinity flag set with the sort flag
+        let mut invalid = G2Affine::identity().to_compressed();
+        invalid[0] |= 0x20;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // x not reduced modulo p
+        let mut invalid = [0xff; 96];
+        invalid[0] = 0x9f;
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+
+        // x of a point that is not on the curve
+        let mut invalid = bytes;
+        loop {
+            invalid[96 - 1] = invalid[96 - 1].wrapping_add(1);
+            if bool::from(G2Affine::from_compressed_unchecked(&invalid).is_none()) {
+                break;
+            }
+        }
+        assert!(!compressed_helper(&invalid).0.is_satisfied());
+    }
 }
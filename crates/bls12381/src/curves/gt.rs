@@ -0,0 +1,282 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bls12_381::fp12::Fp12 as BlsFp12;
+use bls12_381::Gt;
+use ff::PrimeFieldBits;
+
+use crate::fields::fp12::Fp12Element;
+
+/// Represents an element of the target group GT of the BLS12-381 pairing, the subgroup of order
+/// r of the multiplicative group of Fp12. The operations below assume that the element is in
+/// GT (in particular in the cyclotomic subgroup), which holds for pairing outputs and must be
+/// enforced with `assert_subgroup_check` for witnesses
+#[derive(Clone)]
+pub struct GtElement<F: PrimeFieldBits>(pub Fp12Element<F>);
+
+impl<F> From<&Gt> for GtElement<F>
+where
+    F: PrimeFieldBits,
+{
+    fn from(value: &Gt) -> Self {
+        Self(Fp12Element::from(&value.0))
+    }
+}
+
+impl<F> TryFrom<&GtElement<F>> for Gt
+where
+    F: PrimeFieldBits,
+{
+    type Error = SynthesisError;
+
+    fn try_from(value: &GtElement<F>) -> Result<Self, Self::Error> {
+        Ok(Self(BlsFp12::try_from(&value.0)?))
+    }
+}
+
+impl<F: PrimeFieldBits> GtElement<F> {
+    pub fn one() -> Self {
+        Self(Fp12Element::one())
+    }
+
+    pub fn alloc_element<CS>(cs: &mut CS, value: &Option<Gt>) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = value.map(|v| v.0);
+        let x = Fp12Element::alloc_element(&mut cs.namespace(|| "allocate gt"), &value)?;
+        Ok(Self(x))
+    }
+
+    pub fn assert_is_equal<CS>(cs: &mut CS, a: &Self, b: &Self) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Fp12Element::assert_is_equal(cs, &a.0, &b.0)
+    }
+
+    /// Returns a `Boolean` which is true if and only if `a == b`
+    pub fn is_equal<CS>(cs: &mut CS, a: &Self, b: &Self) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let diff = a.0.sub(&mut cs.namespace(|| "diff <- a - b"), &b.0)?;
+        let is_zero = diff.alloc_is_zero(&mut cs.namespace(|| "diff =? 0"))?;
+        Ok(Boolean::Is(is_zero))
+    }
+
+    pub fn reduce<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self(self.0.reduce(cs)?))
+    }
+
+    pub fn mul<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self(self.0.mul(cs, &value.0)?))
+    }
+
+    pub fn square<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self(self.0.cyclotomic_square(cs)?))
+    }
+
+    /// Returns the inverse of x, which is its conjugate in the cyclotomic subgroup
+    pub fn inverse<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self(self.0.conjugate(cs)?))
+    }
+
+    pub fn conditionally_select<CS>(
+        cs: &mut CS,
+        z0: &Self,
+        z1: &Self,
+        condition: &Boolean,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self(Fp12Element::conditionally_select(
+            cs, &z0.0, &z1.0, condition,
+        )?))
+    }
+
+    /// Returns `x^k` where `k` is given as little-endian bits, using square-and-multiply
+    pub fn exp<CS>(&self, cs: &mut CS, scalar: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "Gt::exp(x, k)");
+        let n = scalar.len();
+        if n == 0 {
+            return Ok(Self::one());
+        }
+        let x = self.reduce(&mut cs.namespace(|| "x <- x.reduce()"))?;
+
+        let mut res = Self::conditionally_select(
+            &mut cs.namespace(|| format!("res <- select(1, x, k[{}])", n - 1)),
+            &Self::one(),
+            &x,
+            &scalar[n - 1],
+        )?;
+        for i in (0..n - 1).rev() {
+            let sq = res.square(&mut cs.namespace(|| format!("sq <- res.square() ({i})")))?;
+            let sq = sq.reduce(&mut cs.namespace(|| format!("sq <- sq.reduce() ({i})")))?;
+            let t = sq.mul(&mut cs.namespace(|| format!("t <- sq * x ({i})")), &x)?;
+            let t = t.reduce(&mut cs.namespace(|| format!("t <- t.reduce() ({i})")))?;
+            res = Self::conditionally_select(
+                &mut cs.namespace(|| format!("res <- select(sq, t, k[{i}])")),
+                &sq,
+                &t,
+                &scalar[i],
+            )?;
+        }
+
+        Ok(res)
+    }
+
+    /// Asserts that x is in GT, by checking that x is in the cyclotomic subgroup, i.e.
+    /// `x^(p^4 - p^2 + 1) == 1`, and that `x^p == x^t`
+    pub fn assert_subgroup_check<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = &self.0;
+        let a = x.frobenius_square(&mut cs.namespace(|| "a <- x.frobenius_square()"))?;
+        let b = a.frobenius_square(&mut cs.namespace(|| "b <- a.frobenius_square()"))?;
+        let b = b.mul(&mut cs.namespace(|| "b <- b * x"), x)?;
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "x^(p^4) * x == x^(p^2)"), &a, &b)?;
+
+        let a = x.frobenius(&mut cs.namespace(|| "a <- x.frobenius()"))?;
+        let b = x.cyclotomic_expt(&mut cs.namespace(|| "b <- x.cyclotomic_expt()"))?;
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "x^p == x^t"), &a, &b)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::group::Group;
+
+    use expect_test::{expect, Expect};
+    fn expect_eq(computed: usize, expected: &Expect) {
+        expected.assert_eq(&computed.to_string());
+    }
+
+    fn alloc_bits(
+        cs: &mut TestConstraintSystem<Fp>,
+        name: &str,
+        k: u64,
+        num_bits: usize,
+    ) -> Vec<Boolean> {
+        (0..num_bits)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc {name} bit {i}")),
+                        Some((k >> i) & 1 == 1),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_random_exp() {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let a = Gt::random(&mut rng);
+        // 16-bit scalars keep the size of the test constraint system manageable
+        let k = rng.next_u64() & 0xffff;
+        let c = a * Scalar::from(k);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_bits(&mut cs, "k", k, 16);
+        let a_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc.exp(&mut cs.namespace(|| "a^k"), &k_bits).unwrap();
+        GtElement::assert_is_equal(&mut cs.namespace(|| "a^k = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["437779"]);
+        expect_eq(cs.num_constraints(), &expect!["439819"]);
+    }
+
+    #[test]
+    fn test_random_is_equal() {
+        let mut rng = rand::thread_rng();
+        let a = Gt::random(&mut rng);
+        let b = Gt::random(&mut rng);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let a2_alloc =
+            GtElement::alloc_element(&mut cs.namespace(|| "alloc a2"), &Some(a)).unwrap();
+        let b_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
+        let eq = GtElement::is_equal(&mut cs.namespace(|| "a =? a2"), &a_alloc, &a2_alloc).unwrap();
+        let neq = GtElement::is_equal(&mut cs.namespace(|| "a =? b"), &a_alloc, &b_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        assert_eq!(eq.get_value(), Some(true));
+        assert_eq!(neq.get_value(), Some(false));
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["15250"]);
+        expect_eq(cs.num_constraints(), &expect!["15262"]);
+    }
+
+    #[test]
+    fn test_random_subgroup_check() {
+        let mut rng = rand::thread_rng();
+        let a = Gt::random(&mut rng);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        a_alloc
+            .assert_subgroup_check(&mut cs.namespace(|| "a in GT"))
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["859960"]);
+        expect_eq(cs.num_constraints(), &expect!["864130"]);
+    }
+
+    #[test]
+    fn test_random_subgroup_check_invalid() {
+        let mut rng = rand::thread_rng();
+        let f = BlsFp12::random(&mut rng);
+        // f^((p^6-1)(p^2+1)) is in the cyclotomic subgroup, but not in GT
+        let g = f.conjugate() * f.invert().unwrap();
+        let g = g.frobenius_map().frobenius_map() * g;
+
+        for (name, x) in [("f", f), ("g", g)] {
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let x_alloc = GtElement(
+                Fp12Element::alloc_element(&mut cs.namespace(|| "alloc x"), &Some(x)).unwrap(),
+            );
+            x_alloc
+                .assert_subgroup_check(&mut cs.namespace(|| "x in GT"))
+                .unwrap();
+            assert!(!cs.is_satisfied(), "{name} should not be in GT");
+        }
+    }
+}
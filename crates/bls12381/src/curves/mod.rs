@@ -1,4 +1,7 @@
+mod encoding;
 pub mod g1;
 pub mod g2;
+pub mod gt;
+pub mod msm;
 pub mod pairing;
 pub mod params;
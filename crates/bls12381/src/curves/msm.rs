@@ -0,0 +1,734 @@
+use std::ops::Add;
+
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bls12_381::{G1Projective, G2Projective};
+use ff::PrimeFieldBits;
+
+use super::g1::G1Point;
+use super::g2::G2Point;
+
+/// Largest window size considered when estimating the cost of an MSM strategy
+const MAX_WINDOW_SIZE: usize = 8;
+
+/// Largest number of points in a group of the Pippenger strategy
+const MAX_GROUP_SIZE: usize = 8;
+
+/// Window size used for MSMs with constant bases
+const CONSTANT_BASES_WINDOW_SIZE: usize = 6;
+
+/// Point operations needed by the multi-scalar multiplication gadgets
+pub trait MsmPoint<F: PrimeFieldBits>: Clone + Sized {
+    /// Native point type used to precompute multiples of constant bases
+    type Native: Copy + Default + Add<Output = Self::Native>;
+
+    fn identity() -> Self;
+
+    /// Returns a point with constant coordinates
+    fn from_native(value: &Self::Native) -> Self;
+
+    /// Point addition supporting equal points and the identity
+    fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>;
+
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>;
+}
+
+impl<F: PrimeFieldBits> MsmPoint<F> for G1Point<F> {
+    type Native = G1Projective;
+
+    fn identity() -> Self {
+        G1Point::identity()
+    }
+
+    fn from_native(value: &Self::Native) -> Self {
+        if value.is_identity().into() {
+            G1Point::identity()
+        } else {
+            G1Point::from(&bls12_381::G1Affine::from(value))
+        }
+    }
+
+    fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        G1Point::add_unified(self, cs, value)
+    }
+
+    fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        G1Point::mux_tree(cs, select_bits, inputs)
+    }
+}
+
+impl<F: PrimeFieldBits> MsmPoint<F> for G2Point<F> {
+    type Native = G2Projective;
+
+    fn identity() -> Self {
+        G2Point::identity()
+    }
+
+    fn from_native(value: &Self::Native) -> Self {
+        if value.is_identity().into() {
+            G2Point::identity()
+        } else {
+            G2Point::from(&bls12_381::G2Affine::from(value))
+        }
+    }
+
+    fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        G2Point::add_unified(self, cs, value)
+    }
+
+    fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        G2Point::mux_tree(cs, select_bits, inputs)
+    }
+}
+
+/// Strategy used to compute a multi-scalar multiplication
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MsmStrategy {
+    /// Interleaved windowed method with a lookup table per point
+    Straus { window_size: usize },
+    /// Bucket method of Pippenger, where the points are split in groups of `group_size` and
+    /// added to the buckets using a lookup table of the subset sums of each group
+    Pippenger {
+        window_size: usize,
+        group_size: usize,
+    },
+}
+
+impl MsmStrategy {
+    /// Estimates the number of point additions (and doublings) needed to compute
+    /// an MSM of `num_points` points with scalars of `num_bits` bits
+    pub fn num_additions(&self, num_points: usize, num_bits: usize) -> usize {
+        match *self {
+            Self::Straus { window_size } => {
+                let num_windows = (num_bits + window_size - 1) / window_size;
+                num_points * ((1 << window_size) - 2) + num_bits + num_points * num_windows
+            }
+            Self::Pippenger {
+                window_size,
+                group_size,
+            } => {
+                let num_windows = (num_bits + window_size - 1) / window_size;
+                let num_groups = (num_points + group_size - 1) / group_size;
+                let num_buckets = (1 << window_size) - 1;
+                let subset_sums = num_groups * ((1 << group_size) - group_size - 1);
+                subset_sums + num_windows * (num_groups + 2) * num_buckets + num_bits
+            }
+        }
+    }
+
+    /// Returns the strategy with the lowest estimated number of additions
+    pub fn best(num_points: usize, num_bits: usize) -> Self {
+        let straus = (1..=MAX_WINDOW_SIZE).map(|window_size| Self::Straus { window_size });
+        let pippenger = (1..=MAX_WINDOW_SIZE).flat_map(|window_size| {
+            (1..=MAX_GROUP_SIZE.min(num_points)).map(move |group_size| Self::Pippenger {
+                window_size,
+                group_size,
+            })
+        });
+        straus
+            .chain(pippenger)
+            .min_by_key(|s| s.num_additions(num_points, num_bits))
+            .unwrap()
+    }
+}
+
+fn check_inputs<P>(points: &[P], scalars: &[Vec<Boolean>]) -> Result<usize, SynthesisError> {
+    if points.is_empty() || points.len() != scalars.len() {
+        eprintln!("MSM requires the same non-zero number of points and scalars");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let num_bits = scalars.iter().map(|s| s.len()).max().unwrap();
+    if num_bits == 0 {
+        eprintln!("Scalar bit vectors cannot all be empty");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    Ok(num_bits)
+}
+
+/// Returns the bits `[start, start + len)` of the little-endian `scalar`, padded with zeros
+fn window_bits(scalar: &[Boolean], start: usize, len: usize) -> Vec<Boolean> {
+    (start..start + len)
+        .map(|i| scalar.get(i).cloned().unwrap_or(Boolean::constant(false)))
+        .collect()
+}
+
+/// Returns `a + b`, where `None` stands for the identity
+fn add_opt<F, P, CS>(cs: &mut CS, a: Option<P>, b: Option<&P>) -> Result<Option<P>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    match (a, b) {
+        (Some(a), Some(b)) => Ok(Some(a.add_unified(cs, b)?)),
+        (a, None) => Ok(a),
+        (None, b) => Ok(b.cloned()),
+    }
+}
+
+/// Returns `[2^n]acc`, where `None` stands for the identity
+fn double_n<F, P, CS>(cs: &mut CS, acc: Option<P>, n: usize) -> Result<Option<P>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    let Some(mut acc) = acc else {
+        return Ok(None);
+    };
+    for k in 0..n {
+        acc = acc.add_unified(
+            &mut cs.namespace(|| format!("acc <- acc + acc ({k})")),
+            &acc,
+        )?;
+    }
+    Ok(Some(acc))
+}
+
+/// Returns `sum([k_i]P_i)` where the scalars `k_i` are given as little-endian bits
+///
+/// Each window of `window_size` bits selects `[j]P_i` from a lookup table of `2^window_size`
+/// points for every `i`, and the doublings are shared between all the points.
+pub fn msm_straus<F, P, CS>(
+    cs: &mut CS,
+    points: &[P],
+    scalars: &[Vec<Boolean>],
+    window_size: usize,
+) -> Result<P, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    if window_size == 0 {
+        eprintln!("Window size must be positive");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let num_bits = check_inputs(points, scalars)?;
+    let mut cs = cs.namespace(|| format!("msm_straus({}, {window_size})", points.len()));
+
+    let mut tables = vec![];
+    for (i, p) in points.iter().enumerate() {
+        let mut table = vec![P::identity(), p.clone()];
+        for j in 2..(1usize << window_size) {
+            let point = table[j - 1].add_unified(&mut cs.namespace(|| format!("{j}P_{i}")), p)?;
+            table.push(point);
+        }
+        tables.push(table);
+    }
+
+    let num_windows = (num_bits + window_size - 1) / window_size;
+    let mut res: Option<P> = None;
+    for w in (0..num_windows).rev() {
+        res = double_n(
+            &mut cs.namespace(|| format!("res <- res.double_n() ({w})")),
+            res,
+            window_size,
+        )?;
+        for (i, (table, scalar)) in tables.iter().zip(scalars).enumerate() {
+            let bits = window_bits(scalar, w * window_size, window_size);
+            let tmp = P::mux_tree(
+                &mut cs.namespace(|| format!("tmp <- table_{i}[window {w}]")),
+                bits.iter().rev(), // mux_tree requires MSB first
+                table,
+            )?;
+            res = add_opt(
+                &mut cs.namespace(|| format!("res <- res + tmp ({w}, {i})")),
+                res,
+                Some(&tmp),
+            )?;
+        }
+    }
+
+    Ok(res.unwrap())
+}
+
+/// Returns `sum([k_i]P_i)` where the scalars `k_i` are given as little-endian bits
+///
+/// For each window of `window_size` bits, every point is added to the bucket matching its
+/// window bits, and the buckets are combined with a running sum. Since the bucket of a point
+/// depends on the witness, each bucket is updated once per group of `group_size` points with
+/// the sum of the points of the group that belong to it, selected from a lookup table of the
+/// `2^group_size` subset sums of the group.
+pub fn msm_pippenger<F, P, CS>(
+    cs: &mut CS,
+    points: &[P],
+    scalars: &[Vec<Boolean>],
+    window_size: usize,
+    group_size: usize,
+) -> Result<P, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    if window_size == 0 || group_size == 0 {
+        eprintln!("Window size and group size must be positive");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let num_bits = check_inputs(points, scalars)?;
+    let mut cs = cs.namespace(|| {
+        format!(
+            "msm_pippenger({}, {window_size}, {group_size})",
+            points.len()
+        )
+    });
+    let num_buckets = (1usize << window_size) - 1;
+
+    // subset_sums[g][m] is the sum of the points of group g selected by the bits of m
+    let mut subset_sums = vec![];
+    for (g, group) in points.chunks(group_size).enumerate() {
+        let mut table = vec![P::identity()];
+        for (i, p) in group.iter().enumerate() {
+            table.push(p.clone());
+            for m in 1..(1usize << i) {
+                let point = table[m].add_unified(
+                    &mut cs.namespace(|| format!("subset sum {} of group {g}", m + (1 << i))),
+                    p,
+                )?;
+                table.push(point);
+            }
+        }
+        subset_sums.push(table);
+    }
+
+    let num_windows = (num_bits + window_size - 1) / window_size;
+    let mut res: Option<P> = None;
+    for w in (0..num_windows).rev() {
+        let mut cs = cs.namespace(|| format!("window {w}"));
+        let window_bits = scalars
+            .iter()
+            .map(|scalar| window_bits(scalar, w * window_size, window_size))
+            .collect::<Vec<_>>();
+
+        let mut buckets: Vec<Option<P>> = vec![None; num_buckets];
+        for (j, bucket) in buckets.iter_mut().enumerate() {
+            // is_j[i] <- (window bits of k_i == j + 1)
+            let mut is_j = vec![];
+            for (i, bits) in window_bits.iter().enumerate() {
+                let mut b = Boolean::constant(true);
+                for (k, bit) in bits.iter().enumerate() {
+                    let bit = if ((j + 1) >> k) & 1 == 1 {
+                        bit.clone()
+                    } else {
+                        bit.not()
+                    };
+                    b = Boolean::and(
+                        &mut cs.namespace(|| format!("is_{j}[{i}] <- is_{j}[{i}] & bit {k}")),
+                        &b,
+                        &bit,
+                    )?;
+                }
+                is_j.push(b);
+            }
+
+            for (g, (table, selectors)) in
+                subset_sums.iter().zip(is_j.chunks(group_size)).enumerate()
+            {
+                if selectors
+                    .iter()
+                    .all(|b| matches!(b, Boolean::Constant(false)))
+                {
+                    continue;
+                }
+                let tmp = P::mux_tree(
+                    &mut cs.namespace(|| format!("tmp <- subset_sums_{g}[is_{j}]")),
+                    selectors.iter().rev(), // mux_tree requires MSB first
+                    table,
+                )?;
+                *bucket = add_opt(
+                    &mut cs.namespace(|| format!("bucket_{j} <- bucket_{j} + tmp ({g})")),
+                    bucket.take(),
+                    Some(&tmp),
+                )?;
+            }
+        }
+
+        // sum([j]bucket_j) = bucket_n + (bucket_n + bucket_{n-1}) + ...
+        let mut running: Option<P> = None;
+        let mut total: Option<P> = None;
+        for (j, bucket) in buckets.iter().enumerate().rev() {
+            running = add_opt(
+                &mut cs.namespace(|| format!("running <- running + bucket_{j}")),
+                running,
+                bucket.as_ref(),
+            )?;
+            total = add_opt(
+                &mut cs.namespace(|| format!("total <- total + running ({j})")),
+                total,
+                running.as_ref(),
+            )?;
+        }
+
+        res = double_n(
+            &mut cs.namespace(|| "res <- res.double_n()"),
+            res,
+            window_size,
+        )?;
+        res = add_opt(
+            &mut cs.namespace(|| "res <- res + total"),
+            res,
+            total.as_ref(),
+        )?;
+    }
+
+    // all the window bits are constant zeros
+    Ok(res.unwrap_or_else(P::identity))
+}
+
+/// Returns `sum([k_i]P_i)` using the strategy with the lowest estimated cost
+pub fn msm<F, P, CS>(
+    cs: &mut CS,
+    points: &[P],
+    scalars: &[Vec<Boolean>],
+) -> Result<P, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    let num_bits = check_inputs(points, scalars)?;
+    match MsmStrategy::best(points.len(), num_bits) {
+        MsmStrategy::Straus { window_size } => msm_straus(cs, points, scalars, window_size),
+        MsmStrategy::Pippenger {
+            window_size,
+            group_size,
+        } => msm_pippenger(cs, points, scalars, window_size, group_size),
+    }
+}
+
+/// Returns `sum([k_i]B_i)` for constant bases `B_i` and scalars given as little-endian bits
+///
+/// For the window starting at bit `s`, the multiples `[j * 2^s]B_i` are computed outside the
+/// circuit and selected with the window bits, so no doublings are needed.
+pub fn msm_constant_bases<F, P, CS>(
+    cs: &mut CS,
+    bases: &[P::Native],
+    scalars: &[Vec<Boolean>],
+) -> Result<P, SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: MsmPoint<F>,
+    CS: ConstraintSystem<F>,
+{
+    let window_size = CONSTANT_BASES_WINDOW_SIZE;
+    check_inputs(bases, scalars)?;
+    let mut cs = cs.namespace(|| format!("msm_constant_bases({})", bases.len()));
+
+    let mut res: Option<P> = None;
+    for (i, (base, scalar)) in bases.iter().zip(scalars).enumerate() {
+        // multiple of the base corresponding to the least significant bit of the window
+        let mut window_base = *base;
+        for (w, bits) in scalar.chunks(window_size).enumerate() {
+            let mut table = vec![];
+            let mut multiple = P::Native::default();
+            for _ in 0..(1usize << bits.len()) {
+                table.push(P::from_native(&multiple));
+                multiple = multiple + window_base;
+            }
+            // multiple now equals [2^(window bit count)] * window_base
+            window_base = multiple;
+
+            let tmp = P::mux_tree(
+                &mut cs.namespace(|| format!("tmp <- table_{i}[window {w}]")),
+                bits.iter().rev(), // mux_tree requires MSB first
+                &table,
+            )?;
+            res = add_opt(
+                &mut cs.namespace(|| format!("res <- res + tmp ({i}, {w})")),
+                res,
+                Some(&tmp),
+            )?;
+        }
+    }
+
+    Ok(res.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bls12_381::{G1Affine, G2Affine, Scalar};
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::group::Group;
+    use rand::RngCore;
+
+    use expect_test::{expect, Expect};
+    fn expect_eq(computed: usize, expected: &Expect) {
+        expected.assert_eq(&computed.to_string());
+    }
+
+    fn alloc_scalars(
+        cs: &mut TestConstraintSystem<Fp>,
+        scalars: &[u64],
+        num_bits: usize,
+    ) -> Vec<Vec<Boolean>> {
+        scalars
+            .iter()
+            .enumerate()
+            .map(|(i, k)| {
+                (0..num_bits)
+                    .map(|j| {
+                        Boolean::from(
+                            AllocatedBit::alloc(
+                                cs.namespace(|| format!("alloc k_{i} bit {j}")),
+                                Some((k >> j) & 1 == 1),
+                            )
+                            .unwrap(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn random_scalars(num_scalars: usize, num_bits: usize) -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..num_scalars)
+            .map(|_| rng.next_u64() & ((1 << num_bits) - 1))
+            .collect()
+    }
+
+    fn g1_msm_helper(
+        points: &[G1Projective],
+        scalars: &[u64],
+        num_bits: usize,
+        strategy: MsmStrategy,
+    ) -> TestConstraintSystem<Fp> {
+        let c = points
+            .iter()
+            .zip(scalars)
+            .fold(G1Projective::identity(), |acc, (p, k)| {
+                acc + p * Scalar::from(*k)
+            });
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_scalars(&mut cs, scalars, num_bits);
+        let p_alloc = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                G1Point::alloc_element(
+                    &mut cs.namespace(|| format!("alloc p_{i}")),
+                    &Some(G1Affine::from(p)),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let c_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(G1Affine::from(c)))
+                .unwrap();
+        let res_alloc = match strategy {
+            MsmStrategy::Straus { window_size } => {
+                msm_straus(&mut cs.namespace(|| "msm"), &p_alloc, &k_bits, window_size)
+            }
+            MsmStrategy::Pippenger {
+                window_size,
+                group_size,
+            } => msm_pippenger(
+                &mut cs.namespace(|| "msm"),
+                &p_alloc,
+                &k_bits,
+                window_size,
+                group_size,
+            ),
+        }
+        .unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "msm = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        cs
+    }
+
+    #[test]
+    fn test_random_msm_straus() {
+        let mut rng = rand::thread_rng();
+        let points = (0..3)
+            .map(|_| G1Projective::random(&mut rng))
+            .collect::<Vec<_>>();
+        let scalars = random_scalars(3, 8);
+        let cs = g1_msm_helper(&points, &scalars, 8, MsmStrategy::Straus { window_size: 2 });
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["171000"]);
+        expect_eq(cs.num_constraints(), &expect!["172792"]);
+    }
+
+    #[test]
+    fn test_random_msm_pippenger() {
+        let mut rng = rand::thread_rng();
+        let points = (0..3)
+            .map(|_| G1Projective::random(&mut rng))
+            .collect::<Vec<_>>();
+        let scalars = random_scalars(3, 8);
+        let strategy = MsmStrategy::Pippenger {
+            window_size: 2,
+            group_size: 2,
+        };
+        let cs = g1_msm_helper(&points, &scalars, 8, strategy);
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["282054"]);
+        expect_eq(cs.num_constraints(), &expect!["285046"]);
+    }
+
+    #[test]
+    fn test_msm_exceptional_cases() {
+        let mut rng = rand::thread_rng();
+        let p = G1Projective::random(&mut rng);
+        let cases = [
+            // equal points and scalars
+            ([p, p, p], [5, 5, 5]),
+            // opposite points
+            ([p, -p, p.double()], [3, 3, 1]),
+            // identity and zero scalars
+            ([G1Projective::identity(), p, p], [7, 0, 0]),
+        ];
+        let strategies = [
+            MsmStrategy::Straus { window_size: 1 },
+            MsmStrategy::Pippenger {
+                window_size: 1,
+                group_size: 3,
+            },
+            MsmStrategy::Pippenger {
+                window_size: 3,
+                group_size: 1,
+            },
+        ];
+
+        for (i, (points, scalars)) in cases.iter().enumerate() {
+            for strategy in strategies {
+                let cs = g1_msm_helper(points, scalars, 3, strategy);
+                assert!(cs.is_satisfied(), "case {i} with {strategy:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_msm_constant_bases() {
+        let mut rng = rand::thread_rng();
+        let bases = (0..3)
+            .map(|_| G1Affine::from(G1Projective::random(&mut rng)))
+            .collect::<Vec<_>>();
+        let scalars = random_scalars(3, 16);
+        let c = bases
+            .iter()
+            .zip(&scalars)
+            .fold(G1Projective::identity(), |acc, (b, k)| {
+                acc + b * Scalar::from(*k)
+            });
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_scalars(&mut cs, &scalars, 16);
+        let c_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(G1Affine::from(c)))
+                .unwrap();
+        let res_alloc =
+            G1Point::msm_constant_bases(&mut cs.namespace(|| "msm"), &bases, &k_bits).unwrap();
+        G1Point::assert_is_equal(&mut cs.namespace(|| "msm = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["65550"]);
+        expect_eq(cs.num_constraints(), &expect!["66184"]);
+    }
+
+    #[test]
+    fn test_random_g2_msm() {
+        let mut rng = rand::thread_rng();
+        let points = (0..2)
+            .map(|_| G2Projective::random(&mut rng))
+            .collect::<Vec<_>>();
+        let scalars = random_scalars(2, 4);
+        let c = points
+            .iter()
+            .zip(&scalars)
+            .fold(G2Projective::identity(), |acc, (p, k)| {
+                acc + p * Scalar::from(*k)
+            });
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_scalars(&mut cs, &scalars, 4);
+        let p_alloc = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                G2Point::alloc_element(
+                    &mut cs.namespace(|| format!("alloc p_{i}")),
+                    &Some(G2Affine::from(p)),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let c_alloc =
+            G2Point::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(G2Affine::from(c)))
+                .unwrap();
+        let res_alloc = G2Point::msm(&mut cs.namespace(|| "msm"), &p_alloc, &k_bits).unwrap();
+        G2Point::assert_is_equal(&mut cs.namespace(|| "msm = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["143698"]);
+        expect_eq(cs.num_constraints(), &expect!["145090"]);
+    }
+
+    #[test]
+    fn test_msm_strategy() {
+        expect!["Straus { window_size: 4 }"].assert_eq(&format!("{:?}", MsmStrategy::best(2, 255)));
+        expect!["Pippenger { window_size: 1, group_size: 6 }"]
+            .assert_eq(&format!("{:?}", MsmStrategy::best(100, 255)));
+    }
+
+    #[test]
+    fn test_msm_invalid_inputs() {
+        let mut rng = rand::thread_rng();
+        let p = G1Affine::from(G1Projective::random(&mut rng));
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let k_bits = alloc_scalars(&mut cs, &[1, 2], 2);
+        let p_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc p"), &Some(p)).unwrap();
+
+        let points = vec![p_alloc.clone(); 3];
+        let res = G1Point::msm(&mut cs.namespace(|| "msm"), &points, &k_bits);
+        assert!(res.is_err());
+        let res = G1Point::msm(&mut cs.namespace(|| "msm empty"), &[], &[]);
+        assert!(res.is_err());
+        let res = G1Point::msm(&mut cs.namespace(|| "msm no bits"), &[p_alloc], &[vec![]]);
+        assert!(res.is_err());
+    }
+}
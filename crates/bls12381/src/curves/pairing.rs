@@ -4,26 +4,36 @@ use bellpepper_core::{
     boolean::{AllocatedBit, Boolean},
     ConstraintSystem, SynthesisError,
 };
+use bellpepper_emulated::field_element::EmulatedFieldParams;
+use bls12_381::fp12::Fp12 as BlsFp12;
+use bls12_381::fp2::Fp2 as BlsFp2;
+use bls12_381::fp6::Fp6 as BlsFp6;
+use bls12_381::G2Affine;
 use ff::PrimeFieldBits;
 use num_bigint::BigInt;
+use num_integer::Integer;
 
+use crate::fields::fp::{Bls12381FpParams, Bls12381FrParams};
 use crate::fields::{fp12::Fp12Element, fp2::Fp2Element, fp6::Fp6Element, torus::Torus};
 
-use super::{g1::G1Point, g2::G2Point};
+use super::{g1::G1Point, g2::G2Point, gt::GtElement};
 
 pub trait EmulatedPairing<F, G1Element, G2Element, GtElement>
 where
     F: PrimeFieldBits,
 {
+    /// Output of the Miller loop, which is only mapped into GT by the final exponentiation
+    type MillerLoopElement;
+
     fn miller_loop<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         g1: impl AsRef<[G1Element]>,
         g2: impl AsRef<[G2Element]>,
-    ) -> Result<GtElement, SynthesisError>;
+    ) -> Result<Self::MillerLoopElement, SynthesisError>;
 
     fn final_exponentiation<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        gt: &GtElement,
+        f: &Self::MillerLoopElement,
         is_single_pairing: bool,
     ) -> Result<GtElement, SynthesisError>;
 
@@ -47,6 +57,7 @@ pub struct EmulatedBls12381Pairing<F> {
 /// LineEval represents a sparse Fp12 Elmt (result of the line evaluation)
 /// line: 1 + R0(x/y) + R1(1/y) = 0 instead of R0'*y + R1'*x + R2' = 0 This
 /// makes the multiplication by lines (MulBy014)
+#[derive(Clone)]
 pub struct LineEval<F: PrimeFieldBits> {
     pub(crate) r0: Fp2Element<F>,
     pub(crate) r1: Fp2Element<F>,
@@ -60,6 +71,7 @@ impl<F: PrimeFieldBits> LineEval<F> {
     }
 }
 
+#[derive(Clone)]
 pub struct LineEvals<F: PrimeFieldBits> {
     pub(crate) v0: Vec<LineEval<F>>,
     pub(crate) v1: Vec<LineEval<F>>,
@@ -132,6 +144,231 @@ impl<F: PrimeFieldBits> EmulatedBls12381Pairing<F> {
         Ok(res)
     }
 
+    /// precompute_lines computes the lines of the Miller loop for a fixed point q natively, as
+    /// compute_lines does in-circuit. The lines are constants, so using them in
+    /// miller_loop_with_lines costs no constraints besides the line evaluations.
+    pub fn precompute_lines(q: &G2Affine) -> Result<LineEvals<F>, SynthesisError> {
+        if bool::from(q.is_identity()) {
+            eprintln!("Cannot precompute the lines of the point at infinity");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let div = |n: BlsFp2, d: BlsFp2| {
+            Option::<BlsFp2>::from(d.invert())
+                .map(|d_inv| n * d_inv)
+                .ok_or_else(|| {
+                    eprintln!("Division by zero while precomputing lines");
+                    SynthesisError::Unsatisfiable
+                })
+        };
+        // λ = 3x²/2y
+        let tangent = |x: BlsFp2, y: BlsFp2| {
+            let x2 = x.square();
+            div(x2 + x2 + x2, y + y)
+        };
+        let line = |l: BlsFp2, x: BlsFp2, y: BlsFp2| LineEval {
+            r0: Fp2Element::from(&l),
+            r1: Fp2Element::from(&(l * x - y)),
+        };
+
+        let mut res = LineEvals::<F>::new();
+        let n = LOOP_COUNTER.len();
+
+        // triple_step
+        let (x, y) = (q.x, q.y);
+        let l1 = tangent(x, y)?;
+        let x2 = l1.square() - x - x;
+        let l2 = div(y + y, x - x2)? - l1;
+        let xr = l2.square() - (x2 + x);
+        let yr = l2 * (x - xr) - y;
+        res.v0[n - 2] = line(l1, x, y);
+        res.v1[n - 2] = line(l2, x, y);
+        let (mut x, mut y) = (xr, yr);
+
+        for i in (1..=n - 3).rev() {
+            if LOOP_COUNTER[i] == 0 {
+                // double_step
+                let l = tangent(x, y)?;
+                let xr = l.square() - x - x;
+                let yr = l * (x - xr) - y;
+                res.v0[i] = line(l, x, y);
+                (x, y) = (xr, yr);
+            } else {
+                // double_and_add_step
+                let l1 = div(y - q.y, x - q.x)?;
+                let x3 = l1.square() - x - q.x;
+                let l2 = -(div(y + y, x3 - x)? + l1);
+                let x4 = l2.square() - x - x3;
+                let y4 = l2 * (x - x4) - y;
+                res.v0[i] = line(l1, x, y);
+                res.v1[i] = line(l2, x, y);
+                (x, y) = (x4, y4);
+            }
+        }
+
+        // tangent_compute
+        res.v0[0] = line(tangent(x, y)?, x, y);
+
+        Ok(res)
+    }
+
+    /// miller_loop_with_lines computes the multi-Miller loop
+    /// ∏ᵢ { fᵢ_{u,Q}(P) } ∏ⱼ { fⱼ_{u,Q'}(P') }
+    /// where the lines of the fixed points Q' were computed with precompute_lines
+    pub fn miller_loop_with_lines<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        g1: &[G1Point<F>],
+        g2: &[G2Point<F>],
+        fixed_g1: &[G1Point<F>],
+        fixed_g2_lines: &[LineEvals<F>],
+    ) -> Result<Fp12Element<F>, SynthesisError> {
+        if g1.len() != g2.len()
+            || fixed_g1.len() != fixed_g2_lines.len()
+            || g1.len() + fixed_g1.len() == 0
+        {
+            return Err(SynthesisError::IncompatibleLengthVector(format!(
+                "miller loop with lines: {} vs {}, {} vs {}",
+                g1.len(),
+                g2.len(),
+                fixed_g1.len(),
+                fixed_g2_lines.len()
+            )));
+        }
+
+        let mut lines = Self::lines(cs, g2)?;
+        lines.extend_from_slice(fixed_g2_lines);
+        let p: Vec<G1Point<F>> = g1.iter().chain(fixed_g1).cloned().collect();
+
+        Self::miller_loop_lines(cs, p, lines)
+    }
+
+    /// Same as assert_pairing_check, with additional pairs whose G2 lines were computed with
+    /// precompute_lines
+    pub fn assert_pairing_check_with_lines<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        g1: &[G1Point<F>],
+        g2: &[G2Point<F>],
+        fixed_g1: &[G1Point<F>],
+        fixed_g2_lines: &[LineEvals<F>],
+    ) -> Result<(), SynthesisError> {
+        let p_len = g1.len() + fixed_g1.len();
+        let res = Self::miller_loop_with_lines(cs, g1, g2, fixed_g1, fixed_g2_lines)?;
+        let res = Self::final_exponentiation(cs, &res, p_len == 1)?;
+        let one = GtElement::<F>::one();
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(p, q) =? 1"), &res, &one)?;
+        Ok(())
+    }
+
+    /// Computes the residue witness natively (Novakovic and Eagen, "On Proving Pairings",
+    /// <https://eprint.iacr.org/2024/640.pdf>). Given a Miller loop output `f` whose final
+    /// exponentiation is 1, returns `c` and `w ∈ Fp6` such that `f * w = c^λ`, where
+    /// `λ = p - t = 3 * r * ((1 - t) / 3)²` and `t` is the seed.
+    ///
+    /// The hard part `h = (p¹²-1)/r` of the final exponentiation splits into the pairwise coprime
+    /// factors `27`, `d = (1 - t) / 3` and `m = h / (27 * d)`. The scaling factor `w` cancels the
+    /// components of `f` of order dividing `27` and `d`, so that `f * w` has order dividing `m`
+    /// and its `λ`-th root can be taken with `λ⁻¹ mod m`. Since `27 * d` divides `p⁶-1`, `w`
+    /// lies in Fp6.
+    pub fn residue_witness(f: &BlsFp12) -> (BlsFp12, BlsFp6) {
+        let p = Bls12381FpParams::modulus();
+        let r = Bls12381FrParams::modulus();
+        let t_abs = BigInt::from(0xd201000000010000u64);
+        let h = (p.pow(12) - 1u32) / &r;
+        let d = (&t_abs + 1u32) / 3u32;
+        let m = &h / (&d * 27u32);
+        let lambda = &p + &t_abs;
+
+        // inverse of the component of order dividing d
+        let e = &m * 27u32;
+        let w_d = pow_native(&pow_native(f, &e), &(&d - mod_inverse(&e, &d)));
+        // inverse of the component of order dividing 27
+        let e = &m * &d;
+        let w_27 = pow_native(
+            &pow_native(f, &e),
+            &(27u32 - mod_inverse(&e, &27u32.into())),
+        );
+
+        let w = w_d * w_27;
+        let c = pow_native(&(f * w), &mod_inverse(&lambda, &m));
+        (c, w.c0)
+    }
+
+    /// Same as assert_pairing_check, where the final exponentiation is replaced by the check
+    /// of a residue witness computed natively with residue_witness
+    pub fn assert_pairing_check_with_residue<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        g1: &[G1Point<F>],
+        g2: &[G2Point<F>],
+    ) -> Result<(), SynthesisError> {
+        let f = Self::miller_loop(cs, g1, g2)?;
+        Self::assert_final_exponentiation_is_one_with_residue(cs, &f)
+    }
+
+    /// Enforces that the final exponentiation of the Miller loop output `f` is 1, by checking
+    /// `f * w == c^λ == c^p * c^|t|` for the witnesses `c` and `w ∈ Fp6` of residue_witness.
+    /// This holds only if `f` is an r-th residue, since `r | λ` and `w^((p¹²-1)/r) = 1`.
+    pub fn assert_final_exponentiation_is_one_with_residue<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        f: &Fp12Element<F>,
+    ) -> Result<(), SynthesisError> {
+        let cs = &mut cs.namespace(|| "assert_final_exponentiation_is_one_with_residue(f)");
+        let hint = BlsFp12::try_from(f).ok().map(|f| Self::residue_witness(&f));
+        Self::assert_residue_witness(cs, f, hint)
+    }
+
+    /// Allocates the witnesses `(c, w)` given by `hint` and enforces `f * w == c^λ`, where `c`
+    /// is required to be invertible. Otherwise `c = w = 0` would satisfy the equation for any
+    /// `f`; since `f * w` is then invertible, so is `w`.
+    fn assert_residue_witness<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        f: &Fp12Element<F>,
+        hint: Option<(BlsFp12, BlsFp6)>,
+    ) -> Result<(), SynthesisError> {
+        let c = Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &hint.map(|(c, _)| c))?;
+        let w = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc w"), &hint.map(|(_, w)| w))?;
+
+        // a zero c gets a zero inverse, which does not satisfy c * c_inv == 1
+        let c_inv_value = hint.map(|(c, _)| c.invert().unwrap_or(BlsFp12::zero()));
+        let c_inv = Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c_inv"), &c_inv_value)?;
+        let prod = c_inv.mul(&mut cs.namespace(|| "c * c_inv"), &c)?;
+        Fp12Element::assert_is_equal(
+            &mut cs.namespace(|| "c * c_inv =? 1"),
+            &prod,
+            &Fp12Element::one(),
+        )?;
+
+        let c0 = f.c0.mul(&mut cs.namespace(|| "lhs.c0 <- f.c0 * w"), &w)?;
+        let c1 = f.c1.mul(&mut cs.namespace(|| "lhs.c1 <- f.c1 * w"), &w)?;
+        let lhs = Fp12Element { c0, c1 };
+        let t0 = c.frobenius(&mut cs.namespace(|| "t0 <- c.frobenius()"))?;
+        let t1 = c.expt_abs(&mut cs.namespace(|| "t1 <- c.expt_abs()"))?;
+        let t0 = t0.reduce(&mut cs.namespace(|| "t0 <- t0.reduce()"))?;
+        let t1 = t1.reduce(&mut cs.namespace(|| "t1 <- t1.reduce()"))?;
+        let rhs = t0.mul(&mut cs.namespace(|| "rhs <- t0 * t1"), &t1)?;
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "f * w =? c^λ"), &lhs, &rhs)?;
+        Ok(())
+    }
+
+    /// Returns the lines of the Miller loop for each point of q, which are precomputed natively
+    /// for constant points and computed in-circuit otherwise
+    fn lines<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        q: &[G2Point<F>],
+    ) -> Result<Vec<LineEvals<F>>, SynthesisError> {
+        q.iter()
+            .enumerate()
+            .map(|(idx, pq)| {
+                if pq.is_constant() {
+                    Self::precompute_lines(&G2Affine::try_from(pq)?)
+                } else {
+                    Self::compute_lines(
+                        &mut cs.namespace(|| format!("compute_lines(q[{idx}])")),
+                        pq,
+                    )
+                }
+            })
+            .collect()
+    }
+
     #[allow(clippy::type_complexity)]
     /// triple_step triples p1 in affine coordinates, and evaluates the line in Miller loop
     pub fn triple_step<CS: ConstraintSystem<F>>(
@@ -432,10 +669,31 @@ impl<F: PrimeFieldBits> EmulatedBls12381Pairing<F> {
     }
 }
 
-impl<F> EmulatedPairing<F, G1Point<F>, G2Point<F>, Fp12Element<F>> for EmulatedBls12381Pairing<F>
+/// Returns `x^e` natively, for a non-negative `e`
+fn pow_native(x: &BlsFp12, e: &BigInt) -> BlsFp12 {
+    let mut res = BlsFp12::one();
+    for byte in e.to_bytes_be().1 {
+        for i in (0..8).rev() {
+            res = res.square();
+            if (byte >> i) & 1 == 1 {
+                res *= x;
+            }
+        }
+    }
+    res
+}
+
+/// Returns the inverse of `a` modulo `m`, assuming they are coprime
+fn mod_inverse(a: &BigInt, m: &BigInt) -> BigInt {
+    a.extended_gcd(m).x.mod_floor(m)
+}
+
+impl<F> EmulatedPairing<F, G1Point<F>, G2Point<F>, GtElement<F>> for EmulatedBls12381Pairing<F>
 where
     F: PrimeFieldBits,
 {
+    type MillerLoopElement = Fp12Element<F>;
+
     /// miller_loop computes the multi-Miller loop
     /// ∏ᵢ { fᵢ_{u,Q}(P) }
     fn miller_loop<CS: ConstraintSystem<F>>(
@@ -452,25 +710,18 @@ where
             )));
         }
 
-        // TODO: implement precompute_lines and omit line computation for constant elements here
-        let lines: Vec<LineEvals<F>> = q
-            .iter()
-            .enumerate()
-            .map(|(idx, pq)| {
-                Self::compute_lines(&mut cs.namespace(|| format!("compute_lines(q[{idx}])")), pq)
-            })
-            .collect::<Result<_, _>>()?;
+        let lines = Self::lines(cs, q)?;
 
         Self::miller_loop_lines(cs, p, lines)
     }
 
     fn final_exponentiation<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        gt: &Fp12Element<F>,
+        f: &Fp12Element<F>,
         is_single_pairing: bool,
-    ) -> Result<Fp12Element<F>, SynthesisError> {
+    ) -> Result<GtElement<F>, SynthesisError> {
         let cs = &mut cs.namespace(|| format!("final_exponentiation(e, {is_single_pairing})"));
-        let mut e = gt.reduce(&mut cs.namespace(|| "e <- e.reduce()"))?;
+        let mut e = f.reduce(&mut cs.namespace(|| "e <- e.reduce()"))?;
         let mut sel1: Option<AllocatedBit> = None;
         // 1. Easy part
         // (p⁶-1)(p²+1)
@@ -540,7 +791,7 @@ where
             // For a single pairing, this does not happen because the pairing is non-degenerate.
             let res = ct.mul(&mut cs.namespace(|| "res <- ct * t1"), &t1)?;
             let res = res.decompress(&mut cs.namespace(|| "res <- res.decompress()"))?;
-            Ok(res)
+            Ok(GtElement(res))
         } else {
             // For a product of pairings this might happen when the result is expected to be 1.
             // We assign a dummy value (1) to t1 and proceed furhter.
@@ -573,7 +824,7 @@ where
                 &res,
                 &Boolean::Is(selector),
             )?;
-            Ok(res)
+            Ok(GtElement(res))
         }
     }
 
@@ -581,7 +832,7 @@ where
         cs: &mut CS,
         g1: impl AsRef<[G1Point<F>]>,
         g2: impl AsRef<[G2Point<F>]>,
-    ) -> Result<Fp12Element<F>, SynthesisError> {
+    ) -> Result<GtElement<F>, SynthesisError> {
         let p_len = g1.as_ref().len();
         let res = Self::miller_loop(cs, g1, g2)?;
         let res = Self::final_exponentiation(cs, &res, p_len == 1)?;
@@ -594,8 +845,8 @@ where
         g2: impl AsRef<[G2Point<F>]>,
     ) -> Result<(), SynthesisError> {
         let res = Self::pair(cs, g1, g2)?;
-        let one = Fp12Element::<F>::one();
-        Fp12Element::assert_is_equal(&mut cs.namespace(|| "pair(p, q) =? 1"), &res, &one)?;
+        let one = GtElement::<F>::one();
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(p, q) =? 1"), &res, &one)?;
         Ok(())
     }
 }
@@ -620,14 +871,14 @@ mod tests {
         let mut cs = MetricCS::<Fp>::new();
         let a_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &None).unwrap();
         let b_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc b"), &None).unwrap();
-        let c_alloc = Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &None).unwrap();
+        let c_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc c"), &None).unwrap();
         let res_alloc = EmulatedBls12381Pairing::pair(
             &mut cs.namespace(|| "pair(a, b)"),
             &[a_alloc],
             &[b_alloc],
         )
         .unwrap();
-        Fp12Element::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
             .unwrap();
         expect_eq(cs.num_inputs(), &expect!["1"]);
         expect_eq(cs.num_constraints(), &expect!["9982166"]);
@@ -642,20 +893,18 @@ mod tests {
         let a = G1Affine::from(a);
         let b = G2Affine::from(b);
         let c = bls12_381::pairing(&a, &b);
-        let c = c.0;
 
         let mut cs = TestConstraintSystem::<Fp>::new();
         let a_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
         let b_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
-        let c_alloc =
-            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let c_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
         let res_alloc = EmulatedBls12381Pairing::pair(
             &mut cs.namespace(|| "pair(a, b)"),
             &[a_alloc],
             &[b_alloc],
         )
         .unwrap();
-        Fp12Element::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
             .unwrap();
         if !cs.is_satisfied() {
             eprintln!("{:?}", cs.which_is_unsatisfied())
@@ -684,7 +933,6 @@ mod tests {
         let b_prep: Vec<G2Prepared> = b.iter().cloned().map(G2Prepared::from).collect();
         let terms: Vec<(&G1Affine, &G2Prepared)> = a.iter().zip(b_prep.iter()).collect();
         let c = bls12_381::multi_miller_loop(&terms).final_exponentiation();
-        let c = c.0;
 
         let mut cs = TestConstraintSystem::<Fp>::new();
         let a_allocs: Vec<G1Point<Fp>> = a
@@ -703,12 +951,11 @@ mod tests {
                     .unwrap()
             })
             .collect();
-        let c_alloc =
-            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let c_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
         let res_alloc =
             EmulatedBls12381Pairing::pair(&mut cs.namespace(|| "pair(a, b)"), &a_allocs, &b_allocs)
                 .unwrap();
-        Fp12Element::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
             .unwrap();
         if !cs.is_satisfied() {
             eprintln!("{:?}", cs.which_is_unsatisfied())
@@ -718,4 +965,226 @@ mod tests {
         expect_eq(cs.scalar_aux().len(), &expect!["20752762"]);
         expect_eq(cs.num_constraints(), &expect!["20833926"]);
     }
+
+    #[test]
+    fn test_random_precompute_lines() {
+        let mut rng = rand::thread_rng();
+        let q = G2Affine::from(G2Projective::random(&mut rng));
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let q_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc q"), &Some(q)).unwrap();
+        let lines = EmulatedBls12381Pairing::compute_lines(
+            &mut cs.namespace(|| "compute_lines(q)"),
+            &q_alloc,
+        )
+        .unwrap();
+        let precomputed = EmulatedBls12381Pairing::<Fp>::precompute_lines(&q).unwrap();
+        assert!(cs.is_satisfied());
+
+        let values = |l: &LineEval<Fp>| {
+            (
+                BlsFp2::try_from(&l.r0).unwrap(),
+                BlsFp2::try_from(&l.r1).unwrap(),
+            )
+        };
+        for (a, b) in lines.v0.iter().zip(precomputed.v0.iter()) {
+            assert_eq!(values(a), values(b));
+        }
+        for (a, b) in lines.v1.iter().zip(precomputed.v1.iter()) {
+            assert_eq!(values(a), values(b));
+        }
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let q_const = G2Point::<Fp>::from(&q);
+        let res = EmulatedBls12381Pairing::lines(&mut cs, &[q_const]).unwrap();
+        expect_eq(cs.num_constraints(), &expect!["0"]);
+        for (a, b) in res[0].v0.iter().zip(precomputed.v0.iter()) {
+            assert_eq!(values(a), values(b));
+        }
+
+        assert!(EmulatedBls12381Pairing::<Fp>::precompute_lines(&G2Affine::identity()).is_err());
+    }
+
+    // NOTE: this test currently takes ~40GB of ram to run
+    #[test]
+    fn test_random_multi_pairing_with_lines() {
+        use bls12_381::G2Prepared;
+        let mut rng = rand::thread_rng();
+        let a: Vec<G1Affine> = (0..2)
+            .map(|_| G1Affine::from(G1Projective::random(&mut rng)))
+            .collect();
+        let b: Vec<G2Affine> = (0..2)
+            .map(|_| G2Affine::from(G2Projective::random(&mut rng)))
+            .collect();
+        let b_prep: Vec<G2Prepared> = b.iter().cloned().map(G2Prepared::from).collect();
+        let terms: Vec<(&G1Affine, &G2Prepared)> = a.iter().zip(b_prep.iter()).collect();
+        let c = bls12_381::multi_miller_loop(&terms).final_exponentiation();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a0_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc a0"), &Some(a[0])).unwrap();
+        let a1_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc a1"), &Some(a[1])).unwrap();
+        let b0_alloc =
+            G2Point::alloc_element(&mut cs.namespace(|| "alloc b0"), &Some(b[0])).unwrap();
+        let b1_lines = EmulatedBls12381Pairing::precompute_lines(&b[1]).unwrap();
+        let c_alloc = GtElement::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = EmulatedBls12381Pairing::miller_loop_with_lines(
+            &mut cs.namespace(|| "miller_loop(a, b)"),
+            &[a0_alloc],
+            &[b0_alloc],
+            &[a1_alloc],
+            &[b1_lines],
+        )
+        .unwrap();
+        let res_alloc = EmulatedBls12381Pairing::final_exponentiation(
+            &mut cs.namespace(|| "final_exponentiation(res)"),
+            &res_alloc,
+            false,
+        )
+        .unwrap();
+        GtElement::assert_is_equal(&mut cs.namespace(|| "pair(a, b) = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    /// Returns the native Miller loop output of e(a, b) * e(-a, b) if `valid`, and of
+    /// e(a, b) * e(a, b) otherwise
+    fn random_miller_loop(valid: bool) -> BlsFp12 {
+        use bls12_381::G2Prepared;
+        let mut rng = rand::thread_rng();
+        let a = G1Affine::from(G1Projective::random(&mut rng));
+        let b = G2Prepared::from(G2Affine::from(G2Projective::random(&mut rng)));
+        let a2 = if valid { -a } else { a };
+        bls12_381::multi_miller_loop(&[(&a, &b), (&a2, &b)]).0
+    }
+
+    #[test]
+    fn test_residue_witness_native() {
+        let lambda = Bls12381FpParams::modulus() + BigInt::from(0xd201000000010000u64);
+        for valid in [true, false] {
+            let f = random_miller_loop(valid);
+            let (c, w) = EmulatedBls12381Pairing::<Fp>::residue_witness(&f);
+            let w = BlsFp12 {
+                c0: w,
+                c1: BlsFp6::zero(),
+            };
+            assert_eq!(pow_native(&c, &lambda) == f * w, valid);
+        }
+    }
+
+    #[test]
+    fn test_random_final_exponentiation_is_one_with_residue() {
+        let f = random_miller_loop(true);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let f_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc f"), &Some(f)).unwrap();
+        EmulatedBls12381Pairing::assert_final_exponentiation_is_one_with_residue(
+            &mut cs.namespace(|| "final_exponentiation(f) =? 1"),
+            &f_alloc,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1052420"]);
+        expect_eq(cs.num_constraints(), &expect!["1057230"]);
+    }
+
+    #[test]
+    fn test_random_final_exponentiation_is_not_one_with_residue() {
+        let f = random_miller_loop(false);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let f_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc f"), &Some(f)).unwrap();
+        EmulatedBls12381Pairing::assert_final_exponentiation_is_one_with_residue(
+            &mut cs.namespace(|| "final_exponentiation(f) =? 1"),
+            &f_alloc,
+        )
+        .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_final_exponentiation_is_not_one_with_zero_residue() {
+        // c = w = 0 satisfies f * w == c^λ for any f, and must be rejected
+        let f = random_miller_loop(false);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let f_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc f"), &Some(f)).unwrap();
+        EmulatedBls12381Pairing::assert_residue_witness(
+            &mut cs.namespace(|| "final_exponentiation(f) =? 1"),
+            &f_alloc,
+            Some((BlsFp12::zero(), BlsFp6::zero())),
+        )
+        .unwrap();
+        assert!(!cs.is_satisfied());
+        assert!(cs
+            .which_is_unsatisfied()
+            .unwrap()
+            .contains("c * c_inv =? 1"));
+    }
+
+    #[test]
+    fn test_final_exponentiation_bench_cs() {
+        use bellpepper::util_cs::bench_cs::BenchCS;
+
+        let mut cs = BenchCS::<Fp>::new();
+        let f_alloc = Fp12Element::alloc_element(&mut cs.namespace(|| "alloc f"), &None).unwrap();
+        let res_alloc = EmulatedBls12381Pairing::final_exponentiation(
+            &mut cs.namespace(|| "final_exponentiation(f)"),
+            &f_alloc,
+            true,
+        )
+        .unwrap();
+        GtElement::assert_is_equal(
+            &mut cs.namespace(|| "final_exponentiation(f) = 1"),
+            &res_alloc,
+            &GtElement::one(),
+        )
+        .unwrap();
+        expect_eq(cs.num_constraints(), &expect!["1836407"]);
+
+        // same check with a residue witness instead of the final exponentiation
+        let mut cs = BenchCS::<Fp>::new();
+        let f_alloc = Fp12Element::alloc_element(&mut cs.namespace(|| "alloc f"), &None).unwrap();
+        EmulatedBls12381Pairing::assert_final_exponentiation_is_one_with_residue(
+            &mut cs.namespace(|| "final_exponentiation(f) =? 1"),
+            &f_alloc,
+        )
+        .unwrap();
+        expect_eq(cs.num_constraints(), &expect!["1057230"]);
+    }
+
+    // NOTE: this test currently takes more than 30GB of ram to run
+    #[test]
+    fn test_random_multi_pairing_check_with_residue() {
+        let mut rng = rand::thread_rng();
+        let a = G1Affine::from(G1Projective::random(&mut rng));
+        let b = G2Affine::from(G2Projective::random(&mut rng));
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = G1Point::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let a_neg_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc -a"), &Some(-a)).unwrap();
+        let b_alloc = G2Point::alloc_element(&mut cs.namespace(|| "alloc b"), &Some(b)).unwrap();
+        EmulatedBls12381Pairing::assert_pairing_check_with_residue(
+            &mut cs.namespace(|| "pair(a, b) * pair(-a, b) =? 1"),
+            &[a_alloc, a_neg_alloc],
+            &[b_alloc.clone(), b_alloc],
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
 }
@@ -1,15 +1,12 @@
 use std::marker::PhantomData;
 
+pub use bellpepper_emulated::weierstrass::EmulatedCurveParams;
 use ff::PrimeFieldBits;
 
-use crate::fields::{fp::FpElement, fp2::Fp2Element};
-
-pub trait EmulatedCurveParams<BaseElement> {
-    fn a() -> BaseElement;
-    fn b() -> BaseElement;
-
-    fn generator() -> (BaseElement, BaseElement); // returns (x, y) coordinates of a generator point for the curve
-}
+use crate::fields::{
+    fp::{Bls12381Fp, FpElement},
+    fp2::Fp2Element,
+};
 
 pub struct Bls12381G1Params<F> {
     _f: PhantomData<F>,
@@ -36,6 +33,21 @@ impl<F: PrimeFieldBits> EmulatedCurveParams<FpElement<F>> for Bls12381G1Params<F
     }
 }
 
+impl<F: PrimeFieldBits> EmulatedCurveParams<Bls12381Fp<F>> for Bls12381G1Params<F> {
+    fn a() -> Bls12381Fp<F> {
+        <Self as EmulatedCurveParams<FpElement<F>>>::a().0
+    }
+
+    fn b() -> Bls12381Fp<F> {
+        <Self as EmulatedCurveParams<FpElement<F>>>::b().0
+    }
+
+    fn generator() -> (Bls12381Fp<F>, Bls12381Fp<F>) {
+        let (x, y) = <Self as EmulatedCurveParams<FpElement<F>>>::generator();
+        (x.0, y.0)
+    }
+}
+
 impl<F: PrimeFieldBits> EmulatedCurveParams<Fp2Element<F>> for Bls12381G2Params<F> {
     fn a() -> Fp2Element<F> {
         Fp2Element::<F>::zero()
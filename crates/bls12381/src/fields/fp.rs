@@ -5,6 +5,7 @@ use bellpepper_core::{
 use bellpepper_emulated::field_element::{
     EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
 };
+use bellpepper_emulated::util::enforce_bits_le_at_most_constant;
 use bls12_381::fp::Fp as BlsFp;
 use ff::PrimeFieldBits;
 use num_bigint::{BigInt, Sign};
@@ -162,6 +163,10 @@ impl<F: PrimeFieldBits> FpElement<F> {
         Self(Bls12381Fp::one())
     }
 
+    pub fn is_constant(&self) -> bool {
+        self.0.is_constant()
+    }
+
     pub fn alloc_element<CS>(cs: &mut CS, value: &Option<BlsFp>) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -301,12 +306,60 @@ impl<F: PrimeFieldBits> FpElement<F> {
         Ok(Self(res))
     }
 
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    pub fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let inputs = inputs.iter().map(|i| i.0.clone()).collect::<Vec<_>>();
+        let res = Bls12381Fp::<F>::mux_tree(&mut cs.namespace(|| "mux val"), select_bits, &inputs)?;
+        Ok(Self(res))
+    }
+
     pub fn sgn0<CS>(&self, cs: &mut CS) -> Result<Boolean, SynthesisError>
     where
         CS: ConstraintSystem<F>,
     {
         self.0.sgn0(cs)
     }
+
+    /// Returns the element whose canonical little-endian bit decomposition is `bits`, enforcing
+    /// that they represent an integer smaller than the modulus
+    pub fn from_canonical_bits_le<CS>(cs: &mut CS, bits: &[Boolean]) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        enforce_bits_le_at_most_constant(
+            &mut cs.namespace(|| "bits <= p - 1"),
+            bits,
+            &(Bls12381FpParams::modulus() - 1),
+        )?;
+        Ok(Self(Bls12381Fp::<F>::from_bits_le(CS::one(), bits)))
+    }
+
+    /// Returns the 381 bits of the canonical little-endian bit decomposition of the element
+    pub fn to_bits_le<CS>(&self, cs: &mut CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        self.0.to_bits_le(cs)
+    }
+
+    /// Returns whether the element is lexicographically largest, i.e. greater than `(p-1)/2`,
+    /// as used by the sign flag of compressed point encodings
+    pub fn lexicographically_largest<CS>(&self, cs: &mut CS) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        // 2x mod p is odd if and only if x > (p-1)/2, as p is odd
+        let double = self.double(&mut cs.namespace(|| "double <- 2 * x"))?;
+        let bits = double.to_bits_le(&mut cs.namespace(|| "double.to_bits_le()"))?;
+        Ok(bits[0].clone())
+    }
 }
 
 #[cfg(test)]
@@ -462,6 +515,33 @@ mod tests {
         expect_eq(cs.num_constraints(), &expect!["4"]);
     }
 
+    #[test]
+    fn test_random_lexicographically_largest() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp::random(&mut rng);
+        let c: bool = a.lexicographically_largest().into();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = FpElement::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = AllocatedBit::alloc(&mut cs.namespace(|| "alloc c"), Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .lexicographically_largest(&mut cs.namespace(|| "a.lexicographically_largest()"))
+            .unwrap();
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "a.lexicographically_largest() = c"),
+            &res_alloc,
+            &Boolean::from(c_alloc),
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["840"]);
+        expect_eq(cs.num_constraints(), &expect!["990"]);
+    }
+
     #[test]
     fn test_random_alloc_is_zero() {
         let mut rng = rand::thread_rng();
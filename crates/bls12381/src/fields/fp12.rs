@@ -139,6 +139,102 @@ impl<F: PrimeFieldBits> Fp12Element<F> {
         })
     }
 
+    /// frobenius returns x^p
+    pub fn frobenius<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = &self.c1;
+        let mut cs = cs.namespace(|| "Fp12::frobenius(x)");
+        let z0 = self
+            .c0
+            .frobenius(&mut cs.namespace(|| "z0 <- x.c0.frobenius()"))?;
+        let t0 =
+            x.b0.conjugate(&mut cs.namespace(|| "t0 <- x.c1.b0.conjugate()"))?;
+        let t1 =
+            x.b1.conjugate(&mut cs.namespace(|| "t1 <- x.c1.b1.conjugate()"))?;
+        let t2 =
+            x.b2.conjugate(&mut cs.namespace(|| "t2 <- x.c1.b2.conjugate()"))?;
+        let t0 =
+            t0.mul_by_nonresidue_1pow1(&mut cs.namespace(|| "t0 <- t0.mul_by_nonresidue_1pow1()"))?;
+        let t1 =
+            t1.mul_by_nonresidue_1pow3(&mut cs.namespace(|| "t1 <- t1.mul_by_nonresidue_1pow3()"))?;
+        let t2 =
+            t2.mul_by_nonresidue_1pow5(&mut cs.namespace(|| "t2 <- t2.mul_by_nonresidue_1pow5()"))?;
+
+        Ok(Self {
+            c0: z0,
+            c1: Fp6Element {
+                b0: t0,
+                b1: t1,
+                b2: t2,
+            },
+        })
+    }
+
+    /// frobenius_square returns x^(p^2)
+    pub fn frobenius_square<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = &self.c1;
+        let mut cs = cs.namespace(|| "Fp12::frobenius_square(x)");
+        let z0 = self
+            .c0
+            .frobenius_square(&mut cs.namespace(|| "z0 <- x.c0.frobenius_square()"))?;
+        let t0 = x.b0.mul_by_nonresidue_2pow1(
+            &mut cs.namespace(|| "t0 <- x.c1.b0.mul_by_nonresidue_2pow1()"),
+        )?;
+        let t1 = x.b1.mul_by_nonresidue_2pow3(
+            &mut cs.namespace(|| "t1 <- x.c1.b1.mul_by_nonresidue_2pow3()"),
+        )?;
+        let t2 = x.b2.mul_by_nonresidue_2pow5(
+            &mut cs.namespace(|| "t2 <- x.c1.b2.mul_by_nonresidue_2pow5()"),
+        )?;
+
+        Ok(Self {
+            c0: z0,
+            c1: Fp6Element {
+                b0: t0,
+                b1: t1,
+                b2: t2,
+            },
+        })
+    }
+
+    /// frobenius_cube returns x^(p^3)
+    pub fn frobenius_cube<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = &self.c1;
+        let mut cs = cs.namespace(|| "Fp12::frobenius_cube(x)");
+        let z0 = self
+            .c0
+            .frobenius_cube(&mut cs.namespace(|| "z0 <- x.c0.frobenius_cube()"))?;
+        let t0 =
+            x.b0.conjugate(&mut cs.namespace(|| "t0 <- x.c1.b0.conjugate()"))?;
+        let t1 =
+            x.b1.conjugate(&mut cs.namespace(|| "t1 <- x.c1.b1.conjugate()"))?;
+        let t2 =
+            x.b2.conjugate(&mut cs.namespace(|| "t2 <- x.c1.b2.conjugate()"))?;
+        let t0 =
+            t0.mul_by_nonresidue_3pow1(&mut cs.namespace(|| "t0 <- t0.mul_by_nonresidue_3pow1()"))?;
+        let t1 =
+            t1.mul_by_nonresidue_3pow3(&mut cs.namespace(|| "t1 <- t1.mul_by_nonresidue_3pow3()"))?;
+        let t2 =
+            t2.mul_by_nonresidue_3pow5(&mut cs.namespace(|| "t2 <- t2.mul_by_nonresidue_3pow5()"))?;
+
+        Ok(Self {
+            c0: z0,
+            c1: Fp6Element {
+                b0: t0,
+                b1: t1,
+                b2: t2,
+            },
+        })
+    }
+
     pub fn mul<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -335,6 +431,385 @@ impl<F: PrimeFieldBits> Fp12Element<F> {
         Ok(Self { c0: z0, c1: z1 })
     }
 
+    /// Squares an element of the cyclotomic subgroup, i.e. such that `x^(p⁶-1)(p²+1) == x`
+    /// (Granger and Scott, <https://eprint.iacr.org/2009/565.pdf>, section 3.2)
+    pub fn cyclotomic_square<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "Fp12::cyclotomic_square(x)");
+        let x = self.reduce(&mut cs.namespace(|| "x <- x.reduce()"))?;
+
+        let t0 =
+            x.c1.b1
+                .square(&mut cs.namespace(|| "t0 <- x.c1.b1.square()"))?;
+        let t1 =
+            x.c0.b0
+                .square(&mut cs.namespace(|| "t1 <- x.c0.b0.square()"))?;
+        let t6 =
+            x.c1.b1
+                .add(&mut cs.namespace(|| "t6 <- x.c1.b1 + x.c0.b0"), &x.c0.b0)?;
+        let t6 = t6.square(&mut cs.namespace(|| "t6 <- t6.square()"))?;
+        let t6 = t6.sub(&mut cs.namespace(|| "t6 <- t6 - t0"), &t0)?;
+        let t6 = t6.sub(&mut cs.namespace(|| "t6 <- t6 - t1"), &t1)?;
+        let t2 =
+            x.c0.b2
+                .square(&mut cs.namespace(|| "t2 <- x.c0.b2.square()"))?;
+        let t3 =
+            x.c1.b0
+                .square(&mut cs.namespace(|| "t3 <- x.c1.b0.square()"))?;
+        let t7 =
+            x.c0.b2
+                .add(&mut cs.namespace(|| "t7 <- x.c0.b2 + x.c1.b0"), &x.c1.b0)?;
+        let t7 = t7.square(&mut cs.namespace(|| "t7 <- t7.square()"))?;
+        let t7 = t7.sub(&mut cs.namespace(|| "t7 <- t7 - t2"), &t2)?;
+        let t7 = t7.sub(&mut cs.namespace(|| "t7 <- t7 - t3"), &t3)?;
+        let t4 =
+            x.c1.b2
+                .square(&mut cs.namespace(|| "t4 <- x.c1.b2.square()"))?;
+        let t5 =
+            x.c0.b1
+                .square(&mut cs.namespace(|| "t5 <- x.c0.b1.square()"))?;
+        let t8 =
+            x.c1.b2
+                .add(&mut cs.namespace(|| "t8 <- x.c1.b2 + x.c0.b1"), &x.c0.b1)?;
+        let t8 = t8.square(&mut cs.namespace(|| "t8 <- t8.square()"))?;
+        let t8 = t8.sub(&mut cs.namespace(|| "t8 <- t8 - t4"), &t4)?;
+        let t8 = t8.sub(&mut cs.namespace(|| "t8 <- t8 - t5"), &t5)?;
+        let t8 = t8.mul_by_nonresidue(&mut cs.namespace(|| "t8 <- t8.mul_by_nonresidue()"))?;
+        let t0 = t0.mul_by_nonresidue(&mut cs.namespace(|| "t0 <- t0.mul_by_nonresidue()"))?;
+        let t0 = t0.add(&mut cs.namespace(|| "t0 <- t0 + t1"), &t1)?;
+        let t2 = t2.mul_by_nonresidue(&mut cs.namespace(|| "t2 <- t2.mul_by_nonresidue()"))?;
+        let t2 = t2.add(&mut cs.namespace(|| "t2 <- t2 + t3"), &t3)?;
+        let t4 = t4.mul_by_nonresidue(&mut cs.namespace(|| "t4 <- t4.mul_by_nonresidue()"))?;
+        let t4 = t4.add(&mut cs.namespace(|| "t4 <- t4 + t5"), &t5)?;
+
+        // z = 3t - 2x for the coefficients of c0, and 3t + 2x for the ones of c1
+        let z00 = t0.sub(&mut cs.namespace(|| "z00 <- t0 - x.c0.b0"), &x.c0.b0)?;
+        let z00 = z00.double(&mut cs.namespace(|| "z00 <- z00.double()"))?;
+        let z00 = z00.add(&mut cs.namespace(|| "z00 <- z00 + t0"), &t0)?;
+        let z01 = t2.sub(&mut cs.namespace(|| "z01 <- t2 - x.c0.b1"), &x.c0.b1)?;
+        let z01 = z01.double(&mut cs.namespace(|| "z01 <- z01.double()"))?;
+        let z01 = z01.add(&mut cs.namespace(|| "z01 <- z01 + t2"), &t2)?;
+        let z02 = t4.sub(&mut cs.namespace(|| "z02 <- t4 - x.c0.b2"), &x.c0.b2)?;
+        let z02 = z02.double(&mut cs.namespace(|| "z02 <- z02.double()"))?;
+        let z02 = z02.add(&mut cs.namespace(|| "z02 <- z02 + t4"), &t4)?;
+        let z10 = t8.add(&mut cs.namespace(|| "z10 <- t8 + x.c1.b0"), &x.c1.b0)?;
+        let z10 = z10.double(&mut cs.namespace(|| "z10 <- z10.double()"))?;
+        let z10 = z10.add(&mut cs.namespace(|| "z10 <- z10 + t8"), &t8)?;
+        let z11 = t6.add(&mut cs.namespace(|| "z11 <- t6 + x.c1.b1"), &x.c1.b1)?;
+        let z11 = z11.double(&mut cs.namespace(|| "z11 <- z11.double()"))?;
+        let z11 = z11.add(&mut cs.namespace(|| "z11 <- z11 + t6"), &t6)?;
+        let z12 = t7.add(&mut cs.namespace(|| "z12 <- t7 + x.c1.b2"), &x.c1.b2)?;
+        let z12 = z12.double(&mut cs.namespace(|| "z12 <- z12.double()"))?;
+        let z12 = z12.add(&mut cs.namespace(|| "z12 <- z12 + t7"), &t7)?;
+
+        Ok(Self {
+            c0: Fp6Element {
+                b0: z00,
+                b1: z01,
+                b2: z02,
+            },
+            c1: Fp6Element {
+                b0: z10,
+                b1: z11,
+                b2: z12,
+            },
+        })
+    }
+
+    /// Squares an element of the cyclotomic subgroup `n` times with `cyclotomic_square`
+    pub fn n_cyclotomic_square<CS>(&self, cs: &mut CS, n: usize) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| format!("Fp12::n_cyclotomic_square(x, {n})"));
+        let mut x = self.clone();
+        for i in 0..n {
+            x = x.cyclotomic_square(
+                &mut cs.namespace(|| format!("x <- x.cyclotomic_square() ({i})")),
+            )?;
+        }
+        Ok(x)
+    }
+
+    /// Squares an element of the cyclotomic subgroup in the compressed form of Karabina
+    /// (<https://eprint.iacr.org/2010/542.pdf>), where only `g1 = c0.b1`, `g2 = c0.b2`,
+    /// `g3 = c1.b0` and `g5 = c1.b2` are kept and the other coefficients are ignored. The result
+    /// is recovered with `decompress_karabina`.
+    pub fn cyclotomic_square_compressed<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "Fp12::cyclotomic_square_compressed(x)");
+        let x = self.reduce(&mut cs.namespace(|| "x <- x.reduce()"))?;
+        let (g1, g2, g3, g5) = (&x.c0.b1, &x.c0.b2, &x.c1.b0, &x.c1.b2);
+
+        // t0 = g1², t1 = g5²
+        let t0 = g1.square(&mut cs.namespace(|| "t0 <- g1.square()"))?;
+        let t1 = g5.square(&mut cs.namespace(|| "t1 <- g5.square()"))?;
+        // t5 = 2 * g1 * g5
+        let t5 = g1.add(&mut cs.namespace(|| "t5 <- g1 + g5"), g5)?;
+        let t2 = t5.square(&mut cs.namespace(|| "t2 <- t5.square()"))?;
+        let t3 = t0.add(&mut cs.namespace(|| "t3 <- t0 + t1"), &t1)?;
+        let t5 = t2.sub(&mut cs.namespace(|| "t5 <- t2 - t3"), &t3)?;
+        // t3 = (g3 + g2)², t2 = g3²
+        let t6 = g3.add(&mut cs.namespace(|| "t6 <- g3 + g2"), g2)?;
+        let t3 = t6.square(&mut cs.namespace(|| "t3 <- t6.square()"))?;
+        let t2 = g3.square(&mut cs.namespace(|| "t2 <- g3.square()"))?;
+        // z3 = 6 * nr * g1 * g5 + 2 * g3
+        let t6 = t5.mul_by_nonresidue(&mut cs.namespace(|| "t6 <- t5.mul_by_nonresidue()"))?;
+        let t5 = t6.add(&mut cs.namespace(|| "t5 <- t6 + g3"), g3)?;
+        let t5 = t5.double(&mut cs.namespace(|| "t5 <- t5.double()"))?;
+        let z3 = t5.add(&mut cs.namespace(|| "z3 <- t5 + t6"), &t6)?;
+        // z2 = 3 * nr * g5² + 3 * g1² - 2 * g2
+        let t4 = t1.mul_by_nonresidue(&mut cs.namespace(|| "t4 <- t1.mul_by_nonresidue()"))?;
+        let t5 = t0.add(&mut cs.namespace(|| "t5 <- t0 + t4"), &t4)?;
+        let t6 = t5.sub(&mut cs.namespace(|| "t6 <- t5 - g2"), g2)?;
+        let t1 = g2.square(&mut cs.namespace(|| "t1 <- g2.square()"))?;
+        let t6 = t6.double(&mut cs.namespace(|| "t6 <- t6.double()"))?;
+        let z2 = t6.add(&mut cs.namespace(|| "z2 <- t6 + t5"), &t5)?;
+        // z1 = 3 * g3² + 3 * nr * g2² - 2 * g1
+        let t4 = t1.mul_by_nonresidue(&mut cs.namespace(|| "t4 <- t1.mul_by_nonresidue() (2)"))?;
+        let t5 = t2.add(&mut cs.namespace(|| "t5 <- t2 + t4"), &t4)?;
+        let t6 = t5.sub(&mut cs.namespace(|| "t6 <- t5 - g1"), g1)?;
+        let t6 = t6.double(&mut cs.namespace(|| "t6 <- t6.double() (2)"))?;
+        let z1 = t6.add(&mut cs.namespace(|| "z1 <- t6 + t5"), &t5)?;
+        // z5 = 6 * g3 * g2 + 2 * g5
+        let t0 = t2.add(&mut cs.namespace(|| "t0 <- t2 + t1"), &t1)?;
+        let t5 = t3.sub(&mut cs.namespace(|| "t5 <- t3 - t0"), &t0)?;
+        let t6 = t5.add(&mut cs.namespace(|| "t6 <- t5 + g5"), g5)?;
+        let t6 = t6.double(&mut cs.namespace(|| "t6 <- t6.double() (3)"))?;
+        let z5 = t5.add(&mut cs.namespace(|| "z5 <- t5 + t6"), &t6)?;
+
+        Ok(Self {
+            c0: Fp6Element {
+                b0: x.c0.b0,
+                b1: z1,
+                b2: z2,
+            },
+            c1: Fp6Element {
+                b0: z3,
+                b1: x.c1.b1,
+                b2: z5,
+            },
+        })
+    }
+
+    /// Squares an element of the cyclotomic subgroup `n` times in Karabina's compressed form,
+    /// see `cyclotomic_square_compressed`
+    pub fn n_cyclotomic_square_compressed<CS>(
+        &self,
+        cs: &mut CS,
+        n: usize,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| format!("Fp12::n_cyclotomic_square_compressed(x, {n})"));
+        let mut x = self.clone();
+        for i in 0..n {
+            x = x.cyclotomic_square_compressed(
+                &mut cs.namespace(|| format!("x <- x.cyclotomic_square_compressed() ({i})")),
+            )?;
+        }
+        Ok(x)
+    }
+
+    /// Recovers `g0 = c0.b0` and `g4 = c1.b1` of the result of `cyclotomic_square_compressed`:
+    ///
+    ///   g4 = (nr * g5² + 3 * g1² - 2 * g2) / 4g3 if g3 != 0, 2 * g1 * g5 / g2 otherwise
+    ///   g0 = nr * (2 * g4² + g3 * g5 - 3 * g2 * g1) + 1
+    ///
+    /// If `g2 == g3 == 0`, the element is 1 and `g4 = 0`.
+    pub fn decompress_karabina<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "Fp12::decompress_karabina(x)");
+        let x = self.reduce(&mut cs.namespace(|| "x <- x.reduce()"))?;
+        let (g1, g2, g3, g5) = (&x.c0.b1, &x.c0.b2, &x.c1.b0, &x.c1.b2);
+
+        let g3_is_zero = Boolean::from(g3.alloc_is_zero(&mut cs.namespace(|| "g3 =? 0"))?);
+        let g2_is_zero = Boolean::from(g2.alloc_is_zero(&mut cs.namespace(|| "g2 =? 0"))?);
+        let is_one = Boolean::and(
+            &mut cs.namespace(|| "is_one <- and(g3 == 0, g2 == 0)"),
+            &g3_is_zero,
+            &g2_is_zero,
+        )?;
+
+        // g3 != 0: num = nr * g5² + 3 * g1² - 2 * g2, den = 4 * g3
+        let t0 = g1.square(&mut cs.namespace(|| "t0 <- g1.square()"))?;
+        let t1 = t0.sub(&mut cs.namespace(|| "t1 <- t0 - g2"), g2)?;
+        let t1 = t1.double(&mut cs.namespace(|| "t1 <- t1.double()"))?;
+        let t1 = t1.add(&mut cs.namespace(|| "t1 <- t1 + t0"), &t0)?;
+        let t2 = g5.square(&mut cs.namespace(|| "t2 <- g5.square()"))?;
+        let num0 = t2.mul_by_nonresidue(&mut cs.namespace(|| "num0 <- t2.mul_by_nonresidue()"))?;
+        let num0 = num0.add(&mut cs.namespace(|| "num0 <- num0 + t1"), &t1)?;
+        let den0 = g3.double(&mut cs.namespace(|| "den0 <- g3.double()"))?;
+        let den0 = den0.double(&mut cs.namespace(|| "den0 <- den0.double()"))?;
+        // g3 == 0: num = 2 * g1 * g5, den = g2
+        let num1 = g1.mul(&mut cs.namespace(|| "num1 <- g1 * g5"), g5)?;
+        let num1 = num1.double(&mut cs.namespace(|| "num1 <- num1.double()"))?;
+
+        let num0 = num0.reduce(&mut cs.namespace(|| "num0 <- num0.reduce()"))?;
+        let num1 = num1.reduce(&mut cs.namespace(|| "num1 <- num1.reduce()"))?;
+        let den0 = den0.reduce(&mut cs.namespace(|| "den0 <- den0.reduce()"))?;
+        let num = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "num <- select(num0, num1, g3 == 0)"),
+            &num0,
+            &num1,
+            &g3_is_zero,
+        )?;
+        let den = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "den <- select(den0, g2, g3 == 0)"),
+            &den0,
+            g2,
+            &g3_is_zero,
+        )?;
+        // avoid a division by zero when the element is 1, in which case num = 0
+        let den = Fp2Element::conditionally_select(
+            &mut cs.namespace(|| "den <- select(den, 1, is_one)"),
+            &den,
+            &Fp2Element::one(),
+            &is_one,
+        )?;
+        let g4 = num.div_unchecked(&mut cs.namespace(|| "g4 <- num div den"), &den)?;
+
+        // g0 = nr * (2 * g4² + g3 * g5 - 3 * g2 * g1) + 1
+        let t1 = g2.mul(&mut cs.namespace(|| "t1 <- g2 * g1"), g1)?;
+        let t2 = g4.square(&mut cs.namespace(|| "t2 <- g4.square()"))?;
+        let t2 = t2.sub(&mut cs.namespace(|| "t2 <- t2 - t1"), &t1)?;
+        let t2 = t2.double(&mut cs.namespace(|| "t2 <- t2.double()"))?;
+        let t2 = t2.sub(&mut cs.namespace(|| "t2 <- t2 - t1 (2)"), &t1)?;
+        let t1 = g3.mul(&mut cs.namespace(|| "t1 <- g3 * g5"), g5)?;
+        let t2 = t2.add(&mut cs.namespace(|| "t2 <- t2 + t1"), &t1)?;
+        let g0 = t2.mul_by_nonresidue(&mut cs.namespace(|| "g0 <- t2.mul_by_nonresidue()"))?;
+        let g0 = g0.add(&mut cs.namespace(|| "g0 <- g0 + 1"), &Fp2Element::one())?;
+
+        Ok(Self {
+            c0: Fp6Element {
+                b0: g0,
+                b1: x.c0.b1,
+                b2: x.c0.b2,
+            },
+            c1: Fp6Element {
+                b0: x.c1.b0,
+                b1: g4,
+                b2: x.c1.b2,
+            },
+        })
+    }
+
+    /// Returns `x^(t/2)` for an element of the cyclotomic subgroup, where
+    /// `t = -15132376222941642752` is the seed of BLS12-381. This is the alternative to
+    /// `Torus::expt_half` with cyclotomic and compressed squares.
+    pub fn cyclotomic_expt_half<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self;
+        let mut cs = cs.namespace(|| "Fp12::cyclotomic_expt_half(x)");
+
+        // Same addition chain as Torus::expt_half, where the long runs of squares are done in
+        // the compressed form:
+        //
+        //  return ((((((x^2 * x)^4 * x)^8 * x)^512 * x)^(2^32) * x)^(2^15))^-1
+        //
+        // Operations: 62 squares 5 multiplies
+
+        let z = x.cyclotomic_square(&mut cs.namespace(|| "Step 1: z = x^0x2"))?;
+
+        let z = x.mul(&mut cs.namespace(|| "Step 2: z = x^0x3"), &z)?;
+
+        let z = z.n_cyclotomic_square(&mut cs.namespace(|| "Step 4: z = x^0xc"), 2)?;
+
+        let z = x.mul(&mut cs.namespace(|| "Step 5: z = x^0xd"), &z)?;
+
+        let z = z.n_cyclotomic_square(&mut cs.namespace(|| "Step 8: z = x^0x68"), 3)?;
+
+        let z = x.mul(&mut cs.namespace(|| "Step 9: z = x^0x69"), &z)?;
+
+        let z =
+            z.n_cyclotomic_square_compressed(&mut cs.namespace(|| "Step 18: z = x^0xd200"), 9)?;
+        let z = z.decompress_karabina(&mut cs.namespace(|| "Step 18: decompress"))?;
+
+        let z = x.mul(&mut cs.namespace(|| "Step 19: z = x^0xd201"), &z)?;
+
+        let z = z.n_cyclotomic_square_compressed(
+            &mut cs.namespace(|| "Step 51: z = x^0xd20100000000"),
+            32,
+        )?;
+        let z = z.decompress_karabina(&mut cs.namespace(|| "Step 51: decompress"))?;
+
+        let z = x.mul(&mut cs.namespace(|| "Step 52: z = x^0xd20100000001"), &z)?;
+
+        let z = z.n_cyclotomic_square_compressed(
+            &mut cs.namespace(|| "Step 67: z = x^0x6900800000008000"),
+            15,
+        )?;
+        let z = z.decompress_karabina(&mut cs.namespace(|| "Step 67: decompress"))?;
+
+        // the inverse of an element of the cyclotomic subgroup is its conjugate
+        let z = z.conjugate(&mut cs.namespace(|| "Final step: z = z.conjugate()"))?;
+
+        Ok(z)
+    }
+
+    /// Returns `x^t` for an element of the cyclotomic subgroup, where
+    /// `t = -15132376222941642752` is the seed of BLS12-381. This is the alternative to
+    /// `Torus::expt` with cyclotomic and compressed squares.
+    pub fn cyclotomic_expt<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| "Fp12::cyclotomic_expt(x)");
+        let z = self.cyclotomic_expt_half(&mut cs.namespace(|| "z <- x.cyclotomic_expt_half()"))?;
+        let z = z.cyclotomic_square(&mut cs.namespace(|| "z <- z.cyclotomic_square()"))?;
+        Ok(z)
+    }
+
+    pub fn n_square<CS>(&self, cs: &mut CS, n: usize) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut cs = cs.namespace(|| format!("Fp12::n_square(x, {n})"));
+        let mut x = self.clone();
+        for i in 0..n {
+            x = x.square(&mut cs.namespace(|| format!("x <- x.square() ({i})")))?;
+        }
+        Ok(x)
+    }
+
+    /// Returns `x^|t|` for any element, where `t = -15132376222941642752` is the seed of
+    /// BLS12-381. Unlike `cyclotomic_expt`, this does not assume that x is in the cyclotomic
+    /// subgroup.
+    pub fn expt_abs<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self;
+        let mut cs = cs.namespace(|| "Fp12::expt_abs(x)");
+
+        // Same addition chain as Torus::expt_half, followed by a square
+        let z = x.square(&mut cs.namespace(|| "Step 1: z = x^0x2"))?;
+        let z = x.mul(&mut cs.namespace(|| "Step 2: z = x^0x3"), &z)?;
+        let z = z.n_square(&mut cs.namespace(|| "Step 4: z = x^0xc"), 2)?;
+        let z = x.mul(&mut cs.namespace(|| "Step 5: z = x^0xd"), &z)?;
+        let z = z.n_square(&mut cs.namespace(|| "Step 8: z = x^0x68"), 3)?;
+        let z = x.mul(&mut cs.namespace(|| "Step 9: z = x^0x69"), &z)?;
+        let z = z.n_square(&mut cs.namespace(|| "Step 18: z = x^0xd200"), 9)?;
+        let z = x.mul(&mut cs.namespace(|| "Step 19: z = x^0xd201"), &z)?;
+        let z = z.n_square(&mut cs.namespace(|| "Step 51: z = x^0xd20100000000"), 32)?;
+        let z = x.mul(&mut cs.namespace(|| "Step 52: z = x^0xd20100000001"), &z)?;
+        let z = z.n_square(
+            &mut cs.namespace(|| "Step 68: z = x^0xd201000000010000"),
+            16,
+        )?;
+
+        Ok(z)
+    }
+
     pub fn inverse<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -414,6 +889,7 @@ impl<F: PrimeFieldBits> Fp12Element<F> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fields::torus::Torus;
     use bellpepper_core::test_cs::TestConstraintSystem;
     use halo2curves::bn256::Fq as Fp;
 
@@ -759,6 +1235,70 @@ mod tests {
         expect_eq(cs.num_constraints(), &expect!["2724"]);
     }
 
+    #[test]
+    fn test_random_frobenius() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp12::random(&mut rng);
+        let c = a.frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc.frobenius(&mut cs.namespace(|| "a^p")).unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^p = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_frobenius_square() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp12::random(&mut rng);
+        let c = a.frobenius_map().frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .frobenius_square(&mut cs.namespace(|| "a^(p^2)"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^(p^2) = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_frobenius_cube() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp12::random(&mut rng);
+        let c = a.frobenius_map().frobenius_map().frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .frobenius_cube(&mut cs.namespace(|| "a^(p^3)"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^(p^3) = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
     #[test]
     fn test_random_inverse() {
         let mut rng = rand::thread_rng();
@@ -820,4 +1360,179 @@ mod tests {
         expect_eq(cs.scalar_aux().len(), &expect!["13103"]);
         expect_eq(cs.num_constraints(), &expect!["13163"]);
     }
+
+    /// Returns a random element of the cyclotomic subgroup, `f^((p⁶-1)(p²+1))`
+    fn random_cyclotomic() -> BlsFp12 {
+        let mut rng = rand::thread_rng();
+        let f = BlsFp12::random(&mut rng);
+        let f = f.conjugate() * f.invert().unwrap();
+        f.frobenius_map().frobenius_map() * f
+    }
+
+    /// Returns `x^|t|` natively, where `t = -0xd201000000010000`
+    fn expt_abs_native(x: &BlsFp12) -> BlsFp12 {
+        let mut res = BlsFp12::one();
+        for i in (0..64).rev() {
+            res = res.square();
+            if (0xd201000000010000u64 >> i) & 1 == 1 {
+                res *= x;
+            }
+        }
+        res
+    }
+
+    /// Returns `x^t` natively for an element of the cyclotomic subgroup
+    fn cyclotomic_expt_native(x: &BlsFp12) -> BlsFp12 {
+        expt_abs_native(x).conjugate()
+    }
+
+    #[test]
+    fn test_random_cyclotomic_square() {
+        let a = random_cyclotomic();
+        let c = a.square();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .cyclotomic_square(&mut cs.namespace(|| "a.cyclotomic_square()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a² = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["9732"]);
+        expect_eq(cs.num_constraints(), &expect!["9636"]);
+    }
+
+    #[test]
+    fn test_random_cyclotomic_square_compressed() {
+        let a = random_cyclotomic();
+        let c = a.square().square().square();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .n_cyclotomic_square_compressed(&mut cs.namespace(|| "a.compressed_square()^3"), 3)
+            .unwrap();
+        let res_alloc = res_alloc
+            .decompress_karabina(&mut cs.namespace(|| "res.decompress_karabina()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^8 = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["40230"]);
+        expect_eq(cs.num_constraints(), &expect!["40316"]);
+    }
+
+    #[test]
+    fn test_decompress_karabina_one() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(BlsFp12::one()))
+                .unwrap();
+        let res_alloc = a_alloc
+            .cyclotomic_square_compressed(&mut cs.namespace(|| "a.compressed_square()"))
+            .unwrap();
+        let res_alloc = res_alloc
+            .decompress_karabina(&mut cs.namespace(|| "res.decompress_karabina()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(
+            &mut cs.namespace(|| "1² = 1"),
+            &res_alloc,
+            &Fp12Element::one(),
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_cyclotomic_expt() {
+        let a = random_cyclotomic();
+        let c = cyclotomic_expt_native(&a);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .cyclotomic_expt(&mut cs.namespace(|| "a.cyclotomic_expt()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^t = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["782182"]);
+        expect_eq(cs.num_constraints(), &expect!["785920"]);
+    }
+
+    // same exponentiation as test_random_cyclotomic_expt, through the torus-based arithmetic
+    #[test]
+    fn test_random_torus_expt() {
+        let a = random_cyclotomic();
+        let c = cyclotomic_expt_native(&a);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = Torus::compress(&mut cs.namespace(|| "a <- a.torus()"), &a_alloc).unwrap();
+        let res_alloc = res_alloc.expt(&mut cs.namespace(|| "a.expt()")).unwrap();
+        let res_alloc = res_alloc
+            .decompress(&mut cs.namespace(|| "res.decompress()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^t = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["352790"]);
+        expect_eq(cs.num_constraints(), &expect!["352244"]);
+    }
+
+    #[test]
+    fn test_random_expt_abs() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp12::random(&mut rng);
+        let c = expt_abs_native(&a);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc =
+            Fp12Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .expt_abs(&mut cs.namespace(|| "a.expt_abs()"))
+            .unwrap();
+        Fp12Element::assert_is_equal(&mut cs.namespace(|| "a^|t| = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1015086"]);
+        expect_eq(cs.num_constraints(), &expect!["1019814"]);
+    }
 }
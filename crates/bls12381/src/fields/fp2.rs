@@ -89,6 +89,10 @@ impl<F: PrimeFieldBits> Fp2Element<F> {
         }
     }
 
+    pub fn is_constant(&self) -> bool {
+        self.a0.is_constant() && self.a1.is_constant()
+    }
+
     pub fn non_residue() -> Self {
         Self {
             a0: FpElement::one(),
@@ -335,6 +339,62 @@ impl<F: PrimeFieldBits> Fp2Element<F> {
         )
     }
 
+    /// mul_by_nonresidue_3pow1 returns x*(1+u)^(1*(p^3-1)/6)
+    pub fn mul_by_nonresidue_3pow1<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let elm = Self::from_dec("2973677408986561043442465346520108879172042883009249989176415018091420807192182638567116318576472649347015917690530", "1028732146235106349975324479215795277384839936929757896155643118032610843298655225875571310552543014690878354869257").unwrap();
+        self.mul(
+            &mut cs.namespace(|| "Fp2::mul_by_nonresidue_3pow1(x)"),
+            &elm,
+        )
+    }
+
+    /// mul_by_nonresidue_3pow2 returns x*(1+u)^(2*(p^3-1)/6) = x*u
+    pub fn mul_by_nonresidue_3pow2<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let a = self
+            .a1
+            .neg(&mut cs.namespace(|| "Fp2::mul_by_nonresidue_3pow2(x)"))?;
+        let b = self.a0.clone();
+        Ok(Self { a0: a, a1: b })
+    }
+
+    /// mul_by_nonresidue_3pow3 returns x*(1+u)^(3*(p^3-1)/6)
+    pub fn mul_by_nonresidue_3pow3<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let elm = Self::from_dec("2973677408986561043442465346520108879172042883009249989176415018091420807192182638567116318576472649347015917690530", "2973677408986561043442465346520108879172042883009249989176415018091420807192182638567116318576472649347015917690530").unwrap();
+        self.mul(
+            &mut cs.namespace(|| "Fp2::mul_by_nonresidue_3pow3(x)"),
+            &elm,
+        )
+    }
+
+    /// mul_by_nonresidue_3pow4 returns x*(1+u)^(4*(p^3-1)/6) = -x
+    pub fn mul_by_nonresidue_3pow4<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        self.neg(&mut cs.namespace(|| "Fp2::mul_by_nonresidue_3pow4(x)"))
+    }
+
+    /// mul_by_nonresidue_3pow5 returns x*(1+u)^(5*(p^3-1)/6)
+    pub fn mul_by_nonresidue_3pow5<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let elm = Self::from_dec("1028732146235106349975324479215795277384839936929757896155643118032610843298655225875571310552543014690878354869257", "2973677408986561043442465346520108879172042883009249989176415018091420807192182638567116318576472649347015917690530").unwrap();
+        self.mul(
+            &mut cs.namespace(|| "Fp2::mul_by_nonresidue_3pow5(x)"),
+            &elm,
+        )
+    }
+
     pub fn mul_const<CS>(&self, cs: &mut CS, value: &BigInt) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -478,6 +538,26 @@ impl<F: PrimeFieldBits> Fp2Element<F> {
         Ok(Self { a0, a1 })
     }
 
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    pub fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let inputs_a0 = inputs.iter().map(|i| i.a0.clone()).collect::<Vec<_>>();
+        let inputs_a1 = inputs.iter().map(|i| i.a1.clone()).collect::<Vec<_>>();
+        let a0 = FpElement::<F>::mux_tree(
+            &mut cs.namespace(|| "mux a0"),
+            select_bits.clone(),
+            &inputs_a0,
+        )?;
+        let a1 = FpElement::<F>::mux_tree(&mut cs.namespace(|| "mux a1"), select_bits, &inputs_a1)?;
+        Ok(Self { a0, a1 })
+    }
+
     pub fn sgn0<CS>(&self, cs: &mut CS) -> Result<Boolean, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -498,6 +578,37 @@ impl<F: PrimeFieldBits> Fp2Element<F> {
 
         Ok(out)
     }
+
+    /// Returns whether the element is lexicographically largest, comparing `a1` first and `a0`
+    /// if `a1` is zero, as used by the sign flag of compressed point encodings
+    pub fn lexicographically_largest<CS>(&self, cs: &mut CS) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        // largest[1] || ( (in1 == 0 && largest[0]) )
+        let largest_0 = self.a0.lexicographically_largest(
+            &mut cs.namespace(|| "self.a0.lexicographically_largest()"),
+        )?;
+        let largest_1 = self.a1.lexicographically_largest(
+            &mut cs.namespace(|| "self.a1.lexicographically_largest()"),
+        )?;
+        let is_a1_zero = self
+            .a1
+            .alloc_is_zero(&mut cs.namespace(|| "self.a1.alloc_is_zero()"))?;
+
+        let tmp = Boolean::and(
+            &mut cs.namespace(|| "tmp <- and(is_a1_zero, largest_0)"),
+            &Boolean::from(is_a1_zero),
+            &largest_0,
+        )?;
+        let out = Boolean::or(
+            &mut cs.namespace(|| "out <- or(largest_1, tmp)"),
+            &largest_1,
+            &tmp,
+        )?;
+
+        Ok(out)
+    }
 }
 
 #[cfg(test)]
@@ -840,6 +951,64 @@ mod tests {
         expect_eq(cs.num_constraints(), &expect!["1995"]);
     }
 
+    #[test]
+    fn test_random_lexicographically_largest() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp2::random(&mut rng);
+        let c: bool = a.lexicographically_largest().into();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = Fp2Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = AllocatedBit::alloc(&mut cs.namespace(|| "alloc c"), Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .lexicographically_largest(&mut cs.namespace(|| "a.lexicographically_largest()"))
+            .unwrap();
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "a.lexicographically_largest() = c"),
+            &res_alloc,
+            &Boolean::from(c_alloc),
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1701"]);
+        expect_eq(cs.num_constraints(), &expect!["2007"]);
+    }
+
+    #[test]
+    fn test_random_lexicographically_largest_zero_a1() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp::random(&mut rng);
+        let a = BlsFp2 {
+            c0: a,
+            c1: BlsFp::zero(),
+        };
+        let c: bool = a.lexicographically_largest().into();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = Fp2Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = AllocatedBit::alloc(&mut cs.namespace(|| "alloc c"), Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .lexicographically_largest(&mut cs.namespace(|| "a.lexicographically_largest()"))
+            .unwrap();
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "a.lexicographically_largest() = c"),
+            &res_alloc,
+            &Boolean::from(c_alloc),
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["1701"]);
+        expect_eq(cs.num_constraints(), &expect!["2007"]);
+    }
+
     #[test]
     fn test_random_alloc_is_zero() {
         let mut rng = rand::thread_rng();
@@ -367,6 +367,77 @@ impl<F: PrimeFieldBits> Fp6Element<F> {
         })
     }
 
+    /// frobenius returns x^p
+    pub fn frobenius<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self;
+        let mut cs = cs.namespace(|| "Fp6::frobenius(x)");
+        let z0 =
+            x.b0.conjugate(&mut cs.namespace(|| "z0 <- x.b0.conjugate()"))?;
+        let z1 =
+            x.b1.conjugate(&mut cs.namespace(|| "z1 <- x.b1.conjugate()"))?;
+        let z2 =
+            x.b2.conjugate(&mut cs.namespace(|| "z2 <- x.b2.conjugate()"))?;
+        let z1 =
+            z1.mul_by_nonresidue_1pow2(&mut cs.namespace(|| "z1 <- z1.mul_by_nonresidue_1pow2()"))?;
+        let z2 =
+            z2.mul_by_nonresidue_1pow4(&mut cs.namespace(|| "z2 <- z2.mul_by_nonresidue_1pow4()"))?;
+
+        Ok(Self {
+            b0: z0,
+            b1: z1,
+            b2: z2,
+        })
+    }
+
+    /// frobenius_square returns x^(p^2)
+    pub fn frobenius_square<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self;
+        let mut cs = cs.namespace(|| "Fp6::frobenius_square(x)");
+        let z1 = x.b1.mul_by_nonresidue_2pow2(
+            &mut cs.namespace(|| "z1 <- x.b1.mul_by_nonresidue_2pow2()"),
+        )?;
+        let z2 = x.b2.mul_by_nonresidue_2pow4(
+            &mut cs.namespace(|| "z2 <- x.b2.mul_by_nonresidue_2pow4()"),
+        )?;
+
+        Ok(Self {
+            b0: x.b0.clone(),
+            b1: z1,
+            b2: z2,
+        })
+    }
+
+    /// frobenius_cube returns x^(p^3)
+    pub fn frobenius_cube<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self;
+        let mut cs = cs.namespace(|| "Fp6::frobenius_cube(x)");
+        let z0 =
+            x.b0.conjugate(&mut cs.namespace(|| "z0 <- x.b0.conjugate()"))?;
+        let z1 =
+            x.b1.conjugate(&mut cs.namespace(|| "z1 <- x.b1.conjugate()"))?;
+        let z2 =
+            x.b2.conjugate(&mut cs.namespace(|| "z2 <- x.b2.conjugate()"))?;
+        let z1 =
+            z1.mul_by_nonresidue_3pow2(&mut cs.namespace(|| "z1 <- z1.mul_by_nonresidue_3pow2()"))?;
+        let z2 =
+            z2.mul_by_nonresidue_3pow4(&mut cs.namespace(|| "z2 <- z2.mul_by_nonresidue_3pow4()"))?;
+
+        Ok(Self {
+            b0: z0,
+            b1: z1,
+            b2: z2,
+        })
+    }
+
     pub fn inverse<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -571,6 +642,63 @@ mod tests {
         expect_eq(cs.num_constraints(), &expect!["1362"]);
     }
 
+    #[test]
+    fn test_random_frobenius() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp6::random(&mut rng);
+        let c = a.frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc.frobenius(&mut cs.namespace(|| "a^p")).unwrap();
+        Fp6Element::assert_is_equal(&mut cs.namespace(|| "a^p = c"), &res_alloc, &c_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_frobenius_square() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp6::random(&mut rng);
+        let c = a.frobenius_map().frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .frobenius_square(&mut cs.namespace(|| "a^(p^2)"))
+            .unwrap();
+        Fp6Element::assert_is_equal(&mut cs.namespace(|| "a^(p^2) = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_frobenius_cube() {
+        let mut rng = rand::thread_rng();
+        let a = BlsFp6::random(&mut rng);
+        let c = a.frobenius_map().frobenius_map().frobenius_map();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let a_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc a"), &Some(a)).unwrap();
+        let c_alloc = Fp6Element::alloc_element(&mut cs.namespace(|| "alloc c"), &Some(c)).unwrap();
+        let res_alloc = a_alloc
+            .frobenius_cube(&mut cs.namespace(|| "a^(p^3)"))
+            .unwrap();
+        Fp6Element::assert_is_equal(&mut cs.namespace(|| "a^(p^3) = c"), &res_alloc, &c_alloc)
+            .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
     #[test]
     fn test_random_square() {
         let mut rng = rand::thread_rng();
@@ -0,0 +1,457 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bls12_381::{G1Affine, G2Affine};
+use ff::PrimeFieldBits;
+
+use crate::curves::g1::G1Point;
+use crate::curves::g2::G2Point;
+use crate::curves::pairing::{EmulatedBls12381Pairing, EmulatedPairing};
+
+/// A Groth16 verifying key over BLS12-381
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    /// Elements of the verifying key combined with the public inputs, starting with the one of
+    /// the constant input 1
+    pub ic: Vec<G1Affine>,
+}
+
+/// A Groth16 proof over BLS12-381
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+#[derive(Clone)]
+pub struct AllocatedProof<F: PrimeFieldBits> {
+    pub a: G1Point<F>,
+    pub b: G2Point<F>,
+    pub c: G1Point<F>,
+}
+
+impl<F: PrimeFieldBits> AllocatedProof<F> {
+    pub fn alloc_proof<CS>(cs: &mut CS, value: &Option<Proof>) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let a = G1Point::alloc_element(
+            &mut cs.namespace(|| "allocate A"),
+            &value.as_ref().map(|v| v.a),
+        )?;
+        let b = G2Point::alloc_element(
+            &mut cs.namespace(|| "allocate B"),
+            &value.as_ref().map(|v| v.b),
+        )?;
+        let c = G1Point::alloc_element(
+            &mut cs.namespace(|| "allocate C"),
+            &value.as_ref().map(|v| v.c),
+        )?;
+        Ok(Self { a, b, c })
+    }
+}
+
+/// Verifies Groth16 proofs over BLS12-381 for a given verifying key, which is either a constant
+/// of the circuit or allocated
+#[derive(Clone)]
+pub struct Groth16Verifier<F: PrimeFieldBits> {
+    alpha_g1: G1Point<F>,
+    beta_g2: G2Point<F>,
+    gamma_g2: G2Point<F>,
+    delta_g2: G2Point<F>,
+    ic: Vec<G1Point<F>>,
+    /// Values of `ic` when the verifying key is a constant, used for the MSM with constant bases
+    constant_ic: Option<Vec<G1Affine>>,
+}
+
+impl<F: PrimeFieldBits> Groth16Verifier<F> {
+    /// Returns a verifier for the constant verifying key `vk`. The lines of the Miller loop for
+    /// its G2 points are computed outside of the circuit.
+    pub fn new_constant(vk: &VerifyingKey) -> Self {
+        Self {
+            alpha_g1: G1Point::from(&vk.alpha_g1),
+            beta_g2: G2Point::from(&vk.beta_g2),
+            gamma_g2: G2Point::from(&vk.gamma_g2),
+            delta_g2: G2Point::from(&vk.delta_g2),
+            ic: vk.ic.iter().map(G1Point::from).collect(),
+            constant_ic: Some(vk.ic.clone()),
+        }
+    }
+
+    /// Allocates a verifying key for `num_public_inputs` public inputs. The points of the key are
+    /// not validated, so it is expected to be fixed by other means, e.g. a commitment.
+    pub fn alloc_verifier<CS>(
+        cs: &mut CS,
+        vk: &Option<VerifyingKey>,
+        num_public_inputs: usize,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if matches!(vk, Some(vk) if vk.ic.len() != num_public_inputs + 1) {
+            eprintln!("Verifying key does not match the number of public inputs");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let alpha_g1 = G1Point::alloc_element(
+            &mut cs.namespace(|| "allocate alpha"),
+            &vk.as_ref().map(|vk| vk.alpha_g1),
+        )?;
+        let beta_g2 = G2Point::alloc_element(
+            &mut cs.namespace(|| "allocate beta"),
+            &vk.as_ref().map(|vk| vk.beta_g2),
+        )?;
+        let gamma_g2 = G2Point::alloc_element(
+            &mut cs.namespace(|| "allocate gamma"),
+            &vk.as_ref().map(|vk| vk.gamma_g2),
+        )?;
+        let delta_g2 = G2Point::alloc_element(
+            &mut cs.namespace(|| "allocate delta"),
+            &vk.as_ref().map(|vk| vk.delta_g2),
+        )?;
+        let ic = (0..=num_public_inputs)
+            .map(|i| {
+                G1Point::alloc_element(
+                    &mut cs.namespace(|| format!("allocate ic[{i}]")),
+                    &vk.as_ref().map(|vk| vk.ic[i]),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            ic,
+            constant_ic: None,
+        })
+    }
+
+    /// Verifies `proof` for the public inputs, given as little-endian bits of elements of the
+    /// scalar field of BLS12-381
+    ///
+    /// The points of the proof are checked to be on their curve and in their prime order
+    /// subgroup, and `e(A, B) == e(alpha, beta) * e(IC, gamma) * e(C, delta)` is enforced with a
+    /// single pairing check, where `IC = ic[0] + sum(x_i * ic[i])`. The public inputs are not
+    /// required to be reduced modulo the group order.
+    pub fn verify<CS>(
+        &self,
+        cs: &mut CS,
+        proof: &AllocatedProof<F>,
+        public_inputs: &[Vec<Boolean>],
+    ) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if public_inputs.len() + 1 != self.ic.len() {
+            eprintln!(
+                "Expected {} public inputs, got {}",
+                self.ic.len() - 1,
+                public_inputs.len()
+            );
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        proof
+            .a
+            .assert_is_on_curve(&mut cs.namespace(|| "A.assert_is_on_curve()"))?;
+        proof
+            .a
+            .assert_subgroup_check(&mut cs.namespace(|| "A.assert_subgroup_check()"))?;
+        proof
+            .b
+            .assert_is_on_curve(&mut cs.namespace(|| "B.assert_is_on_curve()"))?;
+        proof
+            .b
+            .assert_subgroup_check(&mut cs.namespace(|| "B.assert_subgroup_check()"))?;
+        proof
+            .c
+            .assert_is_on_curve(&mut cs.namespace(|| "C.assert_is_on_curve()"))?;
+        proof
+            .c
+            .assert_subgroup_check(&mut cs.namespace(|| "C.assert_subgroup_check()"))?;
+
+        let ic = self.compute_ic(
+            &mut cs.namespace(|| "IC <- ic[0] + sum(x_i * ic[i])"),
+            public_inputs,
+        )?;
+        let neg_a = proof.a.neg(&mut cs.namespace(|| "-A"))?;
+
+        EmulatedBls12381Pairing::assert_pairing_check(
+            &mut cs.namespace(|| "e(-A, B) * e(alpha, beta) * e(IC, gamma) * e(C, delta) == 1"),
+            &[neg_a, self.alpha_g1.clone(), ic, proof.c.clone()],
+            &[
+                proof.b.clone(),
+                self.beta_g2.clone(),
+                self.gamma_g2.clone(),
+                self.delta_g2.clone(),
+            ],
+        )
+    }
+
+    /// Returns `ic[0] + sum(x_i * ic[i])`
+    fn compute_ic<CS>(
+        &self,
+        cs: &mut CS,
+        public_inputs: &[Vec<Boolean>],
+    ) -> Result<G1Point<F>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if public_inputs.is_empty() {
+            return Ok(self.ic[0].clone());
+        }
+
+        let sum = match &self.constant_ic {
+            Some(constant_ic) => G1Point::msm_constant_bases(
+                &mut cs.namespace(|| "sum <- msm(ic[1..], x)"),
+                &constant_ic[1..],
+                public_inputs,
+            )?,
+            None => G1Point::msm(
+                &mut cs.namespace(|| "sum <- msm(ic[1..], x)"),
+                &self.ic[1..],
+                public_inputs,
+            )?,
+        };
+        self.ic[0].add_unified(&mut cs.namespace(|| "ic[0] + sum"), &sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::groth16::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+    };
+    use bellman::{Circuit, LinearCombination};
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bls12_381::{pairing, G1Projective, Scalar};
+    use bls12_381_bellman::Bls12;
+    use ff::Field;
+    use halo2curves::bn256::Fq as Fp;
+
+    use expect_test::{expect, Expect};
+    fn expect_eq(computed: usize, expected: &Expect) {
+        expected.assert_eq(&computed.to_string());
+    }
+
+    /// Proves knowledge of x such that x^3 + x + 5 = y, with public inputs y and x^2
+    struct CubeCircuit {
+        x: Option<bls12_381_bellman::Scalar>,
+    }
+
+    impl Circuit<bls12_381_bellman::Scalar> for CubeCircuit {
+        fn synthesize<CS: bellman::ConstraintSystem<bls12_381_bellman::Scalar>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), bellman::SynthesisError> {
+            let x_val = self.x;
+            let x2_val = x_val.map(|x| x.square());
+            let x3_val = x_val.zip(x2_val).map(|(x, x2)| x * x2);
+            let y_val = x_val
+                .zip(x3_val)
+                .map(|(x, x3)| x3 + x + bls12_381_bellman::Scalar::from(5));
+
+            let x = cs.alloc(
+                || "x",
+                || x_val.ok_or(bellman::SynthesisError::AssignmentMissing),
+            )?;
+            let x2 = cs.alloc_input(
+                || "x^2",
+                || x2_val.ok_or(bellman::SynthesisError::AssignmentMissing),
+            )?;
+            let x3 = cs.alloc(
+                || "x^3",
+                || x3_val.ok_or(bellman::SynthesisError::AssignmentMissing),
+            )?;
+            let y = cs.alloc_input(
+                || "y",
+                || y_val.ok_or(bellman::SynthesisError::AssignmentMissing),
+            )?;
+
+            cs.enforce(|| "x^2 = x * x", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+            cs.enforce(|| "x^3 = x^2 * x", |lc| lc + x2, |lc| lc + x, |lc| lc + x3);
+            cs.enforce(
+                || "y = x^3 + x + 5",
+                |lc| lc + x3 + x + (bls12_381_bellman::Scalar::from(5), CS::one()),
+                |lc| lc + CS::one(),
+                |_| LinearCombination::zero() + y,
+            );
+            Ok(())
+        }
+    }
+
+    fn g1_from_bellman(p: &bls12_381_bellman::G1Affine) -> G1Affine {
+        G1Affine::from_uncompressed(&p.to_uncompressed()).unwrap()
+    }
+
+    fn g2_from_bellman(p: &bls12_381_bellman::G2Affine) -> G2Affine {
+        G2Affine::from_uncompressed(&p.to_uncompressed()).unwrap()
+    }
+
+    /// Returns a verifying key, a proof and its public inputs generated with bellman
+    fn bellman_proof() -> (VerifyingKey, Proof, Vec<Scalar>) {
+        let mut rng = rand::thread_rng();
+        let params =
+            generate_random_parameters::<Bls12, _, _>(CubeCircuit { x: None }, &mut rng).unwrap();
+        let x = bls12_381_bellman::Scalar::random(&mut rng);
+        let x2 = x.square();
+        let y = x2 * x + x + bls12_381_bellman::Scalar::from(5);
+        let proof = create_random_proof(CubeCircuit { x: Some(x) }, &params, &mut rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        assert!(verify_proof(&pvk, &proof, &[x2, y]).is_ok());
+
+        let vk = VerifyingKey {
+            alpha_g1: g1_from_bellman(&params.vk.alpha_g1),
+            beta_g2: g2_from_bellman(&params.vk.beta_g2),
+            gamma_g2: g2_from_bellman(&params.vk.gamma_g2),
+            delta_g2: g2_from_bellman(&params.vk.delta_g2),
+            ic: params.vk.ic.iter().map(g1_from_bellman).collect(),
+        };
+        let proof = Proof {
+            a: g1_from_bellman(&proof.a),
+            b: g2_from_bellman(&proof.b),
+            c: g1_from_bellman(&proof.c),
+        };
+        let inputs = [x2, y]
+            .iter()
+            .map(|v| Scalar::from_bytes(&v.to_bytes()).unwrap())
+            .collect::<Vec<_>>();
+
+        // sanity check of the conversion and of the verification equation
+        let ic = inputs
+            .iter()
+            .zip(&vk.ic[1..])
+            .fold(G1Projective::from(vk.ic[0]), |acc, (x, p)| acc + p * x);
+        assert_eq!(
+            pairing(&proof.a, &proof.b),
+            pairing(&vk.alpha_g1, &vk.beta_g2)
+                + pairing(&G1Affine::from(ic), &vk.gamma_g2)
+                + pairing(&proof.c, &vk.delta_g2)
+        );
+
+        (vk, proof, inputs)
+    }
+
+    fn alloc_inputs(cs: &mut TestConstraintSystem<Fp>, inputs: &[Scalar]) -> Vec<Vec<Boolean>> {
+        inputs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let bytes = x.to_bytes();
+                (0..255)
+                    .map(|j| {
+                        Boolean::from(
+                            AllocatedBit::alloc(
+                                cs.namespace(|| format!("alloc x_{i} bit {j}")),
+                                Some((bytes[j / 8] >> (j % 8)) & 1 == 1),
+                            )
+                            .unwrap(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn verify_helper(
+        vk: &VerifyingKey,
+        proof: &Proof,
+        inputs: &[Scalar],
+        constant_vk: bool,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let inputs = alloc_inputs(&mut cs, inputs);
+        let verifier = if constant_vk {
+            Groth16Verifier::new_constant(vk)
+        } else {
+            Groth16Verifier::alloc_verifier(
+                &mut cs.namespace(|| "alloc vk"),
+                &Some(vk.clone()),
+                inputs.len(),
+            )
+            .unwrap()
+        };
+        let proof =
+            AllocatedProof::alloc_proof(&mut cs.namespace(|| "alloc proof"), &Some(proof.clone()))
+                .unwrap();
+        verifier
+            .verify(&mut cs.namespace(|| "verify(proof, x)"), &proof, &inputs)
+            .unwrap();
+        cs
+    }
+
+    // NOTE: this test currently takes more than 80GB of ram to run
+    #[test]
+    fn test_groth16_verifier_constant_vk() {
+        let (vk, proof, inputs) = bellman_proof();
+        let cs = verify_helper(&vk, &proof, &inputs, true);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    // NOTE: this test currently takes more than 80GB of ram to run
+    #[test]
+    fn test_groth16_verifier_allocated_vk() {
+        let (vk, proof, inputs) = bellman_proof();
+        let cs = verify_helper(&vk, &proof, &inputs, false);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    // NOTE: this test currently takes more than 80GB of ram to run
+    #[test]
+    fn test_groth16_verifier_wrong_input() {
+        let (vk, proof, mut inputs) = bellman_proof();
+        inputs[1] += Scalar::one();
+        let cs = verify_helper(&vk, &proof, &inputs, true);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_groth16_compute_ic_constant_vk() {
+        let (vk, _, inputs) = bellman_proof();
+        let expected = inputs
+            .iter()
+            .zip(&vk.ic[1..])
+            .fold(G1Projective::from(vk.ic[0]), |acc, (x, p)| acc + p * x);
+        let expected = G1Affine::from(expected);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let input_bits = alloc_inputs(&mut cs, &inputs);
+        let verifier = Groth16Verifier::new_constant(&vk);
+        let ic = verifier
+            .compute_ic(&mut cs.namespace(|| "compute_ic(x)"), &input_bits)
+            .unwrap();
+        assert_eq!(G1Affine::try_from(&ic).unwrap(), expected);
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_constraints(), &expect!["717146"]);
+    }
+
+    #[test]
+    fn test_groth16_verifier_invalid_inputs() {
+        let (vk, proof, inputs) = bellman_proof();
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let inputs = alloc_inputs(&mut cs, &inputs);
+        let verifier = Groth16Verifier::new_constant(&vk);
+        let proof =
+            AllocatedProof::alloc_proof(&mut cs.namespace(|| "alloc proof"), &Some(proof)).unwrap();
+        let res = verifier.verify(&mut cs.namespace(|| "missing input"), &proof, &inputs[..1]);
+        assert!(res.is_err());
+
+        let res =
+            Groth16Verifier::<Fp>::alloc_verifier(&mut cs.namespace(|| "alloc vk"), &Some(vk), 3);
+        assert!(res.is_err());
+    }
+}
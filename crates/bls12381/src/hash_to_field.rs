@@ -0,0 +1,466 @@
+use bellpepper::gadgets::sha256::sha256;
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_sha512::sha512::sha512;
+use ff::PrimeFieldBits;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::fields::fp::{Bls12381Fp, FpElement};
+use crate::fields::fp2::Fp2Element;
+
+/// Number of bytes used to derive each Fp element in `hash_to_field`, i.e.
+/// `L = ceil((ceil(log2(p)) + k) / 8)` with the security parameter `k = 128`
+const HASH_TO_FIELD_L: usize = 64;
+
+/// Hash function used by `expand_message_xmd`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XmdHash {
+    Sha256,
+    Sha512,
+}
+
+impl XmdHash {
+    /// Output size of the hash function in bytes
+    fn b_in_bytes(&self) -> usize {
+        match self {
+            Self::Sha256 => 32,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// Input block size of the hash function in bytes
+    fn s_in_bytes(&self) -> usize {
+        match self {
+            Self::Sha256 => 64,
+            Self::Sha512 => 128,
+        }
+    }
+
+    fn hash<F, CS>(&self, cs: CS, input: &[Boolean]) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        F: PrimeFieldBits,
+        CS: ConstraintSystem<F>,
+    {
+        match self {
+            Self::Sha256 => sha256(cs, input),
+            Self::Sha512 => sha512(cs, input),
+        }
+    }
+
+    /// Hashes the constant `input` outside of the circuit
+    fn hash_native(&self, input: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(input).to_vec(),
+            Self::Sha512 => Sha512::digest(input).to_vec(),
+        }
+    }
+}
+
+/// Returns the constant bits of `bytes`, with the bits of each byte in big-endian order
+fn bytes_to_bits(bytes: &[u8]) -> Vec<Boolean> {
+    bytes
+        .iter()
+        .flat_map(|byte| {
+            (0..8)
+                .rev()
+                .map(move |i| Boolean::constant((byte >> i) & 1 == 1))
+        })
+        .collect()
+}
+
+/// Implementation of `expand_message_xmd` from [section 5.3.1 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message_xmd)
+///
+/// The bits of the message and of the output are in the order used by the hash gadgets,
+/// i.e. bytes with the bits of each byte in big-endian order. The domain separation tag
+/// `dst` is a constant. A `dst` longer than 255 bytes is replaced by
+/// `H("H2C-OVERSIZE-DST-" || dst)`, as specified in [section 5.3.3 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-using-dsts-longer-than-255-).
+pub fn expand_message_xmd<F, CS>(
+    cs: &mut CS,
+    hash: XmdHash,
+    msg: &[Boolean],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if msg.len() % 8 != 0 {
+        eprintln!("Message bit length must be a multiple of 8");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let b_in_bytes = hash.b_in_bytes();
+    let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+    if len_in_bytes == 0 || ell > 255 || len_in_bytes > 65535 {
+        eprintln!("Invalid output length for expand_message_xmd: {len_in_bytes}");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let dst = if dst.len() > 255 {
+        hash.hash_native(&[b"H2C-OVERSIZE-DST-", dst].concat())
+    } else {
+        dst.to_vec()
+    };
+
+    // DST_prime = DST || I2OSP(len(DST), 1)
+    let mut dst_prime = dst.clone();
+    dst_prime.push(dst.len() as u8);
+    let dst_prime = bytes_to_bits(&dst_prime);
+
+    // msg_prime = Z_pad || msg || I2OSP(len_in_bytes, 2) || I2OSP(0, 1) || DST_prime
+    let mut msg_prime = bytes_to_bits(&vec![0u8; hash.s_in_bytes()]);
+    msg_prime.extend_from_slice(msg);
+    msg_prime.extend(bytes_to_bits(&[
+        (len_in_bytes >> 8) as u8,
+        len_in_bytes as u8,
+        0,
+    ]));
+    msg_prime.extend_from_slice(&dst_prime);
+    let b_0 = hash.hash(cs.namespace(|| "b_0 <- H(msg_prime)"), &msg_prime)?;
+
+    let mut uniform_bytes: Vec<Boolean> = vec![];
+    let mut b_i: Vec<Boolean> = vec![];
+    for i in 1..=ell {
+        // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+        // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+        let mut input = if i == 1 {
+            b_0.clone()
+        } else {
+            b_0.iter()
+                .zip(&b_i)
+                .enumerate()
+                .map(|(j, (a, b))| {
+                    Boolean::xor(
+                        cs.namespace(|| format!("bit {j} of b_0 xor b_{}", i - 1)),
+                        a,
+                        b,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        input.extend(bytes_to_bits(&[i as u8]));
+        input.extend_from_slice(&dst_prime);
+        b_i = hash.hash(cs.namespace(|| format!("b_{i} <- H(b_{})", i - 1)), &input)?;
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(8 * len_in_bytes);
+
+    Ok(uniform_bytes)
+}
+
+/// Returns the Fp element equal to the big-endian `bits` modulo p
+fn fp_from_bits_be<F, CS>(cs: &mut CS, bits: &[Boolean]) -> Result<FpElement<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let bits_le = bits.iter().rev().cloned().collect::<Vec<_>>();
+    let value = Bls12381Fp::<F>::from_bits_le(CS::one(), &bits_le);
+    let bits_le = value.to_bits_le(&mut cs.namespace(|| "reduce bits modulo p"))?;
+    Ok(FpElement(Bls12381Fp::<F>::from_bits_le(
+        CS::one(),
+        &bits_le,
+    )))
+}
+
+/// Implementation of `hash_to_field` from [section 5.2 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-hash_to_field-implementatio)
+/// for Fp, using `expand_message_xmd`. Returns `count` elements of Fp.
+pub fn hash_to_field_fp<F, CS>(
+    cs: &mut CS,
+    hash: XmdHash,
+    msg: &[Boolean],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<FpElement<F>>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let uniform_bytes = expand_message_xmd(
+        &mut cs.namespace(|| "expand_message_xmd(msg, DST)"),
+        hash,
+        msg,
+        dst,
+        count * HASH_TO_FIELD_L,
+    )?;
+    uniform_bytes
+        .chunks(8 * HASH_TO_FIELD_L)
+        .enumerate()
+        .map(|(i, bits)| fp_from_bits_be(&mut cs.namespace(|| format!("u_{i}")), bits))
+        .collect()
+}
+
+/// Implementation of `hash_to_field` from [section 5.2 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-hash_to_field-implementatio)
+/// for Fp2, using `expand_message_xmd`. Returns `count` elements of Fp2.
+pub fn hash_to_field_fp2<F, CS>(
+    cs: &mut CS,
+    hash: XmdHash,
+    msg: &[Boolean],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<Fp2Element<F>>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let uniform_bytes = expand_message_xmd(
+        &mut cs.namespace(|| "expand_message_xmd(msg, DST)"),
+        hash,
+        msg,
+        dst,
+        2 * count * HASH_TO_FIELD_L,
+    )?;
+    uniform_bytes
+        .chunks(16 * HASH_TO_FIELD_L)
+        .enumerate()
+        .map(|(i, bits)| {
+            let (e0, e1) = bits.split_at(8 * HASH_TO_FIELD_L);
+            let a0 = fp_from_bits_be(&mut cs.namespace(|| format!("u_{i}.a0")), e0)?;
+            let a1 = fp_from_bits_be(&mut cs.namespace(|| format!("u_{i}.a1")), e1)?;
+            Ok(Fp2Element { a0, a1 })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bls12_381::fp::Fp as BlsFp;
+    use bls12_381::fp2::Fp2 as BlsFp2;
+    use halo2curves::bn256::Fq as Fp;
+    use num_bigint::BigInt;
+
+    use crate::fields::fp::bigint_to_fpelem;
+
+    use expect_test::{expect, Expect};
+    fn expect_eq(computed: usize, expected: &Expect) {
+        expected.assert_eq(&computed.to_string());
+    }
+
+    fn alloc_msg_bits(cs: &mut TestConstraintSystem<Fp>, msg: &[u8]) -> Vec<Boolean> {
+        msg.iter()
+            .enumerate()
+            .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+            .map(|(i, j, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc bit {j} of msg byte {i}")),
+                        Some(b),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn bits_to_hex(bits: &[Boolean]) -> String {
+        bits.chunks(8)
+            .map(|byte| {
+                let byte = byte
+                    .iter()
+                    .fold(0u8, |acc, b| (acc << 1) | b.get_value().unwrap() as u8);
+                format!("{byte:02x}")
+            })
+            .collect()
+    }
+
+    fn fp_from_hex(v: &str) -> BlsFp {
+        bigint_to_fpelem(&BigInt::parse_bytes(v.as_bytes(), 16).unwrap()).unwrap()
+    }
+
+    // Test vectors from appendix K.1 and K.3 of RFC 9380 (msg, len_in_bytes, uniform_bytes)
+    const SHA256_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    const SHA256_VECTORS: [(&str, usize, &str); 4] = [
+        ("", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+        ("abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+        ("", 0x80, "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+        ("abc", 0x80, "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"),
+    ];
+    const SHA512_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA512-256";
+    const SHA512_VECTORS: [(&str, usize, &str); 4] = [
+        ("", 0x20, "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba"),
+        ("abc", 0x20, "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc"),
+        ("", 0x80, "41b037d1734a5f8df225dd8c7de38f851efdb45c372887be655212d07251b921b052b62eaed99b46f72f2ef4cc96bfaf254ebbbec091e1a3b9e4fb5e5b619d2e0c5414800a1d882b62bb5cd1778f098b8eb6cb399d5d9d18f5d5842cf5d13d7eb00a7cff859b605da678b318bd0e65ebff70bec88c753b159a805d2c89c55961"),
+        ("abc", 0x80, "7f1dddd13c08b543f2e2037b14cefb255b44c83cc397c1786d975653e36a6b11bdd7732d8b38adb4a0edc26a0cef4bb45217135456e58fbca1703cd6032cb1347ee720b87972d63fbf232587043ed2901bce7f22610c0419751c065922b488431851041310ad659e4b23520e1772ab29dcdeb2002222a363f0c2b1c972b3efe1"),
+    ];
+
+    // Test vectors from appendix K.2 of RFC 9380, whose DST is longer than 255 bytes
+    const SHA256_LONG_DST: &str = "QUUX-V01-CS02-with-expander-SHA256-128-long-DST-1111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111";
+    const SHA256_LONG_DST_VECTORS: [(&str, usize, &str); 4] = [
+        ("", 0x20, "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3"),
+        ("abc", 0x20, "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12"),
+        ("", 0x80, "14604d85432c68b757e485c8894db3117992fc57e0e136f71ad987f789a0abc287c47876978e2388a02af86b1e8d1342e5ce4f7aaa07a87321e691f6fba7e0072eecc1218aebb89fb14a0662322d5edbd873f0eb35260145cd4e64f748c5dfe60567e126604bcab1a3ee2dc0778102ae8a5cfd1429ebc0fa6bf1a53c36f55dfc"),
+        ("abc", 0x80, "1a30a5e36fbdb87077552b9d18b9f0aee16e80181d5b951d0471d55b66684914aef87dbb3626eaabf5ded8cd0686567e503853e5c84c259ba0efc37f71c839da2129fe81afdaec7fbdc0ccd4c794727a17c0d20ff0ea55e1389d6982d1241cb8d165762dbc39fb0cee4474d2cbbd468a835ae5b2f20e4f959f56ab24cd6fe267"),
+    ];
+
+    fn expand_message_xmd_helper(
+        hash: XmdHash,
+        dst: &[u8],
+        vector: &(&str, usize, &str),
+    ) -> TestConstraintSystem<Fp> {
+        let (msg, len_in_bytes, expected) = vector;
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msg_bits = alloc_msg_bits(&mut cs, msg.as_bytes());
+        let res = expand_message_xmd(
+            &mut cs.namespace(|| "expand_message_xmd"),
+            hash,
+            &msg_bits,
+            dst,
+            *len_in_bytes,
+        )
+        .unwrap();
+        assert_eq!(&bits_to_hex(&res), expected);
+        assert!(cs.is_satisfied());
+        cs
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha256() {
+        for vector in SHA256_VECTORS.iter() {
+            expand_message_xmd_helper(XmdHash::Sha256, SHA256_DST, vector);
+        }
+        let cs = expand_message_xmd_helper(XmdHash::Sha256, SHA256_DST, &SHA256_VECTORS[3]);
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["202064"]);
+        expect_eq(cs.num_constraints(), &expect!["202274"]);
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha512() {
+        for vector in SHA512_VECTORS.iter() {
+            expand_message_xmd_helper(XmdHash::Sha512, SHA512_DST, vector);
+        }
+        let cs = expand_message_xmd_helper(XmdHash::Sha512, SHA512_DST, &SHA512_VECTORS[3]);
+        expect_eq(cs.num_inputs(), &expect!["1"]);
+        expect_eq(cs.scalar_aux().len(), &expect!["193192"]);
+        expect_eq(cs.num_constraints(), &expect!["193422"]);
+    }
+
+    #[test]
+    fn test_expand_message_xmd_long_dst() {
+        assert_eq!(SHA256_LONG_DST.len(), 256);
+        for vector in SHA256_LONG_DST_VECTORS.iter() {
+            expand_message_xmd_helper(XmdHash::Sha256, SHA256_LONG_DST.as_bytes(), vector);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_invalid_inputs() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msg_bits = alloc_msg_bits(&mut cs, b"abc");
+        let dst = [0u8; 1];
+        for (i, (msg, dst, len_in_bytes)) in [
+            (&msg_bits[..7], &dst[..], 32),
+            (&msg_bits[..], &dst[..], 0),
+            (&msg_bits[..], &dst[..], 256 * 32),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let res = expand_message_xmd(
+                &mut cs.namespace(|| format!("expand_message_xmd {i}")),
+                XmdHash::Sha256,
+                msg,
+                dst,
+                len_in_bytes,
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    #[test]
+    fn test_hash_to_field_fp2() {
+        // Test vectors for BLS12381G2_XMD:SHA-256_SSWU_RO_ from appendix J.10.1 of RFC 9380
+        // (msg, u[0], u[1]) with each element of Fp2 given as (c0, c1)
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let vectors = [
+            (
+                "",
+                [
+                    "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8",
+                    "05a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+                ],
+                [
+                    "02f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee94",
+                    "145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+                ],
+            ),
+            (
+                "abc",
+                [
+                    "15f7c0aa8f6b296ab5ff9c2c7581ade64f4ee6f1bf18f55179ff44a2cf355fa53dd2a2158c5ecb17d7c52f63e7195771",
+                    "01c8067bf4c0ba709aa8b9abc3d1cef589a4758e09ef53732d670fd8739a7274e111ba2fcaa71b3d33df2a3a0c8529dd",
+                ],
+                [
+                    "187111d5e088b6b9acfdfad078c4dacf72dcd17ca17c82be35e79f8c372a693f60a033b461d81b025864a0ad051a06e4",
+                    "08b852331c96ed983e497ebc6dee9b75e373d923b729194af8e72a051ea586f3538a6ebb1e80881a082fa2b24df9f566",
+                ],
+            ),
+        ];
+
+        for (msg, u0, u1) in vectors.iter() {
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let msg_bits = alloc_msg_bits(&mut cs, msg.as_bytes());
+            let res = hash_to_field_fp2(
+                &mut cs.namespace(|| "hash_to_field"),
+                XmdHash::Sha256,
+                &msg_bits,
+                DST,
+                2,
+            )
+            .unwrap();
+            assert_eq!(res.len(), 2);
+            for (u, expected) in res.iter().zip([u0, u1]) {
+                let expected = BlsFp2 {
+                    c0: fp_from_hex(expected[0]),
+                    c1: fp_from_hex(expected[1]),
+                };
+                assert_eq!(BlsFp2::try_from(u).unwrap(), expected);
+            }
+            assert!(cs.is_satisfied());
+            if msg.is_empty() {
+                expect_eq(cs.num_inputs(), &expect!["1"]);
+                expect_eq(cs.scalar_aux().len(), &expect!["4244"]);
+                expect_eq(cs.num_constraints(), &expect!["4872"]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_to_field_fp() {
+        // Test vectors for BLS12381G1_XMD:SHA-256_SSWU_RO_ from appendix J.9.1 of RFC 9380
+        // (msg, u[0], u[1])
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let vectors = [
+            (
+                "",
+                "0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f",
+                "019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9",
+            ),
+            (
+                "abc",
+                "0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951",
+                "003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139",
+            ),
+        ];
+
+        for (msg, u0, u1) in vectors.iter() {
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let msg_bits = alloc_msg_bits(&mut cs, msg.as_bytes());
+            let res = hash_to_field_fp(
+                &mut cs.namespace(|| "hash_to_field"),
+                XmdHash::Sha256,
+                &msg_bits,
+                DST,
+                2,
+            )
+            .unwrap();
+            assert_eq!(res.len(), 2);
+            assert_eq!(BlsFp::try_from(&res[0]).unwrap(), fp_from_hex(u0));
+            assert_eq!(BlsFp::try_from(&res[1]).unwrap(), fp_from_hex(u1));
+            assert!(cs.is_satisfied());
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bls12_381::{pairing, G1Affine, G2Affine};
+use ff::PrimeFieldBits;
+
+use crate::curves::g1::G1Point;
+use crate::curves::gt::GtElement;
+use crate::curves::pairing::{EmulatedBls12381Pairing, EmulatedPairing};
+
+/// Verifies a KZG opening proof, as `verify_kzg_proof` of EIP-4844: the polynomial committed to
+/// by `commitment` evaluates to `y` at `z`, where `z` and `y` are given as little-endian bits of
+/// elements of the scalar field of BLS12-381 and `tau_g2` is `[τ]G2` from the trusted setup.
+///
+/// The commitment and the proof are checked to be either the point at infinity or on the curve
+/// and in the prime order subgroup, and `e(C - [y]G1, G2) == e(π, [τ]G2 - [z]G2)` is enforced
+/// as `e(C - [y]G1 + [z]π, -G2) * e(π, [τ]G2) == 1`.
+pub fn verify_kzg_proof<F, CS>(
+    cs: &mut CS,
+    commitment: &G1Point<F>,
+    z: &[Boolean],
+    y: &[Boolean],
+    proof: &G1Point<F>,
+    tau_g2: &G2Affine,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    assert_is_valid_point(&mut cs.namespace(|| "check commitment"), commitment)?;
+    assert_is_valid_point(&mut cs.namespace(|| "check proof"), proof)?;
+    let lhs = opening_lhs(
+        &mut cs.namespace(|| "lhs <- C - [y]G1 + [z]proof"),
+        commitment,
+        z,
+        y,
+        proof,
+    )?;
+    assert_opening_pairing_check(
+        &mut cs.namespace(|| "e(lhs, -G2) * e(proof, [tau]G2) == 1"),
+        &lhs,
+        proof,
+        tau_g2,
+    )
+}
+
+/// Verifies several KZG opening proofs with a single pairing check, by taking a random linear
+/// combination of their verification equations with `coefficients`, given as little-endian
+/// bits. The coefficients must be unpredictable to the prover, e.g. derived from a hash of all
+/// the commitments, points, values and proofs.
+pub fn verify_kzg_proof_batch<F, CS>(
+    cs: &mut CS,
+    commitments: &[G1Point<F>],
+    zs: &[Vec<Boolean>],
+    ys: &[Vec<Boolean>],
+    proofs: &[G1Point<F>],
+    coefficients: &[Vec<Boolean>],
+    tau_g2: &G2Affine,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let n = commitments.len();
+    if n == 0 || zs.len() != n || ys.len() != n || proofs.len() != n || coefficients.len() != n {
+        eprintln!("KZG batch verification needs the same non-zero number of each input");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let mut lhs = Vec::with_capacity(n);
+    for i in 0..n {
+        assert_is_valid_point(
+            &mut cs.namespace(|| format!("check commitment[{i}]")),
+            &commitments[i],
+        )?;
+        assert_is_valid_point(
+            &mut cs.namespace(|| format!("check proof[{i}]")),
+            &proofs[i],
+        )?;
+        lhs.push(opening_lhs(
+            &mut cs.namespace(|| format!("lhs[{i}] <- C[{i}] - [y[{i}]]G1 + [z[{i}]]proof[{i}]")),
+            &commitments[i],
+            &zs[i],
+            &ys[i],
+            &proofs[i],
+        )?);
+    }
+
+    let lhs = G1Point::msm(
+        &mut cs.namespace(|| "lhs <- msm(lhs, r)"),
+        &lhs,
+        coefficients,
+    )?;
+    let proof = G1Point::msm(
+        &mut cs.namespace(|| "proof <- msm(proof, r)"),
+        proofs,
+        coefficients,
+    )?;
+    assert_opening_pairing_check(
+        &mut cs.namespace(|| "e(lhs, -G2) * e(proof, [tau]G2) == 1"),
+        &lhs,
+        &proof,
+        tau_g2,
+    )
+}
+
+/// Asserts that `p` is either the point at infinity or on the curve and in G1
+fn assert_is_valid_point<F, CS>(cs: &mut CS, p: &G1Point<F>) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let is_identity = p.alloc_is_identity(&mut cs.namespace(|| "p.is_identity()"))?;
+    // the checks do not hold for (0, 0), so they are done on the generator instead
+    let p = G1Point::conditionally_select(
+        &mut cs.namespace(|| "p <- select(p, g1, is_identity)"),
+        p,
+        &G1Point::from(&G1Affine::generator()),
+        &Boolean::from(is_identity),
+    )?;
+    p.assert_is_on_curve(&mut cs.namespace(|| "p.assert_is_on_curve()"))?;
+    p.assert_subgroup_check(&mut cs.namespace(|| "p.assert_subgroup_check()"))
+}
+
+/// Returns `C - [y]G1 + [z]π`
+fn opening_lhs<F, CS>(
+    cs: &mut CS,
+    commitment: &G1Point<F>,
+    z: &[Boolean],
+    y: &[Boolean],
+    proof: &G1Point<F>,
+) -> Result<G1Point<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let z_proof = G1Point::msm(
+        &mut cs.namespace(|| "z_proof <- [z]proof"),
+        std::slice::from_ref(proof),
+        &[z.to_vec()],
+    )?;
+    let y_g1 = G1Point::msm_constant_bases(
+        &mut cs.namespace(|| "y_g1 <- [y]G1"),
+        &[G1Affine::generator()],
+        &[y.to_vec()],
+    )?;
+    let y_g1 = y_g1.neg(&mut cs.namespace(|| "y_g1 <- -y_g1"))?;
+    let res = commitment.add_unified(&mut cs.namespace(|| "res <- C + z_proof"), &z_proof)?;
+    res.add_unified(&mut cs.namespace(|| "res <- res - [y]G1"), &y_g1)
+}
+
+/// Asserts that `e(lhs, -G2) * e(proof, [τ]G2) == 1`, where either point can be the point at
+/// infinity
+///
+/// The Miller loop does not support the point at infinity, so such points are replaced by the
+/// generator of G1 and the result is compared to the pairings of the generator that were added,
+/// which are computed outside of the circuit.
+fn assert_opening_pairing_check<F, CS>(
+    cs: &mut CS,
+    lhs: &G1Point<F>,
+    proof: &G1Point<F>,
+    tau_g2: &G2Affine,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let g1 = G1Affine::generator();
+    let neg_g2 = -G2Affine::generator();
+    let g1_point = G1Point::from(&g1);
+
+    let lhs_is_identity = Boolean::from(
+        lhs.alloc_is_identity(&mut cs.namespace(|| "lhs_is_identity <- lhs.is_identity()"))?,
+    );
+    let proof_is_identity = Boolean::from(
+        proof
+            .alloc_is_identity(&mut cs.namespace(|| "proof_is_identity <- proof.is_identity()"))?,
+    );
+    let lhs = G1Point::conditionally_select(
+        &mut cs.namespace(|| "lhs <- select(lhs, g1, lhs_is_identity)"),
+        lhs,
+        &g1_point,
+        &lhs_is_identity,
+    )?;
+    let proof = G1Point::conditionally_select(
+        &mut cs.namespace(|| "proof <- select(proof, g1, proof_is_identity)"),
+        proof,
+        &g1_point,
+        &proof_is_identity,
+    )?;
+
+    let lines = [
+        EmulatedBls12381Pairing::precompute_lines(&neg_g2)?,
+        EmulatedBls12381Pairing::precompute_lines(tau_g2)?,
+    ];
+    let res = EmulatedBls12381Pairing::miller_loop_with_lines(
+        &mut cs.namespace(|| "miller_loop_with_lines(p, q)"),
+        &[],
+        &[],
+        &[lhs, proof],
+        &lines,
+    )?;
+    let res = EmulatedBls12381Pairing::final_exponentiation(
+        &mut cs.namespace(|| "final_exponentiation(res)"),
+        &res,
+        false,
+    )?;
+
+    // e(g1, -G2)^lhs_is_identity * e(g1, [τ]G2)^proof_is_identity
+    let lhs_gt = pairing(&g1, &neg_g2);
+    let proof_gt = pairing(&g1, tau_g2);
+    let expected0 = GtElement::conditionally_select(
+        &mut cs.namespace(|| "expected0 <- select(1, e(g1, tau), proof_is_identity)"),
+        &GtElement::one(),
+        &GtElement::from(&proof_gt),
+        &proof_is_identity,
+    )?;
+    let expected1 = GtElement::conditionally_select(
+        &mut cs
+            .namespace(|| "expected1 <- select(e(g1, -G2), e(g1, -G2 + tau), proof_is_identity)"),
+        &GtElement::from(&lhs_gt),
+        &GtElement::from(&(lhs_gt + proof_gt)),
+        &proof_is_identity,
+    )?;
+    let expected = GtElement::conditionally_select(
+        &mut cs.namespace(|| "expected <- select(expected0, expected1, lhs_is_identity)"),
+        &expected0,
+        &expected1,
+        &lhs_is_identity,
+    )?;
+    GtElement::assert_is_equal(&mut cs.namespace(|| "res == expected"), &res, &expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bls12_381::{G1Projective, Gt, Scalar};
+    use halo2curves::bn256::Fq as Fp;
+
+    use expect_test::{expect, Expect};
+    fn expect_eq(computed: usize, expected: &Expect) {
+        expected.assert_eq(&computed.to_string());
+    }
+
+    /// `[τ]G2` of the trusted setup of Ethereum for EIP-4844
+    const TAU_G2: &str = "b5bfd7dd8cdeb128843bc287230af38926187075cbfbefa81009a2ce615ac53d2914e5870cb452d2afaaab24f3499f72185cbfee53492714734429b7b38608e23926c911cceceac9a36851477ba4c60b087041de621000edc98edada20c1def2";
+
+    struct TestVector {
+        commitment: G1Affine,
+        z: Scalar,
+        y: Scalar,
+        proof: G1Affine,
+        output: bool,
+    }
+
+    /// Parses a `verify_kzg_proof` test vector in the format of the consensus spec tests. The
+    /// vectors were generated with c-kzg-4844 and the trusted setup of Ethereum.
+    fn parse_vector(data: &str) -> TestVector {
+        let field = |key: &str| {
+            let line = data
+                .lines()
+                .find(|l| l.trim_start().starts_with(&format!("{key}:")))
+                .unwrap();
+            line.split(':').nth(1).unwrap().trim().trim_matches('\'')
+        };
+        let bytes = |key: &str| hex::decode(field(key).trim_start_matches("0x")).unwrap();
+        let g1 = |key: &str| {
+            let b: [u8; 48] = bytes(key).try_into().unwrap();
+            G1Affine::from_compressed(&b).unwrap()
+        };
+        let scalar = |key: &str| {
+            let mut b: [u8; 32] = bytes(key).try_into().unwrap();
+            b.reverse();
+            Scalar::from_bytes(&b).unwrap()
+        };
+        TestVector {
+            commitment: g1("commitment"),
+            z: scalar("z"),
+            y: scalar("y"),
+            proof: g1("proof"),
+            output: field("output") == "true",
+        }
+    }
+
+    macro_rules! test_vector {
+        ($name:literal) => {
+            parse_vector(include_str!(concat!(
+                "../test_vectors/kzg/verify_kzg_proof/",
+                $name,
+                "/data.yaml"
+            )))
+        };
+    }
+
+    fn tau_g2() -> G2Affine {
+        let b: [u8; 96] = hex::decode(TAU_G2).unwrap().try_into().unwrap();
+        G2Affine::from_compressed(&b).unwrap()
+    }
+
+    fn alloc_scalar(cs: &mut TestConstraintSystem<Fp>, name: &str, x: &Scalar) -> Vec<Boolean> {
+        alloc_scalar_bits(cs, name, x, 255)
+    }
+
+    fn alloc_scalar_bits(
+        cs: &mut TestConstraintSystem<Fp>,
+        name: &str,
+        x: &Scalar,
+        num_bits: usize,
+    ) -> Vec<Boolean> {
+        let bytes = x.to_bytes();
+        (0..num_bits)
+            .map(|j| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc {name} bit {j}")),
+                        Some((bytes[j / 8] >> (j % 8)) & 1 == 1),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn alloc_g1(cs: &mut TestConstraintSystem<Fp>, name: &str, p: &G1Affine) -> G1Point<Fp> {
+        G1Point::alloc_element(&mut cs.namespace(|| format!("alloc {name}")), &Some(*p)).unwrap()
+    }
+
+    fn verify_helper(v: &TestVector) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let commitment = alloc_g1(&mut cs, "commitment", &v.commitment);
+        let z = alloc_scalar(&mut cs, "z", &v.z);
+        let y = alloc_scalar(&mut cs, "y", &v.y);
+        let proof = alloc_g1(&mut cs, "proof", &v.proof);
+        verify_kzg_proof(
+            &mut cs.namespace(|| "verify_kzg_proof"),
+            &commitment,
+            &z,
+            &y,
+            &proof,
+            &tau_g2(),
+        )
+        .unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_vectors_natively() {
+        // sanity check of the parsing and of the verification equation
+        let vectors = [
+            test_vector!("verify_kzg_proof_case_correct_proof_0"),
+            test_vector!("verify_kzg_proof_case_correct_proof_1"),
+            test_vector!("verify_kzg_proof_case_correct_proof_random"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_0"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_1"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_random"),
+            test_vector!("verify_kzg_proof_case_incorrect_proof"),
+            test_vector!("verify_kzg_proof_case_incorrect_y"),
+        ];
+        for v in vectors {
+            let lhs = G1Affine::from(
+                G1Projective::from(v.commitment) - G1Affine::generator() * v.y + v.proof * v.z,
+            );
+            let res = pairing(&lhs, &-G2Affine::generator()) + pairing(&v.proof, &tau_g2());
+            assert_eq!(res == Gt::identity(), v.output);
+        }
+    }
+
+    fn opening_lhs_helper(
+        commitment: &G1Affine,
+        z: u64,
+        y: u64,
+        proof: &G1Affine,
+    ) -> TestConstraintSystem<Fp> {
+        let (z, y) = (Scalar::from(z), Scalar::from(y));
+        let expected =
+            G1Affine::from(G1Projective::from(commitment) - G1Affine::generator() * y + proof * z);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let commitment = alloc_g1(&mut cs, "commitment", commitment);
+        // short scalars keep the test within a reasonable amount of ram
+        let z = alloc_scalar_bits(&mut cs, "z", &z, 64);
+        let y = alloc_scalar_bits(&mut cs, "y", &y, 64);
+        let proof = alloc_g1(&mut cs, "proof", proof);
+        let lhs = opening_lhs(
+            &mut cs.namespace(|| "opening_lhs"),
+            &commitment,
+            &z,
+            &y,
+            &proof,
+        )
+        .unwrap();
+        assert_eq!(G1Affine::try_from(&lhs).unwrap(), expected);
+        cs
+    }
+
+    #[test]
+    fn test_opening_lhs() {
+        let v = test_vector!("verify_kzg_proof_case_correct_proof_random");
+        let cs = opening_lhs_helper(&v.commitment, 0xdeadbeef, 0x12345678, &v.proof);
+        assert!(cs.is_satisfied());
+        expect_eq(cs.num_constraints(), &expect!["773867"]);
+    }
+
+    #[test]
+    fn test_opening_lhs_point_at_infinity() {
+        let identity = G1Affine::identity();
+        let cs = opening_lhs_helper(&identity, 0xdeadbeef, 0, &identity);
+        assert!(cs.is_satisfied());
+        let cs = opening_lhs_helper(&identity, 0, 0x12345678, &identity);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_point_at_infinity_is_valid() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let p = alloc_g1(&mut cs, "p", &G1Affine::identity());
+        assert_is_valid_point(&mut cs.namespace(|| "check p"), &p).unwrap();
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_point_outside_of_subgroup_is_invalid() {
+        use crate::curves::params::{Bls12381G1Params, EmulatedCurveParams};
+        use crate::fields::fp::FpElement;
+        use bls12_381::fp::Fp as BlsFp;
+        let b: FpElement<Fp> = Bls12381G1Params::b();
+        let b = BlsFp::try_from(&b).unwrap();
+        let mut rng = rand::thread_rng();
+        let p = loop {
+            let x = BlsFp::random(&mut rng);
+            let y = ((x.square() * x) + b).sqrt();
+            if y.is_some().into() {
+                let p = G1Affine {
+                    x,
+                    y: y.unwrap(),
+                    infinity: 0.into(),
+                };
+                if !bool::from(p.is_torsion_free()) {
+                    break p;
+                }
+            }
+        };
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let p = alloc_g1(&mut cs, "p", &p);
+        assert_is_valid_point(&mut cs.namespace(|| "check p"), &p).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    // NOTE: this test currently takes more than 40GB of ram to run
+    #[test]
+    fn test_verify_kzg_proof_correct() {
+        for v in [
+            test_vector!("verify_kzg_proof_case_correct_proof_0"),
+            test_vector!("verify_kzg_proof_case_correct_proof_1"),
+            test_vector!("verify_kzg_proof_case_correct_proof_random"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_0"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_1"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_random"),
+        ] {
+            assert!(v.output);
+            let cs = verify_helper(&v);
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    // NOTE: this test currently takes more than 40GB of ram to run
+    #[test]
+    fn test_verify_kzg_proof_incorrect() {
+        for v in [
+            test_vector!("verify_kzg_proof_case_incorrect_proof"),
+            test_vector!("verify_kzg_proof_case_incorrect_y"),
+        ] {
+            assert!(!v.output);
+            let cs = verify_helper(&v);
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    // NOTE: this test currently takes more than 40GB of ram to run
+    #[test]
+    fn test_verify_kzg_proof_batch() {
+        let vectors = [
+            test_vector!("verify_kzg_proof_case_correct_proof_random"),
+            test_vector!("verify_kzg_proof_case_correct_proof_1"),
+            test_vector!("verify_kzg_proof_case_correct_proof_point_at_infinity_random"),
+        ];
+        let mut rng = rand::thread_rng();
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let mut commitments = vec![];
+        let mut zs = vec![];
+        let mut ys = vec![];
+        let mut proofs = vec![];
+        let mut coefficients = vec![];
+        for (i, v) in vectors.iter().enumerate() {
+            commitments.push(alloc_g1(
+                &mut cs,
+                &format!("commitment[{i}]"),
+                &v.commitment,
+            ));
+            zs.push(alloc_scalar(&mut cs, &format!("z[{i}]"), &v.z));
+            ys.push(alloc_scalar(&mut cs, &format!("y[{i}]"), &v.y));
+            proofs.push(alloc_g1(&mut cs, &format!("proof[{i}]"), &v.proof));
+            let r = <Scalar as ff::Field>::random(&mut rng);
+            coefficients.push(alloc_scalar(&mut cs, &format!("r[{i}]"), &r));
+        }
+        verify_kzg_proof_batch(
+            &mut cs.namespace(|| "verify_kzg_proof_batch"),
+            &commitments,
+            &zs,
+            &ys,
+            &proofs,
+            &coefficients,
+            &tau_g2(),
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+}
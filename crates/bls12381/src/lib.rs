@@ -1,3 +1,11 @@
 pub mod fields;
 
 pub mod curves;
+
+pub mod hash_to_field;
+
+pub mod signature;
+
+pub mod groth16;
+
+pub mod kzg;
@@ -0,0 +1,517 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bls12_381::{G1Affine, G2Affine};
+use ff::PrimeFieldBits;
+
+use crate::curves::g1::G1Point;
+use crate::curves::g2::G2Point;
+use crate::curves::pairing::{EmulatedBls12381Pairing, EmulatedPairing};
+
+/// Domain separation tag of the `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite
+/// (minimal-pubkey-size, proof of possession), as used by the Ethereum consensus layer
+pub const DST_MIN_PK_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag of the `BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_` ciphersuite
+/// (minimal-signature-size, basic scheme)
+pub const DST_MIN_SIG_NUL: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Verifies a BLS signature in the minimal-pubkey-size variant, i.e. with the public key in G1
+/// and the signature in G2, as specified in [section 2.7 of the BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.7)
+///
+/// Both points are checked to be on their curve and in their prime order subgroup, which also
+/// rules out the point at infinity, and `e(pk, H(msg)) == e(g1, sig)` is enforced with a single
+/// pairing check. The message is hashed to G2 with `hash_to_g2` and the domain separation tag
+/// `dst`, so its bits are given in the order expected by the SHA-256 gadget.
+pub fn verify_bls_signature<F, CS>(
+    cs: &mut CS,
+    pk: &G1Point<F>,
+    msg: &[Boolean],
+    sig: &G2Point<F>,
+    dst: &[u8],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    pk.assert_is_on_curve(&mut cs.namespace(|| "pk.assert_is_on_curve()"))?;
+    pk.assert_subgroup_check(&mut cs.namespace(|| "pk.assert_subgroup_check()"))?;
+    sig.assert_is_on_curve(&mut cs.namespace(|| "sig.assert_is_on_curve()"))?;
+    sig.assert_subgroup_check(&mut cs.namespace(|| "sig.assert_subgroup_check()"))?;
+
+    let h = G2Point::hash_to_g2(&mut cs.namespace(|| "h <- hash_to_g2(msg)"), msg, dst)?;
+    let neg_g1 = G1Point::from(&-G1Affine::generator());
+    EmulatedBls12381Pairing::assert_pairing_check(
+        &mut cs.namespace(|| "e(-g1, sig) * e(pk, h) == 1"),
+        &[neg_g1, pk.clone()],
+        &[sig.clone(), h],
+    )
+}
+
+/// Verifies a BLS signature in the minimal-signature-size variant, i.e. with the public key in
+/// G2 and the signature in G1, as specified in [section 2.7 of the BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.7)
+///
+/// Same as `verify_bls_signature` with the roles of G1 and G2 swapped: the message is hashed to
+/// G1 with `hash_to_g1` and `e(H(msg), pk) == e(sig, g2)` is enforced.
+pub fn verify_bls_signature_min_sig<F, CS>(
+    cs: &mut CS,
+    pk: &G2Point<F>,
+    msg: &[Boolean],
+    sig: &G1Point<F>,
+    dst: &[u8],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    pk.assert_is_on_curve(&mut cs.namespace(|| "pk.assert_is_on_curve()"))?;
+    pk.assert_subgroup_check(&mut cs.namespace(|| "pk.assert_subgroup_check()"))?;
+    sig.assert_is_on_curve(&mut cs.namespace(|| "sig.assert_is_on_curve()"))?;
+    sig.assert_subgroup_check(&mut cs.namespace(|| "sig.assert_subgroup_check()"))?;
+
+    let h = G1Point::hash_to_g1(&mut cs.namespace(|| "h <- hash_to_g1(msg)"), msg, dst)?;
+    let neg_g2 = G2Point::from(&-G2Affine::generator());
+    EmulatedBls12381Pairing::assert_pairing_check(
+        &mut cs.namespace(|| "e(sig, -g2) * e(h, pk) == 1"),
+        &[sig.clone(), h],
+        &[neg_g2, pk.clone()],
+    )
+}
+
+/// Verifies an aggregate signature of `msg` by all the signers in `pks`, as specified for
+/// `FastAggregateVerify` in [section 3.3.4 of the BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3.4)
+/// (minimal-pubkey-size variant)
+///
+/// If `participation` is given, only the public keys whose bit is set are aggregated, as for
+/// the sync committee of the Ethereum consensus layer. The public keys are only summed, so they
+/// are expected to have been validated (and their proof of possession checked) outside of the
+/// circuit. An aggregate public key equal to the identity, e.g. when nobody participates, is
+/// rejected: the circuit is then unsatisfiable.
+pub fn fast_aggregate_verify<F, CS>(
+    cs: &mut CS,
+    pks: &[G1Point<F>],
+    participation: Option<&[Boolean]>,
+    msg: &[Boolean],
+    sig: &G2Point<F>,
+    dst: &[u8],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if pks.is_empty() {
+        eprintln!("fast_aggregate_verify needs at least one public key");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    if matches!(participation, Some(bits) if bits.len() != pks.len()) {
+        eprintln!("Participation bitfield and public keys have different lengths");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let mut apk: Option<G1Point<F>> = None;
+    for (i, pk) in pks.iter().enumerate() {
+        let pk = match participation {
+            Some(bits) => G1Point::conditionally_select(
+                &mut cs.namespace(|| format!("pk[{i}] <- select(0, pk[{i}], bits[{i}])")),
+                &G1Point::identity(),
+                pk,
+                &bits[i],
+            )?,
+            None => pk.clone(),
+        };
+        apk = match apk {
+            None => Some(pk),
+            Some(apk) => {
+                Some(apk.add_unified(&mut cs.namespace(|| format!("apk <- apk + pk[{i}]")), &pk)?)
+            }
+        };
+    }
+    let apk = apk.unwrap();
+    let apk_is_identity = Boolean::from(
+        apk.alloc_is_identity(&mut cs.namespace(|| "apk_is_identity <- apk.is_identity()"))?,
+    );
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "apk is not the identity"),
+        &apk_is_identity,
+        &Boolean::constant(false),
+    )?;
+    // the Miller loop cannot be synthesized for (0, 0), so it is run on the generator instead
+    let apk = G1Point::conditionally_select(
+        &mut cs.namespace(|| "apk <- select(apk, g1, apk_is_identity)"),
+        &apk,
+        &G1Point::from(&G1Affine::generator()),
+        &apk_is_identity,
+    )?;
+
+    sig.assert_is_on_curve(&mut cs.namespace(|| "sig.assert_is_on_curve()"))?;
+    sig.assert_subgroup_check(&mut cs.namespace(|| "sig.assert_subgroup_check()"))?;
+
+    let h = G2Point::hash_to_g2(&mut cs.namespace(|| "h <- hash_to_g2(msg)"), msg, dst)?;
+    let neg_g1 = G1Point::from(&-G1Affine::generator());
+    EmulatedBls12381Pairing::assert_pairing_check(
+        &mut cs.namespace(|| "e(-g1, sig) * e(apk, h) == 1"),
+        &[neg_g1, apk],
+        &[sig.clone(), h],
+    )
+}
+
+/// Verifies an aggregate signature of the messages `msgs[i]` by the signers `pks[i]`, as specified
+/// for `AggregateVerify` in [section 2.9 of the BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.9)
+/// (minimal-pubkey-size variant)
+///
+/// `e(g1, sig) == e(pk_1, H(msg_1)) * ... * e(pk_n, H(msg_n))` is enforced with a single
+/// multi-Miller loop of n + 1 pairs and one final exponentiation. As with `fast_aggregate_verify`,
+/// the public keys are expected to have been validated outside of the circuit, and the basic
+/// scheme additionally requires the messages to be distinct, which is not checked here.
+pub fn aggregate_verify<F, CS>(
+    cs: &mut CS,
+    pks: &[G1Point<F>],
+    msgs: &[&[Boolean]],
+    sig: &G2Point<F>,
+    dst: &[u8],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if pks.is_empty() || pks.len() != msgs.len() {
+        eprintln!("aggregate_verify needs the same non-zero number of public keys and messages");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    sig.assert_is_on_curve(&mut cs.namespace(|| "sig.assert_is_on_curve()"))?;
+    sig.assert_subgroup_check(&mut cs.namespace(|| "sig.assert_subgroup_check()"))?;
+
+    let mut g1 = vec![G1Point::from(&-G1Affine::generator())];
+    let mut g2 = vec![sig.clone()];
+    for (i, (pk, msg)) in pks.iter().zip(msgs).enumerate() {
+        let h = G2Point::hash_to_g2(
+            &mut cs.namespace(|| format!("h[{i}] <- hash_to_g2(msg[{i}])")),
+            msg,
+            dst,
+        )?;
+        g1.push(pk.clone());
+        g2.push(h);
+    }
+    EmulatedBls12381Pairing::assert_pairing_check(
+        &mut cs.namespace(|| "e(-g1, sig) * prod(e(pk[i], h[i])) == 1"),
+        &g1,
+        &g2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use halo2curves::bn256::Fq as Fp;
+
+    // Test vectors from the `bls/verify` tests of the Ethereum consensus specs
+    // (public key, message, signature) with the points in compressed form
+    const ETH_VERIFY_VECTORS: [(&str, &str, &str); 3] = [
+        (
+            "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+            "abababababababababababababababababababababababababababababababab",
+            "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+        ),
+        (
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+            "5656565656565656565656565656565656565656565656565656565656565656",
+            "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+        ),
+        (
+            "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+        ),
+    ];
+
+    // Signatures with the `BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_` ciphersuite computed
+    // with the bls12_381 crate, all under the public key MIN_SIG_PK
+    // (message, signature) with the points in compressed form
+    const MIN_SIG_PK: &str = "883cc71dc495c6adfb41a3c8986ea09115a4dc99627b456817fc4469ca995ab6491a790f470e38965a2f940c450cd7d40ea5a6ef53d4078ff946e46825a40aad627bd9f131af1a40b306d0600ed5057796d7c1d07bf1c371c99f47558d9b1492";
+    const MIN_SIG_VECTORS: [(&str, &str); 2] = [
+        (
+            "",
+            "90ddd8907d5f93a0af9d18fb5791357805af458798289f00a61905c540620e6f70c50d1afd46557610e827abc24d193e",
+        ),
+        (
+            "abababababababababababababababababababababababababababababababab",
+            "b32e569265a4537bafa287002664a1ff0457a52c2d44b51e5c491d02b49499059af52dd49052c1fc0a1fbb976bf142e4",
+        ),
+    ];
+
+    fn g1_from_hex(s: &str) -> G1Affine {
+        let bytes: [u8; 48] = hex::decode(s).unwrap().try_into().unwrap();
+        G1Affine::from_compressed(&bytes).unwrap()
+    }
+
+    fn g2_from_hex(s: &str) -> G2Affine {
+        let bytes: [u8; 96] = hex::decode(s).unwrap().try_into().unwrap();
+        G2Affine::from_compressed(&bytes).unwrap()
+    }
+
+    fn alloc_msg_bits<CS: ConstraintSystem<Fp>>(cs: &mut CS, msg: &[u8]) -> Vec<Boolean> {
+        msg.iter()
+            .enumerate()
+            .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+            .map(|(i, j, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc bit {j} of msg byte {i}")),
+                        Some(b),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn verify_bls_signature_helper(
+        pk: &G1Affine,
+        msg: &[u8],
+        sig: &G2Affine,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msg_bits = alloc_msg_bits(&mut cs, msg);
+        let pk_alloc =
+            G1Point::alloc_element(&mut cs.namespace(|| "alloc pk"), &Some(*pk)).unwrap();
+        let sig_alloc =
+            G2Point::alloc_element(&mut cs.namespace(|| "alloc sig"), &Some(*sig)).unwrap();
+        verify_bls_signature(
+            &mut cs.namespace(|| "verify_bls_signature(pk, msg, sig)"),
+            &pk_alloc,
+            &msg_bits,
+            &sig_alloc,
+            DST_MIN_PK_POP,
+        )
+        .unwrap();
+        cs
+    }
+
+    // NOTE: this test currently takes more than 50GB of ram to run
+    #[test]
+    fn test_verify_bls_signature_eth_vectors() {
+        for (pk, msg, sig) in ETH_VERIFY_VECTORS.iter() {
+            let cs = verify_bls_signature_helper(
+                &g1_from_hex(pk),
+                &hex::decode(msg).unwrap(),
+                &g2_from_hex(sig),
+            );
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    // NOTE: this test currently takes more than 50GB of ram to run
+    #[test]
+    fn test_verify_bls_signature_wrong_pubkey() {
+        let (_, msg, sig) = ETH_VERIFY_VECTORS[0];
+        let (pk, _, _) = ETH_VERIFY_VECTORS[1];
+        let cs = verify_bls_signature_helper(
+            &g1_from_hex(pk),
+            &hex::decode(msg).unwrap(),
+            &g2_from_hex(sig),
+        );
+        assert!(!cs.is_satisfied());
+    }
+
+    // NOTE: this test currently takes more than 50GB of ram to run
+    #[test]
+    fn test_verify_bls_signature_min_sig() {
+        let pk = g2_from_hex(MIN_SIG_PK);
+        for (msg, sig) in MIN_SIG_VECTORS.iter() {
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let msg_bits = alloc_msg_bits(&mut cs, &hex::decode(msg).unwrap());
+            let pk_alloc =
+                G2Point::alloc_element(&mut cs.namespace(|| "alloc pk"), &Some(pk)).unwrap();
+            let sig_alloc =
+                G1Point::alloc_element(&mut cs.namespace(|| "alloc sig"), &Some(g1_from_hex(sig)))
+                    .unwrap();
+            verify_bls_signature_min_sig(
+                &mut cs.namespace(|| "verify_bls_signature_min_sig(pk, msg, sig)"),
+                &pk_alloc,
+                &msg_bits,
+                &sig_alloc,
+                DST_MIN_SIG_NUL,
+            )
+            .unwrap();
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+    // Aggregate signatures computed with the bls12_381 crate from the secret keys of the
+    // `bls/fast_aggregate_verify` and `bls/aggregate_verify` tests of the Ethereum consensus
+    // specs, whose public keys are those of ETH_VERIFY_VECTORS in order 1, 2, 0
+    const FAST_AGGREGATE_MSG: &str =
+        "abababababababababababababababababababababababababababababababab";
+    const FAST_AGGREGATE_SIG: &str = "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930";
+    // Signed by the first and the last public key only
+    const FAST_AGGREGATE_PARTIAL_SIG: &str = "8e5b04bfbf91fbf55bc19d0f049dba8102b1bf397ef3bee7d3d7cf10989df6b3e6e19e7ce2f239e3de60e4050e8cdac5174fdc3933259a683bd86d559d3772aa9a369de4261a343fd6e8b7effdc5b83610523c79a643e12c543fe0fdc0b0f4a6";
+    const AGGREGATE_MSGS: [&str; 3] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "5656565656565656565656565656565656565656565656565656565656565656",
+        "abababababababababababababababababababababababababababababababab",
+    ];
+    const AGGREGATE_SIG: &str = "9104e74b9dfd3ad502f25d6a5ef57db0ed7d9a0e00f3500586d8ce44231212542fcfaf87840539b398bf07626705cf1105d246ca1062c6c2e1a53029a0f790ed5e3cb1f52f8234dc5144c45fc847c0cd37a92d68e7c5ba7c648a8a339f171244";
+
+    fn alloc_aggregate_pks<CS: ConstraintSystem<Fp>>(cs: &mut CS) -> Vec<G1Point<Fp>> {
+        [1, 2, 0]
+            .iter()
+            .map(|&i| {
+                let pk = g1_from_hex(ETH_VERIFY_VECTORS[i].0);
+                G1Point::alloc_element(&mut cs.namespace(|| format!("alloc pk {i}")), &Some(pk))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn fast_aggregate_verify_helper(
+        participation: Option<&[bool]>,
+        sig: &str,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msg_bits = alloc_msg_bits(&mut cs, &hex::decode(FAST_AGGREGATE_MSG).unwrap());
+        let pks = alloc_aggregate_pks(&mut cs);
+        let bits = participation.map(|bits| {
+            bits.iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    Boolean::from(
+                        AllocatedBit::alloc(
+                            cs.namespace(|| format!("alloc participation bit {i}")),
+                            Some(*b),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        let sig_alloc =
+            G2Point::alloc_element(&mut cs.namespace(|| "alloc sig"), &Some(g2_from_hex(sig)))
+                .unwrap();
+        fast_aggregate_verify(
+            &mut cs.namespace(|| "fast_aggregate_verify(pks, msg, sig)"),
+            &pks,
+            bits.as_deref(),
+            &msg_bits,
+            &sig_alloc,
+            DST_MIN_PK_POP,
+        )
+        .unwrap();
+        cs
+    }
+
+    // NOTE: this test currently takes more than 50GB of ram to run
+    #[test]
+    fn test_fast_aggregate_verify() {
+        let cs = fast_aggregate_verify_helper(None, FAST_AGGREGATE_SIG);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    // NOTE: this test currently takes more than 50GB of ram to run
+    #[test]
+    fn test_fast_aggregate_verify_participation() {
+        let cs =
+            fast_aggregate_verify_helper(Some(&[true, false, true]), FAST_AGGREGATE_PARTIAL_SIG);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+
+        let cs =
+            fast_aggregate_verify_helper(Some(&[true, true, true]), FAST_AGGREGATE_PARTIAL_SIG);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_no_participation() {
+        let cs =
+            fast_aggregate_verify_helper(Some(&[false, false, false]), FAST_AGGREGATE_PARTIAL_SIG);
+        assert!(!cs.is_satisfied());
+        assert!(cs
+            .which_is_unsatisfied()
+            .unwrap()
+            .contains("apk is not the identity"));
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_invalid_inputs() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let pks = alloc_aggregate_pks(&mut cs);
+        let sig = G2Point::alloc_element(
+            &mut cs.namespace(|| "alloc sig"),
+            &Some(g2_from_hex(FAST_AGGREGATE_SIG)),
+        )
+        .unwrap();
+        let res = fast_aggregate_verify(
+            &mut cs.namespace(|| "no pks"),
+            &[],
+            None,
+            &[],
+            &sig,
+            DST_MIN_PK_POP,
+        );
+        assert!(res.is_err());
+        let bits = vec![Boolean::constant(true); 2];
+        let res = fast_aggregate_verify(
+            &mut cs.namespace(|| "short bitfield"),
+            &pks,
+            Some(&bits),
+            &[],
+            &sig,
+            DST_MIN_PK_POP,
+        );
+        assert!(res.is_err());
+        let res = aggregate_verify(
+            &mut cs.namespace(|| "missing msg"),
+            &pks,
+            &[&[], &[]],
+            &sig,
+            DST_MIN_PK_POP,
+        );
+        assert!(res.is_err());
+    }
+
+    // NOTE: this test currently takes more than 100GB of ram to run
+    #[test]
+    fn test_aggregate_verify() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msgs_bits = AGGREGATE_MSGS
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| {
+                alloc_msg_bits(
+                    &mut cs.namespace(|| format!("alloc msg {i}")),
+                    &hex::decode(msg).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let msgs = msgs_bits.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let pks = alloc_aggregate_pks(&mut cs);
+        let sig = G2Point::alloc_element(
+            &mut cs.namespace(|| "alloc sig"),
+            &Some(g2_from_hex(AGGREGATE_SIG)),
+        )
+        .unwrap();
+        aggregate_verify(
+            &mut cs.namespace(|| "aggregate_verify(pks, msgs, sig)"),
+            &pks,
+            &msgs,
+            &sig,
+            DST_MIN_PK_POP,
+        )
+        .unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+}
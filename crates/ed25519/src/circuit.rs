@@ -1,10 +1,12 @@
-use bellpepper_core::boolean::Boolean;
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
 use bellpepper_core::{ConstraintSystem, SynthesisError};
 use bellpepper_emulated::field_element::{
     EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
 };
+use bellpepper_emulated::util::enforce_bits_le_at_most_constant;
 use ff::PrimeFieldBits;
 use num_bigint::BigInt;
+use num_traits::One;
 
 use crate::{
     curve::{AffinePoint, Ed25519Curve},
@@ -12,6 +14,8 @@ use crate::{
 };
 
 const DEFAULT_SCALAR_MULT_WINDOW_SIZE: i32 = 4;
+const DEFAULT_FIXED_BASE_WINDOW_SIZE: i32 = 7;
+const DEFAULT_DOUBLE_SCALAR_MULT_WINDOW_SIZE: i32 = 2;
 
 struct Ed25519FpParams;
 
@@ -107,6 +111,124 @@ impl<F: PrimeFieldBits> AllocatedAffinePoint<F> {
         Ok(identity)
     }
 
+    /// Checks that the point satisfies the curve equation -x^2 + y^2 = 1 + d*x^2*y^2
+    pub fn assert_is_on_curve<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let one = Ed25519Fp::<F>::from(&Fe25519::one());
+        let d = Ed25519Fp::<F>::from(&Ed25519Curve::d());
+
+        let x2 = self.x.mul(&mut cs.namespace(|| "x*x"), &self.x)?;
+        let y2 = self.y.mul(&mut cs.namespace(|| "y*y"), &self.y)?;
+        let x2y2 = x2.mul(&mut cs.namespace(|| "x*x*y*y"), &y2)?;
+        let dx2y2 = d.mul(&mut cs.namespace(|| "d*x*x*y*y"), &x2y2)?;
+
+        let lhs = y2.sub(&mut cs.namespace(|| "-x*x + y*y"), &x2)?;
+        let rhs = one.add(&mut cs.namespace(|| "1 + d*x*x*y*y"), &dx2y2)?;
+        Ed25519Fp::<F>::assert_is_equal(
+            &mut cs.namespace(|| "-x*x + y*y == 1 + d*x*x*y*y"),
+            &lhs,
+            &rhs,
+        )
+    }
+
+    /// Returns the 256 bits of the RFC 8032 encoding of the point in little-endian order,
+    /// i.e. the 255 bits of the y-coordinate followed by the least significant bit of the
+    /// x-coordinate.
+    pub fn compressed_bits_le<CS>(&self, cs: &mut CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut bits = self.y.to_bits_le(&mut cs.namespace(|| "y to bits"))?;
+        let x_bits = self.x.to_bits_le(&mut cs.namespace(|| "x to bits"))?;
+        bits.push(x_bits[0].clone());
+        Ok(bits)
+    }
+
+    /// Allocates a point from the 256 little-endian bits of its RFC 8032 encoding, i.e. the
+    /// 255 bits of the y-coordinate followed by the least significant bit of the x-coordinate.
+    ///
+    /// The x-coordinate is allocated as the little-endian bits of a witness less than p and is
+    /// constrained by the curve equation. The parity of x must match the sign bit and
+    /// non-canonical encodings with y >= p are rejected. Invalid encodings do not cause a
+    /// synthesis error, they leave the constraints unsatisfied.
+    pub fn alloc_from_compressed_bits_le<CS>(
+        cs: &mut CS,
+        bits: &[Boolean],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let bytes = bits
+            .iter()
+            .enumerate()
+            .try_fold([0u8; 32], |mut bytes, (i, b)| {
+                let bit = b.get_value()?;
+                bytes[i / 8] |= (bit as u8) << (i % 8);
+                Some(bytes)
+            });
+        let value = bytes.map(|bytes| Ed25519Curve::decompress(&bytes));
+        Self::alloc_from_compressed_bits_le_with_hint(cs, bits, value)
+    }
+
+    /// Same as alloc_from_compressed_bits_le, where the witness is the decompressed point
+    /// `value`. If the encoding is invalid, `value` is `Some(None)` and the point (0, y) is
+    /// allocated, which does not satisfy the constraints.
+    fn alloc_from_compressed_bits_le_with_hint<CS>(
+        cs: &mut CS,
+        bits: &[Boolean],
+        value: Option<Option<AffinePoint>>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if bits.len() != 256 {
+            eprintln!("Point encoding must have 256 bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let (y_bits, sign_bit) = (&bits[..255], &bits[255]);
+        enforce_bits_le_at_most_constant(
+            &mut cs.namespace(|| "check y is less than modulus"),
+            y_bits,
+            &(Fe25519::modulus() - BigInt::one()),
+        )?;
+        let y = Ed25519Fp::<F>::from_bits_le(CS::one(), y_bits);
+
+        let x_value = value
+            .as_ref()
+            .map(|v| v.as_ref().map(|p| p.x.0.clone()).unwrap_or_default());
+        let x_bits = (0..255)
+            .map(|i| {
+                Ok::<Boolean, SynthesisError>(Boolean::from(AllocatedBit::alloc(
+                    cs.namespace(|| format!("allocate x bit {i}")),
+                    x_value.as_ref().map(|x| x.bit(i)),
+                )?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        enforce_bits_le_at_most_constant(
+            &mut cs.namespace(|| "check x is less than modulus"),
+            &x_bits,
+            &(Fe25519::modulus() - BigInt::one()),
+        )?;
+        let x = Ed25519Fp::<F>::from_bits_le(CS::one(), &x_bits);
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| "parity of x equals sign bit"),
+            &x_bits[0],
+            sign_bit,
+        )?;
+
+        let point = Self {
+            x,
+            y,
+            value: value.flatten().unwrap_or_default(),
+        };
+        point.assert_is_on_curve(&mut cs.namespace(|| "check point is on the curve"))?;
+
+        Ok(point)
+    }
+
     pub fn assert_equality<CS>(cs: &mut CS, p: &Self, q: &Self) -> Result<(), SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -481,6 +603,237 @@ impl<F: PrimeFieldBits> AllocatedAffinePoint<F> {
             DEFAULT_SCALAR_MULT_WINDOW_SIZE,
         )
     }
+
+    /// Computes `[a]P + [b]Q` with the interleaved (Straus-Shamir) method
+    ///
+    /// The doublings are shared between both scalars. For every window of `window_size` bits
+    /// the point `[i]P + [j]Q` is selected from a joint lookup table, where `i` and `j` are the
+    /// window bits of `a` and `b` respectively. Scalars of different lengths are padded with zeros.
+    pub fn ed25519_double_scalar_multiplication_windowed<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+        window_size: i32,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if window_size <= 0 {
+            eprintln!("Window size must be positive");
+            return Err(SynthesisError::Unsatisfiable);
+        };
+        if a.is_empty() && b.is_empty() {
+            eprintln!("Scalar bit vectors cannot both be empty");
+            return Err(SynthesisError::Unsatisfiable);
+        };
+        if a.len() >= 254usize || b.len() >= 254usize {
+            // the largest curve25519 scalar fits in 253 bits
+            eprintln!("Scalar bit vector has more than 253 bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // No range checks on limbs required as it is checked to be equal to (0,1)
+        let identity_point =
+            Self::alloc_identity_point(&mut cs.namespace(|| "allocate identity point"))?;
+
+        // Remember to avoid field membership checks before calling this function
+        p.x.check_field_membership(
+            &mut cs.namespace(|| "check x coordinate of P is in base field"),
+        )?;
+        p.y.check_field_membership(
+            &mut cs.namespace(|| "check y coordinate of P is in base field"),
+        )?;
+        q.x.check_field_membership(
+            &mut cs.namespace(|| "check x coordinate of Q is in base field"),
+        )?;
+        q.y.check_field_membership(
+            &mut cs.namespace(|| "check y coordinate of Q is in base field"),
+        )?;
+
+        let window_size = window_size as usize;
+        let table_size = 1usize << window_size;
+
+        // The entry at index i + 2^window_size * j is [i]P + [j]Q
+        let mut lookup_table: Vec<Self> = vec![];
+        lookup_table.push(identity_point);
+        lookup_table.push(p.clone());
+        for i in 2..table_size {
+            if i % 2 == 0 {
+                lookup_table.push(Self::ed25519_point_doubling(
+                    &mut cs.namespace(|| format!("allocate {i}P")),
+                    &lookup_table[i / 2],
+                )?);
+            } else {
+                lookup_table.push(Self::ed25519_point_addition(
+                    &mut cs.namespace(|| format!("allocate {i}P")),
+                    &lookup_table[i - 1],
+                    p,
+                )?);
+            };
+        }
+        for j in 1..table_size {
+            for i in 0..table_size {
+                lookup_table.push(Self::ed25519_point_addition(
+                    &mut cs.namespace(|| format!("allocate {i}P + {j}Q")),
+                    &lookup_table[i + table_size * (j - 1)],
+                    q,
+                )?);
+            }
+        }
+
+        let num_windows = (a.len().max(b.len()) + window_size - 1) / window_size;
+        let bit_or_zero = |bits: &[Boolean], i: usize| -> Boolean {
+            bits.get(i).cloned().unwrap_or(Boolean::constant(false))
+        };
+
+        let mut output: Option<Self> = None;
+        for w in (0..num_windows).rev() {
+            let window_bits = (0..window_size)
+                .map(|k| bit_or_zero(a, w * window_size + k))
+                .chain((0..window_size).map(|k| bit_or_zero(b, w * window_size + k)))
+                .collect::<Vec<_>>();
+
+            let tmp = Self::conditionally_select(
+                &mut cs.namespace(|| format!("select multiple in window {w}")),
+                &lookup_table,
+                &window_bits,
+            )?;
+
+            output = match output {
+                None => Some(tmp),
+                Some(mut acc) => {
+                    for k in 0..window_size {
+                        acc = Self::ed25519_point_doubling(
+                            &mut cs
+                                .namespace(|| format!("doubling number {} in window {w}", k + 1)),
+                            &acc,
+                        )?;
+                    }
+                    Some(Self::ed25519_point_addition(
+                        &mut cs.namespace(|| format!("sum of output and tmp in window {w}")),
+                        &acc,
+                        &tmp,
+                    )?)
+                }
+            };
+        }
+
+        Ok(output.unwrap())
+    }
+
+    pub fn ed25519_double_scalar_multiplication<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::ed25519_double_scalar_multiplication_windowed(
+            &mut cs.namespace(|| {
+                format!(
+                    "double scalar multiplication with window {DEFAULT_DOUBLE_SCALAR_MULT_WINDOW_SIZE}"
+                )
+            }),
+            p,
+            a,
+            q,
+            b,
+            DEFAULT_DOUBLE_SCALAR_MULT_WINDOW_SIZE,
+        )
+    }
+
+    /// Returns a point with constant coordinates that is not allocated in the constraint system
+    fn constant_point(value: &AffinePoint) -> Self {
+        Self {
+            x: Ed25519Fp::<F>::from(&value.x),
+            y: Ed25519Fp::<F>::from(&value.y),
+            value: value.clone(),
+        }
+    }
+
+    /// Computes the scalar multiple of the base point `Ed25519Curve::basepoint()`
+    ///
+    /// The scalar is split into windows of `window_size` bits. For the window starting at bit
+    /// `i`, the multiples `[j * 2^i]B` for `j` in `0..2^window_size` are computed outside the
+    /// circuit and the multiple corresponding to the window bits is selected from these
+    /// constants. The selected points are then added together, so no doublings are required.
+    pub fn ed25519_fixed_base_scalar_multiplication_windowed<CS>(
+        cs: &mut CS,
+        scalar: &[Boolean],
+        window_size: i32,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if window_size <= 0 {
+            eprintln!("Window size must be positive");
+            return Err(SynthesisError::Unsatisfiable);
+        };
+        if scalar.is_empty() {
+            eprintln!("Scalar bit vector cannot be empty");
+            return Err(SynthesisError::Unsatisfiable);
+        };
+        if scalar.len() >= 254usize {
+            // the largest curve25519 scalar fits in 253 bits
+            eprintln!("Scalar bit vector has more than 253 bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // Multiple of the base point corresponding to the least significant bit of the window
+        let mut window_base = Ed25519Curve::basepoint();
+        let mut output: Option<Self> = None;
+
+        for (i, window_bits) in scalar.chunks(window_size as usize).enumerate() {
+            let mut lookup_table: Vec<Self> = vec![];
+            let mut multiple = AffinePoint::default();
+            for _ in 0..(1usize << window_bits.len()) {
+                lookup_table.push(Self::constant_point(&multiple));
+                multiple = multiple + &window_base;
+            }
+            // multiple now equals [2^(window bit count)] * window_base
+            window_base = multiple;
+
+            let tmp = Self::conditionally_select(
+                &mut cs.namespace(|| format!("select multiple in window {i}")),
+                &lookup_table,
+                window_bits,
+            )?;
+
+            output = match output {
+                None => Some(tmp),
+                Some(acc) => Some(Self::ed25519_point_addition(
+                    &mut cs.namespace(|| format!("sum of output and tmp in window {i}")),
+                    &acc,
+                    &tmp,
+                )?),
+            };
+        }
+
+        Ok(output.unwrap())
+    }
+
+    pub fn ed25519_fixed_base_scalar_multiplication<CS>(
+        cs: &mut CS,
+        scalar: &[Boolean],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::ed25519_fixed_base_scalar_multiplication_windowed(
+            &mut cs.namespace(|| {
+                format!(
+                    "fixed-base scalar multiplication with window {DEFAULT_FIXED_BASE_WINDOW_SIZE}"
+                )
+            }),
+            scalar,
+            DEFAULT_FIXED_BASE_WINDOW_SIZE,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -488,7 +841,10 @@ mod tests {
     use crate::curve::Ed25519Curve;
 
     use super::*;
+    use bellpepper::util_cs::bench_cs::BenchCS;
+    use bellpepper_core::boolean::AllocatedBit;
     use bellpepper_core::test_cs::TestConstraintSystem;
+    use ff::Field;
     use num_bigint::{BigUint, RandBigInt};
     use num_integer::Integer;
     use num_traits::Zero;
@@ -638,6 +994,135 @@ mod tests {
         assert_eq!(cs.num_inputs(), 1);
     }
 
+    fn alloc_compressed_bits(cs: &mut TestConstraintSystem<Fp>, bytes: &[u8; 32]) -> Vec<Boolean> {
+        (0..256)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc encoding bit {i}")),
+                        Some((bytes[i / 8] >> (i % 8)) & 1 == 1),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn alloc_affine_point_decompression() {
+        for _ in 0..50 {
+            let p = random_point();
+            let bytes = Ed25519Curve::compress(&p);
+
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let bits = alloc_compressed_bits(&mut cs, &bytes);
+            let p_alloc = AllocatedAffinePoint::alloc_from_compressed_bits_le(
+                &mut cs.namespace(|| "decompress p"),
+                &bits,
+            );
+            assert!(p_alloc.is_ok());
+            let p_al = p_alloc.unwrap();
+            assert_eq!(p_al.value, p);
+
+            let p_expected =
+                AllocatedAffinePoint::alloc_affine_point(&mut cs.namespace(|| "alloc point p"), &p)
+                    .unwrap();
+            AllocatedAffinePoint::assert_equality(
+                &mut cs.namespace(|| "decompressed p == p"),
+                &p_al,
+                &p_expected,
+            )
+            .unwrap();
+
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 3528);
+            assert_eq!(cs.num_inputs(), 1);
+
+            // Flipping the sign bit in the witness violates the parity check
+            let sign_bit_path = "alloc encoding bit 255/boolean";
+            let sign_bit = cs.get(sign_bit_path);
+            cs.set(sign_bit_path, Fp::ONE - sign_bit);
+            assert!(!cs.is_satisfied());
+        }
+
+        // Encoding of y = p + 1 which is non-canonical
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&(Fe25519::modulus() + 1u64).to_bytes_le().1);
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = alloc_compressed_bits(&mut cs, &bytes);
+        let p_alloc = AllocatedAffinePoint::alloc_from_compressed_bits_le(
+            &mut cs.namespace(|| "decompress non-canonical encoding"),
+            &bits,
+        );
+        assert!(p_alloc.is_ok());
+        assert!(!cs.is_satisfied());
+        assert!(cs
+            .which_is_unsatisfied()
+            .unwrap()
+            .contains("check y is less than modulus"));
+
+        // Encoding of y = 2, for which there is no x on the curve
+        let mut bytes = [0u8; 32];
+        bytes[0] = 2;
+        assert!(Ed25519Curve::decompress(&bytes).is_none());
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = alloc_compressed_bits(&mut cs, &bytes);
+        let p_alloc = AllocatedAffinePoint::alloc_from_compressed_bits_le(
+            &mut cs.namespace(|| "decompress invalid encoding"),
+            &bits,
+        );
+        assert!(p_alloc.is_ok());
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn alloc_affine_point_decompression_wrong_parity() {
+        // The prover supplies -x, whose parity does not match the sign bit, along with
+        // consistent values for all the other witnesses
+        let p = random_point();
+        let bytes = Ed25519Curve::compress(&p);
+        let p_neg = AffinePoint {
+            x: -p.x.clone(),
+            y: p.y.clone(),
+        };
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = alloc_compressed_bits(&mut cs, &bytes);
+        AllocatedAffinePoint::alloc_from_compressed_bits_le_with_hint(
+            &mut cs.namespace(|| "decompress p"),
+            &bits,
+            Some(Some(p_neg)),
+        )
+        .unwrap();
+        assert!(!cs.is_satisfied());
+        assert!(cs
+            .which_is_unsatisfied()
+            .unwrap()
+            .contains("parity of x equals sign bit"));
+    }
+
+    #[test]
+    fn alloc_affine_point_decompression_without_witness() {
+        let mut cs = BenchCS::<Fp>::new();
+        let bits = (0..256)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("alloc encoding bit {i}")), None)
+                        .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let p_alloc = AllocatedAffinePoint::alloc_from_compressed_bits_le(
+            &mut cs.namespace(|| "decompress p"),
+            &bits,
+        );
+        assert!(p_alloc.is_ok());
+        assert_eq!(cs.num_constraints(), 3212);
+    }
+
     #[test]
     fn alloc_affine_scalar_multiplication_default() {
         let b = Ed25519Curve::basepoint();
@@ -729,4 +1214,155 @@ mod tests {
         assert!(cs.is_satisfied());
         cs.num_constraints()
     }
+
+    fn fixed_base_scalar_multiplication_helper(window_size: i32) -> usize {
+        let b = Ed25519Curve::basepoint();
+        let mut rng = rand::thread_rng();
+
+        let mut scalar = rng.gen_biguint(256u64);
+        scalar >>= 3; // scalar now has 253 significant bits
+        let p = Ed25519Curve::scalar_multiplication(&b, &scalar);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+
+        let mut scalar_vec: Vec<Boolean> = vec![];
+        for i in 0..253 {
+            scalar_vec.push(Boolean::from(
+                AllocatedBit::alloc(
+                    cs.namespace(|| format!("alloc scalar bit {i}")),
+                    Some(scalar.is_odd()),
+                )
+                .unwrap(),
+            ));
+            scalar >>= 1;
+        }
+
+        let p_alloc = AllocatedAffinePoint::ed25519_fixed_base_scalar_multiplication_windowed(
+            &mut cs.namespace(|| "fixed-base scalar multiplication"),
+            &scalar_vec,
+            window_size,
+        );
+        assert!(p_alloc.is_ok());
+        let p_al = p_alloc.unwrap();
+
+        assert_eq!(p, p_al.value);
+
+        let p_expected =
+            AllocatedAffinePoint::alloc_affine_point(&mut cs.namespace(|| "alloc point p"), &p)
+                .unwrap();
+        AllocatedAffinePoint::assert_equality(
+            &mut cs.namespace(|| "[scalar]B == p"),
+            &p_al,
+            &p_expected,
+        )
+        .unwrap();
+
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        cs.num_constraints()
+    }
+
+    #[test]
+    fn alloc_affine_fixed_base_scalar_multiplication_window_range() {
+        assert_eq!(fixed_base_scalar_multiplication_helper(1), 996_231);
+        assert_eq!(fixed_base_scalar_multiplication_helper(2), 500_925);
+        assert_eq!(fixed_base_scalar_multiplication_helper(3), 337_503);
+        assert_eq!(fixed_base_scalar_multiplication_helper(4), 258_312);
+        assert_eq!(fixed_base_scalar_multiplication_helper(5), 213_189);
+        assert_eq!(fixed_base_scalar_multiplication_helper(6), 192_561);
+        assert_eq!(fixed_base_scalar_multiplication_helper(7), 188_175);
+        assert_eq!(fixed_base_scalar_multiplication_helper(8), 202_100);
+    }
+
+    fn double_scalar_multiplication_helper(
+        window_size: i32,
+        a_num_bits: u64,
+        b_num_bits: u64,
+    ) -> usize {
+        let p = random_point();
+        let q = random_point();
+        let mut rng = rand::thread_rng();
+
+        let a = rng.gen_biguint(a_num_bits);
+        let b = rng.gen_biguint(b_num_bits);
+        let expected = Ed25519Curve::scalar_multiplication(&p, &a)
+            + &Ed25519Curve::scalar_multiplication(&q, &b);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+
+        let a_vec = (0..a_num_bits)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc a bit {i}")),
+                        Some(a.bit(i)),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let b_vec = (0..b_num_bits)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc b bit {i}")),
+                        Some(b.bit(i)),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let p_al =
+            AllocatedAffinePoint::alloc_affine_point(&mut cs.namespace(|| "alloc point P"), &p)
+                .unwrap();
+        let q_al =
+            AllocatedAffinePoint::alloc_affine_point(&mut cs.namespace(|| "alloc point Q"), &q)
+                .unwrap();
+
+        let res = AllocatedAffinePoint::ed25519_double_scalar_multiplication_windowed(
+            &mut cs.namespace(|| "double scalar multiplication"),
+            &p_al,
+            &a_vec,
+            &q_al,
+            &b_vec,
+            window_size,
+        );
+        assert!(res.is_ok());
+        let res = res.unwrap();
+
+        assert_eq!(expected, res.value);
+
+        let res_expected = AllocatedAffinePoint::alloc_affine_point(
+            &mut cs.namespace(|| "alloc expected point"),
+            &expected,
+        )
+        .unwrap();
+        AllocatedAffinePoint::assert_equality(
+            &mut cs.namespace(|| "[a]P + [b]Q == expected"),
+            &res,
+            &res_expected,
+        )
+        .unwrap();
+
+        assert!(cs.is_satisfied());
+        cs.num_constraints()
+    }
+
+    #[test]
+    fn alloc_affine_double_scalar_multiplication_window_range() {
+        assert_eq!(double_scalar_multiplication_helper(1, 253, 253), 1_515_476);
+        assert_eq!(double_scalar_multiplication_helper(2, 253, 253), 1_075_724);
+        assert_eq!(double_scalar_multiplication_helper(3, 253, 253), 1_129_994);
+    }
+
+    #[test]
+    fn alloc_affine_double_scalar_multiplication_different_lengths() {
+        double_scalar_multiplication_helper(2, 253, 128);
+        double_scalar_multiplication_helper(2, 5, 253);
+        double_scalar_multiplication_helper(3, 1, 0);
+        double_scalar_multiplication_helper(3, 0, 7);
+    }
 }
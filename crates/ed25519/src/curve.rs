@@ -128,18 +128,55 @@ impl Ed25519Curve {
         .unwrap()
     }
 
-    pub fn recover_even_x_from_y(y: &Fe25519) -> Fe25519 {
+    /// Returns the even x-coordinate corresponding to `y` if `y` is the
+    /// y-coordinate of a curve point
+    pub fn try_recover_even_x_from_y(y: &Fe25519) -> Option<Fe25519> {
         let y_sq = &y.square();
         let x_sq = (y_sq - &Fe25519::one()) * (Self::d() * y_sq + Fe25519::one()).invert().unwrap();
 
-        let x = x_sq.sqrt();
-        assert!(x.is_some()); // y must correspond to a curve point
-        let x = x.unwrap();
+        let x = x_sq.sqrt()?;
         if x.is_even() {
-            x
+            Some(x)
         } else {
-            -x
+            Some(-x)
+        }
+    }
+
+    pub fn recover_even_x_from_y(y: &Fe25519) -> Fe25519 {
+        let x = Self::try_recover_even_x_from_y(y);
+        assert!(x.is_some()); // y must correspond to a curve point
+        x.unwrap()
+    }
+
+    /// Encodes a point as specified in [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.2)
+    pub fn compress(point: &AffinePoint) -> [u8; 32] {
+        let mut bytes = point.y.to_bytes_le();
+        if !point.x.is_even() {
+            bytes[31] |= 0x80;
+        }
+        bytes
+    }
+
+    /// Decodes a point as specified in [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.3)
+    ///
+    /// Returns `None` for non-canonical encodings with y >= p, for y-coordinates that do
+    /// not correspond to a curve point and for encodings of x = 0 with the sign bit set.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<AffinePoint> {
+        let x_is_odd = bytes[31] >> 7 == 1;
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7f;
+
+        let y = Fe25519::from_bytes_le(&y_bytes);
+        if y.0 >= Fe25519::modulus() {
+            return None;
         }
+
+        let x = Self::try_recover_even_x_from_y(&y)?;
+        if x_is_odd && x.is_zero() {
+            return None;
+        }
+        let x = if x_is_odd { -x } else { x };
+        Some(AffinePoint { x, y })
     }
 
     pub fn basepoint() -> AffinePoint {
@@ -263,6 +300,35 @@ mod tests {
         assert_eq!(p, b + b + b + b + b + b);
     }
 
+    #[test]
+    fn point_compression() {
+        for _ in 0..50 {
+            let p = random_point();
+            let bytes = Ed25519Curve::compress(&p);
+            assert_eq!(Ed25519Curve::decompress(&bytes), Some(p));
+        }
+
+        let b = Ed25519Curve::basepoint();
+        let mut expected = [0x66u8; 32];
+        expected[0] = 0x58;
+        assert_eq!(Ed25519Curve::compress(&b), expected);
+
+        // y = p + 1 is a non-canonical encoding of the identity
+        let mut bytes = (Fe25519::modulus() + 1u64).to_bytes_le().1;
+        bytes.resize(32, 0);
+        let bytes: [u8; 32] = bytes.try_into().unwrap();
+        assert!(Ed25519Curve::decompress(&bytes).is_none());
+
+        // x = 0 with the sign bit set
+        let mut bytes = Ed25519Curve::compress(&AffinePoint::default());
+        bytes[31] |= 0x80;
+        assert!(Ed25519Curve::decompress(&bytes).is_none());
+
+        // y = 2 does not correspond to a curve point
+        let bytes = Fe25519::from(2u64).to_bytes_le();
+        assert!(Ed25519Curve::decompress(&bytes).is_none());
+    }
+
     #[test]
     fn point_order() {
         let b = Ed25519Curve::basepoint();
@@ -0,0 +1,265 @@
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::util::enforce_bits_le_at_most_constant;
+use bellpepper_sha512::sha512::sha512;
+use ff::PrimeFieldBits;
+use num_bigint::{BigInt, BigUint};
+use num_traits::One;
+
+use crate::circuit::AllocatedAffinePoint;
+use crate::curve::{AffinePoint, Ed25519Curve};
+use crate::scalar::reduce_bits_mod_order;
+
+/// Number of bits in the encoding of a scalar in an Ed25519 signature
+const SCALAR_ENCODING_BITS: usize = 256;
+
+/// An Ed25519 signature (R, S) with the point R in decoded form
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: AffinePoint,
+    pub s: BigUint,
+}
+
+#[derive(Clone)]
+pub struct AllocatedSignature<F: PrimeFieldBits> {
+    r: AllocatedAffinePoint<F>,
+    /// Little-endian bits of S
+    s: Vec<Boolean>,
+}
+
+impl<F: PrimeFieldBits> AllocatedSignature<F> {
+    pub fn alloc_signature<CS>(cs: &mut CS, value: &Signature) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if value.s.bits() as usize > SCALAR_ENCODING_BITS {
+            eprintln!("Signature scalar does not fit in {SCALAR_ENCODING_BITS} bits");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let r =
+            AllocatedAffinePoint::alloc_affine_point(&mut cs.namespace(|| "allocate R"), &value.r)?;
+        let s = (0..SCALAR_ENCODING_BITS)
+            .map(|i| {
+                Ok::<Boolean, SynthesisError>(Boolean::from(AllocatedBit::alloc(
+                    cs.namespace(|| format!("allocate bit {i} of S")),
+                    Some(value.s.bit(i as u64)),
+                )?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { r, s })
+    }
+}
+
+/// Reverses the order of the bits within every byte. Converts little-endian bits of an
+/// integer into the byte-wise big-endian bits consumed and produced by the SHA-512 gadget,
+/// and vice versa.
+fn reverse_bits_in_bytes(bits: &[Boolean]) -> Vec<Boolean> {
+    bits.chunks(8)
+        .flat_map(|byte| byte.iter().rev().cloned())
+        .collect()
+}
+
+/// Verifies an Ed25519 signature as specified in [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.7)
+///
+/// The bits of the message are given in the order expected by the SHA-512 gadget, i.e. the
+/// bytes of the message with the bits of each byte in big-endian order. The scalar S is
+/// required to be less than the group order L and the cofactored verification equation
+/// [8][S]B = [8]R + [8][k]A is enforced, where k = SHA-512(R || A || M) mod L.
+pub fn verify_signature<F, CS>(
+    cs: &mut CS,
+    public_key: &AllocatedAffinePoint<F>,
+    message_bits: &[Boolean],
+    signature: &AllocatedSignature<F>,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if message_bits.len() % 8 != 0 {
+        eprintln!("Message bit length must be a multiple of 8");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    public_key.assert_is_on_curve(&mut cs.namespace(|| "check A is on the curve"))?;
+    signature
+        .r
+        .assert_is_on_curve(&mut cs.namespace(|| "check R is on the curve"))?;
+
+    let l_minus_one = BigInt::from(Ed25519Curve::order()) - BigInt::one();
+    enforce_bits_le_at_most_constant(
+        &mut cs.namespace(|| "check S < L"),
+        &signature.s,
+        &l_minus_one,
+    )?;
+
+    let r_bits = signature
+        .r
+        .compressed_bits_le(&mut cs.namespace(|| "encode R"))?;
+    let a_bits = public_key.compressed_bits_le(&mut cs.namespace(|| "encode A"))?;
+
+    let mut hash_input = reverse_bits_in_bytes(&r_bits);
+    hash_input.extend(reverse_bits_in_bytes(&a_bits));
+    hash_input.extend_from_slice(message_bits);
+
+    let digest = sha512(cs.namespace(|| "SHA-512(R || A || M)"), &hash_input)?;
+    let k = reduce_bits_mod_order(
+        &mut cs.namespace(|| "k <- SHA-512(R || A || M) mod L"),
+        &reverse_bits_in_bytes(&digest),
+    )?;
+
+    // The most significant bits of S are zero as S < L
+    let l_bits = Ed25519Curve::order().bits() as usize;
+    let mut lhs = AllocatedAffinePoint::ed25519_fixed_base_scalar_multiplication(
+        &mut cs.namespace(|| "[S]B"),
+        &signature.s[..l_bits],
+    )?;
+
+    let ka = public_key.ed25519_scalar_multiplication(&mut cs.namespace(|| "[k]A"), &k)?;
+    let mut rhs = AllocatedAffinePoint::ed25519_point_addition(
+        &mut cs.namespace(|| "R + [k]A"),
+        &signature.r,
+        &ka,
+    )?;
+
+    for i in 0..3 {
+        lhs = AllocatedAffinePoint::ed25519_point_doubling(
+            &mut cs.namespace(|| format!("doubling number {} of lhs", i + 1)),
+            &lhs,
+        )?;
+        rhs = AllocatedAffinePoint::ed25519_point_doubling(
+            &mut cs.namespace(|| format!("doubling number {} of rhs", i + 1)),
+            &rhs,
+        )?;
+    }
+
+    AllocatedAffinePoint::assert_equality(
+        &mut cs.namespace(|| "[8][S]B == [8]R + [8][k]A"),
+        &lhs,
+        &rhs,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use pasta_curves::Fp;
+
+    // Test vectors from https://www.rfc-editor.org/rfc/rfc8032#section-7.1
+    // (public key, message, signature)
+    const RFC8032_VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+        (
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+        ),
+    ];
+
+    fn parse_vector(vector: &(&str, &str, &str)) -> (AffinePoint, Vec<u8>, Signature) {
+        let public_key = hex::decode(vector.0).unwrap();
+        let public_key = Ed25519Curve::decompress(&public_key.try_into().unwrap()).unwrap();
+        let message = hex::decode(vector.1).unwrap();
+        let signature = hex::decode(vector.2).unwrap();
+        let signature = Signature {
+            r: Ed25519Curve::decompress(&signature[..32].try_into().unwrap()).unwrap(),
+            s: BigUint::from_bytes_le(&signature[32..]),
+        };
+        (public_key, message, signature)
+    }
+
+    fn verify_signature_helper(
+        public_key: &AffinePoint,
+        message: &[u8],
+        signature: &Signature,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+
+        let message_bits = message
+            .iter()
+            .enumerate()
+            .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+            .map(|(i, j, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("allocate bit {j} of message byte {i}")),
+                        Some(b),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let public_key = AllocatedAffinePoint::alloc_affine_point(
+            &mut cs.namespace(|| "allocate public key"),
+            public_key,
+        )
+        .unwrap();
+        let signature = AllocatedSignature::alloc_signature(
+            &mut cs.namespace(|| "allocate signature"),
+            signature,
+        )
+        .unwrap();
+
+        let res = verify_signature(
+            &mut cs.namespace(|| "verify signature"),
+            &public_key,
+            &message_bits,
+            &signature,
+        );
+        assert!(res.is_ok());
+        cs
+    }
+
+    #[test]
+    fn verify_signature_rfc8032_vectors() {
+        for vector in RFC8032_VECTORS.iter() {
+            let (public_key, message, signature) = parse_vector(vector);
+            let cs = verify_signature_helper(&public_key, &message, &signature);
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn verify_signature_num_constraints() {
+        let (public_key, message, signature) = parse_vector(&RFC8032_VECTORS[0]);
+        let cs = verify_signature_helper(&public_key, &message, &signature);
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_constraints(), 1_076_168);
+        assert_eq!(cs.num_inputs(), 1);
+    }
+
+    #[test]
+    fn verify_signature_wrong_message() {
+        let (public_key, mut message, signature) = parse_vector(&RFC8032_VECTORS[2]);
+        message[0] ^= 1;
+        let cs = verify_signature_helper(&public_key, &message, &signature);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn verify_signature_non_canonical_s() {
+        let (public_key, message, mut signature) = parse_vector(&RFC8032_VECTORS[1]);
+        // [8][S + L]B = [8][S]B, so only the S < L check rejects this signature
+        signature.s += Ed25519Curve::order();
+        let cs = verify_signature_helper(&public_key, &message, &signature);
+        assert!(!cs.is_satisfied());
+        assert!(cs.which_is_unsatisfied().unwrap().contains("check S < L"));
+    }
+}
@@ -1,3 +1,5 @@
 pub mod circuit;
 pub mod curve;
+pub mod eddsa;
 pub mod field;
+pub mod scalar;
@@ -0,0 +1,133 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::field_element::{
+    EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
+};
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+
+use crate::curve::Ed25519Curve;
+
+/// Parameters of the scalar field of Ed25519, i.e. integers modulo the
+/// order L of the prime-order subgroup
+pub struct Ed25519ScalarParams;
+
+impl EmulatedFieldParams for Ed25519ScalarParams {
+    fn num_limbs() -> usize {
+        5
+    }
+
+    fn bits_per_limb() -> usize {
+        51
+    }
+
+    fn modulus() -> BigInt {
+        BigInt::from(Ed25519Curve::order())
+    }
+
+    fn is_modulus_pseudo_mersenne() -> bool {
+        false
+    }
+
+    fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+        None
+    }
+}
+
+pub type Ed25519Scalar<F> = EmulatedFieldElement<F, Ed25519ScalarParams>;
+
+/// Reduces the integer represented by little-endian `bits` modulo the group order L
+///
+/// The input can have an arbitrary number of bits, e.g. the 512 bits of a SHA-512
+/// digest. The output is the canonical little-endian bit decomposition of the
+/// remainder and has 253 bits, so it can be used directly as a scalar in
+/// `AllocatedAffinePoint::ed25519_scalar_multiplication`.
+pub fn reduce_bits_mod_order<F, CS>(
+    cs: &mut CS,
+    bits: &[Boolean],
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let scalar = Ed25519Scalar::<F>::from_bits_le(CS::one(), bits);
+    scalar.to_bits_le(&mut cs.namespace(|| "reduce bits modulo L"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use num_bigint::{RandBigInt, Sign};
+    use pasta_curves::Fp;
+
+    #[test]
+    fn scalar_modulus_is_group_order() {
+        assert_eq!(
+            Ed25519ScalarParams::modulus(),
+            BigInt::from_biguint(Sign::Plus, Ed25519Curve::order())
+        );
+    }
+
+    fn reduce_bits_helper(value: &BigInt, num_bits: usize) -> usize {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let bits = (0..num_bits)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc bit {i}")),
+                        Some(value.bit(i as u64)),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let reduced = reduce_bits_mod_order(&mut cs.namespace(|| "reduce"), &bits);
+        assert!(reduced.is_ok());
+        let reduced = reduced.unwrap();
+        assert_eq!(reduced.len(), 253);
+
+        let expected = value % Ed25519ScalarParams::modulus();
+        for (i, b) in reduced.iter().enumerate() {
+            assert_eq!(b.get_value(), Some(expected.bit(i as u64)));
+        }
+
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+        cs.num_constraints()
+    }
+
+    #[test]
+    fn reduce_bits_mod_order_random() {
+        let mut rng = rand::thread_rng();
+        for num_bits in [0, 1, 100, 252, 253, 256, 512, 1024] {
+            let value = rng.gen_bigint_range(&BigInt::from(0), &(BigInt::from(1) << num_bits));
+            reduce_bits_helper(&value, num_bits);
+        }
+    }
+
+    #[test]
+    fn reduce_bits_mod_order_edge_cases() {
+        let l = Ed25519ScalarParams::modulus();
+        for value in [
+            BigInt::from(0),
+            &l - 1,
+            l.clone(),
+            &l + 1,
+            (BigInt::from(1) << 512) - 1,
+        ] {
+            reduce_bits_helper(&value, 512);
+        }
+    }
+
+    #[test]
+    fn reduce_bits_mod_order_num_constraints() {
+        let mut rng = rand::thread_rng();
+        let value = rng.gen_bigint_range(&BigInt::from(0), &(BigInt::from(1) << 512));
+        assert_eq!(reduce_bits_helper(&value, 512), 1599);
+    }
+}
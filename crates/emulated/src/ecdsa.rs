@@ -0,0 +1,112 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+use num_traits::One;
+
+use crate::field_element::{EmulatedFieldElement, EmulatedFieldParams};
+use crate::util::enforce_bits_le_at_most_constant;
+use crate::weierstrass::{EmulatedCurveParams, WeierstrassPoint};
+
+/// Returns the scalar given by the little-endian `bits`, enforcing that it is in `[1, n-1]`
+/// where `n` is the modulus of `Q`. Also returns the `Boolean` which is true if and only if the
+/// scalar is zero.
+pub fn nonzero_scalar_from_bits<F, Q, CS>(
+    cs: &mut CS,
+    bits: &[Boolean],
+) -> Result<(EmulatedFieldElement<F, Q>, Boolean), SynthesisError>
+where
+    F: PrimeFieldBits,
+    Q: EmulatedFieldParams,
+    CS: ConstraintSystem<F>,
+{
+    let n_minus_one = Q::modulus() - BigInt::one();
+    enforce_bits_le_at_most_constant(&mut cs.namespace(|| "x < n"), bits, &n_minus_one)?;
+    // the limbs of x are only reduced modulo n if x < n, so x is rebuilt from its canonical bits
+    // for the zero check to also detect x = n when the circuit is unsatisfiable
+    let x = EmulatedFieldElement::<F, Q>::from_bits_le(CS::one(), bits);
+    let x_bits = x.to_bits_le(&mut cs.namespace(|| "x to bits"))?;
+    let x = EmulatedFieldElement::from_bits_le(CS::one(), &x_bits);
+    let is_zero = Boolean::from(x.alloc_is_zero(&mut cs.namespace(|| "x =? 0"))?);
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "x != 0"),
+        &is_zero,
+        &Boolean::constant(false),
+    )?;
+    Ok((x, is_zero))
+}
+
+/// Verifies an ECDSA signature `(r, s)` as specified in
+/// [SEC 1 (Section 4.1.4)](https://www.secg.org/sec1-v2.pdf), over the curve given by `C` with
+/// base field `P` and scalar field `Q`
+///
+/// `msg_hash`, `r` and `s` are given as little-endian bits, as many as the bit length of the
+/// group order `n`. `msg_hash` is the integer whose big-endian encoding is the message digest,
+/// e.g. the SHA-256 hash of the message. The scalars `r` and `s` are required to be in `[1, n-1]`
+/// and the public key to be on the curve; high values of `s` are accepted, as in SEC 1. The
+/// verification equation `x(R) mod n == r` is enforced, where `R = [msg_hash/s]G + [r/s]pk` must
+/// not be the point at infinity.
+///
+/// `x(R)` is reduced modulo `n` by the final equality check, which requires `p < 2n` as for
+/// secp256k1 and P-256.
+pub fn ecdsa_verify<F, P, Q, C, CS>(
+    cs: &mut CS,
+    pk: &WeierstrassPoint<F, P, C>,
+    msg_hash: &[Boolean],
+    r: &[Boolean],
+    s: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    Q: EmulatedFieldParams,
+    C: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+    CS: ConstraintSystem<F>,
+{
+    let num_bits = Q::modulus().bits() as usize;
+    if [msg_hash, r, s].iter().any(|bits| bits.len() != num_bits) {
+        eprintln!("Message hash and signature scalars must have {num_bits} bits");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    // (0, 0) is not on the curve, so this also ensures that pk is not the point at infinity
+    pk.assert_is_on_curve(&mut cs.namespace(|| "check pk is on the curve"))?;
+    let (r, _) = nonzero_scalar_from_bits::<F, Q, _>(&mut cs.namespace(|| "check 0 < r < n"), r)?;
+    let (s, s_is_zero) =
+        nonzero_scalar_from_bits::<F, Q, _>(&mut cs.namespace(|| "check 0 < s < n"), s)?;
+    // the circuit is already unsatisfiable if s = 0, so replace it with 1 to be able to compute
+    // its inverse and finish the synthesis
+    let s = EmulatedFieldElement::conditionally_select(
+        &mut cs.namespace(|| "s <- select(s, 1, s_is_zero)"),
+        &s,
+        &EmulatedFieldElement::one(),
+        &s_is_zero,
+    )?;
+    let z = EmulatedFieldElement::<F, Q>::from_bits_le(CS::one(), msg_hash);
+
+    let w = s.inverse(&mut cs.namespace(|| "w <- s^-1"))?;
+    let u1 = z.mul(&mut cs.namespace(|| "u1 <- z * w"), &w)?;
+    let u2 = r.mul(&mut cs.namespace(|| "u2 <- r * w"), &w)?;
+    let u1_bits = u1.to_bits_le(&mut cs.namespace(|| "u1 to bits"))?;
+    let u2_bits = u2.to_bits_le(&mut cs.namespace(|| "u2 to bits"))?;
+
+    let g = WeierstrassPoint::alloc_generator(&mut cs.namespace(|| "alloc G"))?;
+    let res = WeierstrassPoint::double_scalar_mul(
+        &mut cs.namespace(|| "R <- [u1]G + [u2]pk"),
+        &g,
+        &u1_bits,
+        pk,
+        &u2_bits,
+    )?;
+    let is_identity = res.alloc_is_identity(&mut cs.namespace(|| "R =? O"))?;
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "R != O"),
+        &Boolean::from(is_identity),
+        &Boolean::constant(false),
+    )?;
+
+    // x(R) < p < 2n, so its canonical bits are reduced modulo n by the equality check
+    let x_bits = res.x.to_bits_le(&mut cs.namespace(|| "x(R) to bits"))?;
+    let x = EmulatedFieldElement::<F, Q>::from_bits_le(CS::one(), &x_bits);
+    EmulatedFieldElement::assert_is_equal(&mut cs.namespace(|| "x(R) mod n == r"), &x, &r)
+}
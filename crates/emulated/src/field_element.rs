@@ -6,7 +6,7 @@ use bellpepper_core::{
     boolean::{AllocatedBit, Boolean},
     num::Num,
 };
-use bellpepper_core::{ConstraintSystem, LinearCombination, SynthesisError};
+use bellpepper_core::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
 use ff::PrimeFieldBits;
 use num_bigint::{BigInt, BigUint, Sign};
 use num_traits::{One, Signed, Zero};
@@ -767,6 +767,62 @@ where
 
         Ok(out)
     }
+
+    /// Constructs an [EmulatedFieldElement] from little-endian bits. The bit vector
+    /// can be longer than the modulus, in which case the element has more limbs than
+    /// `P::num_limbs()` and is reduced by subsequent operations.
+    ///
+    /// `one` is the constant one variable of the constraint system, i.e. `CS::one()`.
+    pub fn from_bits_le(one: Variable, bits: &[Boolean]) -> Self {
+        let num_limbs =
+            ((bits.len() + P::bits_per_limb() - 1) / P::bits_per_limb()).max(P::num_limbs());
+        let mut limbs = vec![Num::<F>::zero(); num_limbs];
+
+        for (limb, limb_bits) in limbs.iter_mut().zip(bits.chunks(P::bits_per_limb())) {
+            let mut coeff = F::ONE;
+            for b in limb_bits {
+                *limb = limb.clone().add_bool_with_coeff(one, b, coeff);
+                coeff = coeff.double();
+            }
+        }
+
+        Self::new_internal_element(EmulatedLimbs::Allocated(limbs), 0)
+    }
+
+    /// Returns the canonical little-endian bit decomposition of the element, i.e.
+    /// the bits of the element reduced modulo `P::modulus()`. The output has as many
+    /// bits as the modulus.
+    pub fn to_bits_le<CS>(&self, cs: &mut CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let p = P::modulus();
+        let value: Option<BigInt> = BigInt::try_from(self).ok().map(|v| v.rem(&p));
+
+        let bits = (0..p.bits())
+            .map(|i| {
+                Ok::<Boolean, SynthesisError>(Boolean::from(AllocatedBit::alloc(
+                    cs.namespace(|| format!("allocate bit {i}")),
+                    value.as_ref().map(|v| v.bit(i)),
+                )?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        enforce_bits_le_at_most_constant(
+            &mut cs.namespace(|| "check bits are less than modulus"),
+            &bits,
+            &(&p - BigInt::one()),
+        )?;
+
+        let packed = Self::from_bits_le(CS::one(), &bits);
+        Self::assert_is_equal(
+            &mut cs.namespace(|| "check bits equal element"),
+            &packed,
+            self,
+        )?;
+
+        Ok(bits)
+    }
 }
 
 #[cfg(test)]
@@ -1574,4 +1630,71 @@ mod tests {
         assert_eq!(cs.scalar_aux().len(), 0);
         assert_eq!(cs.num_constraints(), 0);
     }
+
+    #[test]
+    fn test_bits_le() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let mut rng = rand::thread_rng();
+        let a_int = rng.gen_bigint_range(&BigInt::zero(), &Ed25519Fp::modulus());
+        let a_const = EmulatedFieldElement::<Fp, Ed25519Fp>::from(&a_int);
+        let a = a_const
+            .allocate_field_element_unchecked(&mut cs.namespace(|| "a"))
+            .unwrap();
+
+        let a_bits = a.to_bits_le(&mut cs.namespace(|| "a to bits")).unwrap();
+        assert_eq!(a_bits.len(), 255);
+        for (i, b) in a_bits.iter().enumerate() {
+            assert_eq!(b.get_value(), Some(a_int.bit(i as u64)));
+        }
+
+        // Bits of a 512-bit integer are reduced modulo the field modulus
+        let h_int = rng.gen_bigint_range(&BigInt::zero(), &(BigInt::one() << 512));
+        let h_bits = (0..512)
+            .map(|i| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("alloc h bit {i}")),
+                        Some(h_int.bit(i)),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let h = EmulatedFieldElement::<Fp, Ed25519Fp>::from_bits_le(
+            TestConstraintSystem::<Fp>::one(),
+            &h_bits,
+        );
+        assert_eq!(h.len(), 11);
+        let h_red_bits = h.to_bits_le(&mut cs.namespace(|| "h to bits")).unwrap();
+        let h_red_int = h_int.rem(Ed25519Fp::modulus());
+        for (i, b) in h_red_bits.iter().enumerate() {
+            assert_eq!(b.get_value(), Some(h_red_int.bit(i as u64)));
+        }
+
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_bits_at_most_constant() {
+        let c = BigInt::from(0b101100u64);
+        for v in 0u64..64 {
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let bits = (0..6)
+                .map(|i| {
+                    Boolean::from(
+                        AllocatedBit::alloc(
+                            cs.namespace(|| format!("alloc bit {i}")),
+                            Some((v >> i) & 1 == 1),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            enforce_bits_le_at_most_constant(&mut cs.namespace(|| "v <= c"), &bits, &c).unwrap();
+            assert_eq!(cs.is_satisfied(), BigInt::from(v) <= c);
+        }
+    }
 }
@@ -310,9 +310,9 @@ where
     where
         CS: ConstraintSystem<F>,
     {
-        assert!(self.overflow + 2 <= Self::max_overflow(),
-                "Not enough bits in native field to accomodate a subtraction operation which is performed during reduce: {} > {}",
-                self.overflow + 2,
+        assert!(self.overflow < Self::max_overflow(),
+                "Not enough bits in native field to accomodate a subtraction operation which is performed during reduce: {} >= {}",
+                self.overflow,
                 Self::max_overflow(),
             );
 
@@ -326,8 +326,16 @@ where
             return Err(SynthesisError::Unsatisfiable);
         }
 
+        // The preconditions of the operations keep their results below the maximum overflow, so
+        // that they can always be reduced
         let r = self.compute_rem(&mut cs.namespace(|| "remainder modulo field modulus"))?;
-        Self::assert_is_equal(&mut cs.namespace(|| "check equality"), &r, self)?;
+        if self.overflow + 3 <= Self::max_overflow() {
+            Self::assert_is_equal(&mut cs.namespace(|| "check equality"), &r, self)?;
+        } else {
+            // r - self has 2 more bits of overflow than self, which leaves no room for the carry
+            // in the limbs equality check, whereas self - r has the overflow of self
+            Self::assert_is_equal(&mut cs.namespace(|| "check equality"), self, &r)?;
+        }
         Ok(r)
     }
 
@@ -335,7 +343,7 @@ where
         let reduce_right = a.overflow < b.overflow;
         let next_overflow = a.overflow.max(b.overflow) + 1;
 
-        if next_overflow > Self::max_overflow() {
+        if next_overflow >= Self::max_overflow() {
             Err(OverflowError {
                 op: Optype::Add,
                 next_overflow,
@@ -413,7 +421,7 @@ where
         let reduce_right = a.overflow < b.overflow;
         let next_overflow = a.overflow.max(b.overflow + 2);
 
-        if next_overflow > Self::max_overflow() {
+        if next_overflow >= Self::max_overflow() {
             Err(OverflowError {
                 op: Optype::Sub,
                 next_overflow,
@@ -560,7 +568,7 @@ where
         let max_carry_bits = (a.len().min(b.len()) as f32).log2().ceil() as usize;
         let next_overflow = P::bits_per_limb() + a.overflow + b.overflow + max_carry_bits;
 
-        if next_overflow > Self::max_overflow() {
+        if next_overflow >= Self::max_overflow() {
             Err(OverflowError {
                 op: Optype::Mul,
                 next_overflow,
@@ -717,7 +725,7 @@ where
         }
         let mut next_overflow: usize = constant.bits() as usize + self.overflow;
 
-        let elem = if next_overflow > Self::max_overflow() {
+        let elem = if next_overflow >= Self::max_overflow() {
             next_overflow = constant.bits() as usize;
             self.reduce(
                 &mut cs.namespace(|| "reduce element to accommodate mul with const".to_string()),
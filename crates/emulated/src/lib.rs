@@ -1,4 +1,8 @@
+pub mod ecdsa;
 pub mod field_element;
 pub mod field_hints;
 pub mod field_ops;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 pub mod util;
+pub mod weierstrass;
@@ -0,0 +1,233 @@
+//! Helpers shared by the tests of curves built on [`WeierstrassPoint`] and of their ECDSA
+//! verification gadgets. Only available in tests and with the `test-utils` feature.
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
+use bellpepper_core::test_cs::TestConstraintSystem;
+use bellpepper_core::ConstraintSystem;
+use ff::{PrimeField, PrimeFieldBits};
+use halo2curves::group::{Curve, Group};
+use halo2curves::CurveAffine;
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_traits::{One, Zero};
+
+use crate::field_element::{EmulatedFieldElement, EmulatedFieldParams};
+use crate::weierstrass::{EmulatedCurveParams, WeierstrassPoint};
+
+/// Returns the affine coordinates of `p`, or `(0, 0)` if `p` is the identity
+pub fn coordinates<C: CurveAffine>(p: &C) -> (BigInt, BigInt) {
+    let to_bigint = |v: &C::Base| BigInt::from_bytes_le(Sign::Plus, v.to_repr().as_ref());
+    Option::from(p.coordinates())
+        .map(|c: halo2curves::Coordinates<C>| (to_bigint(c.x()), to_bigint(c.y())))
+        .unwrap_or((BigInt::zero(), BigInt::zero()))
+}
+
+/// Returns `k` reduced modulo the order of the field `S`
+pub fn bigint_to_scalar<S: PrimeField>(k: &BigInt) -> S {
+    S::from_str_vartime(&k.to_str_radix(10)).unwrap()
+}
+
+/// Allocates the point `p` of the curve `C`
+pub fn alloc_point<F, P, CP, C, CS>(cs: &mut CS, name: &str, p: &C) -> WeierstrassPoint<F, P, CP>
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    CP: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+    C: CurveAffine,
+    CS: ConstraintSystem<F>,
+{
+    WeierstrassPoint::alloc_element(
+        &mut cs.namespace(|| format!("alloc {name}")),
+        &Some(coordinates(p)),
+    )
+    .unwrap()
+}
+
+/// Allocates the `num_bits` least significant bits of `value`, in little-endian order
+pub fn alloc_bits<F, CS>(cs: &mut CS, name: &str, value: &BigInt, num_bits: usize) -> Vec<Boolean>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    (0..num_bits)
+        .map(|i| {
+            Boolean::from(
+                AllocatedBit::alloc(
+                    cs.namespace(|| format!("alloc {name} bit {i}")),
+                    Some(value.bit(i as u64)),
+                )
+                .unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// Returns a random public key and a signature `(r, s)` of `msg_hash` over the curve `C`, whose
+/// group order is the modulus of `Q`
+pub fn sign<C, Q>(msg_hash: &BigInt) -> ((BigInt, BigInt), BigInt, BigInt)
+where
+    C: CurveAffine,
+    Q: EmulatedFieldParams,
+{
+    let mut rng = rand::thread_rng();
+    let n = Q::modulus();
+    let d = rng.gen_bigint_range(&BigInt::one(), &n);
+    let k = rng.gen_bigint_range(&BigInt::one(), &n);
+    let mul_generator = |k: &BigInt| {
+        let k = bigint_to_scalar::<<C::CurveExt as Group>::Scalar>(k);
+        coordinates::<C>(&(C::CurveExt::generator() * k).to_affine())
+    };
+    let pk = mul_generator(&d);
+    let r = mul_generator(&k).0 % &n;
+    let k_inv = k.modpow(&(&n - 2), &n);
+    let s = k_inv * (msg_hash + &r * &d) % &n;
+    (pk, r, s)
+}
+
+/// Checks that the generator of `CP` is the generator of `C` and is on the curve
+pub fn check_generator_is_on_curve<F, P, CP, C>()
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    CP: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+    C: CurveAffine,
+{
+    let mut cs = TestConstraintSystem::<F>::new();
+    let g = WeierstrassPoint::<F, P, CP>::generator();
+    let g_alloc =
+        alloc_point::<F, P, CP, C, _>(&mut cs, "g", &C::CurveExt::generator().to_affine());
+    WeierstrassPoint::assert_is_equal(&mut cs.namespace(|| "g = G"), &g_alloc, &g).unwrap();
+    g_alloc
+        .assert_is_on_curve(&mut cs.namespace(|| "g on curve"))
+        .unwrap();
+    if !cs.is_satisfied() {
+        eprintln!("{:?}", cs.which_is_unsatisfied())
+    }
+    assert!(cs.is_satisfied());
+}
+
+/// Checks `double_scalar_mul` on random points of `C` and random scalars modulo the modulus
+/// of `Q`
+pub fn check_random_double_scalar_mul<F, P, CP, C, Q>()
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    CP: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+    C: CurveAffine,
+    Q: EmulatedFieldParams,
+{
+    let mut rng = rand::thread_rng();
+    let n = Q::modulus();
+    let p = C::CurveExt::random(&mut rng);
+    let q = C::CurveExt::random(&mut rng);
+    let a = rng.gen_bigint_range(&BigInt::zero(), &n);
+    let b = rng.gen_bigint_range(&BigInt::zero(), &n);
+    let c = (p * bigint_to_scalar::<<C::CurveExt as Group>::Scalar>(&a)
+        + q * bigint_to_scalar::<<C::CurveExt as Group>::Scalar>(&b))
+    .to_affine();
+
+    let num_bits = n.bits() as usize;
+    let mut cs = TestConstraintSystem::<F>::new();
+    let p_alloc = alloc_point::<F, P, CP, C, _>(&mut cs, "p", &p.to_affine());
+    let q_alloc = alloc_point::<F, P, CP, C, _>(&mut cs, "q", &q.to_affine());
+    let a_bits = alloc_bits(&mut cs, "a", &a, num_bits);
+    let b_bits = alloc_bits(&mut cs, "b", &b, num_bits);
+    let c_alloc = alloc_point::<F, P, CP, C, _>(&mut cs, "c", &c);
+    let res_alloc = WeierstrassPoint::double_scalar_mul(
+        &mut cs.namespace(|| "[a]p + [b]q"),
+        &p_alloc,
+        &a_bits,
+        &q_alloc,
+        &b_bits,
+    )
+    .unwrap();
+    WeierstrassPoint::assert_is_equal(&mut cs.namespace(|| "res = c"), &res_alloc, &c_alloc)
+        .unwrap();
+    if !cs.is_satisfied() {
+        eprintln!("{:?}", cs.which_is_unsatisfied())
+    }
+    assert!(cs.is_satisfied());
+}
+
+/// A Wycheproof ECDSA test vector, where `sig` is DER-encoded
+pub struct WycheproofVector {
+    pub pk: (BigInt, BigInt),
+    pub msg: Vec<u8>,
+    pub sig: Vec<u8>,
+    pub valid: bool,
+}
+
+/// Parses Wycheproof ECDSA test vectors given as CSV with a header line and one line
+/// `qx,qy,msg,sig,result` per test case, all fields but `result` being hex-encoded
+pub fn parse_wycheproof_vectors(csv: &str) -> Vec<WycheproofVector> {
+    let hex_int = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+    csv.lines()
+        .skip(1)
+        .map(|line| {
+            let fields = line.split(',').collect::<Vec<_>>();
+            assert_eq!(fields.len(), 5, "malformed test vector {line}");
+            WycheproofVector {
+                pk: (hex_int(fields[0]), hex_int(fields[1])),
+                msg: hex::decode(fields[2]).unwrap(),
+                sig: hex::decode(fields[3]).unwrap(),
+                valid: fields[4] == "valid",
+            }
+        })
+        .collect()
+}
+
+/// Parses a DER-encoded INTEGER starting at `data[0]`, returning its value and the
+/// remaining bytes. Rejects non-minimal and negative encodings.
+fn parse_der_integer(data: &[u8]) -> Option<(BigInt, &[u8])> {
+    let (&tag, data) = data.split_first()?;
+    let (&len, data) = data.split_first()?;
+    let len = len as usize;
+    if tag != 0x02 || len == 0 || len >= 0x80 || data.len() < len {
+        return None;
+    }
+    let (value, rest) = data.split_at(len);
+    let negative = value[0] & 0x80 != 0;
+    let non_minimal = len > 1 && value[0] == 0 && value[1] & 0x80 == 0;
+    if negative || non_minimal {
+        return None;
+    }
+    Some((BigInt::from_bytes_be(Sign::Plus, value), rest))
+}
+
+/// Parses a DER-encoded ECDSA signature `SEQUENCE { r INTEGER, s INTEGER }`
+pub fn parse_der_signature(sig: &[u8]) -> Option<(BigInt, BigInt)> {
+    let (&tag, data) = sig.split_first()?;
+    let (&len, data) = data.split_first()?;
+    if tag != 0x30 || len >= 0x80 || len as usize != data.len() {
+        return None;
+    }
+    let (r, data) = parse_der_integer(data)?;
+    let (s, data) = parse_der_integer(data)?;
+    data.is_empty().then_some((r, s))
+}
+
+/// Runs `verify` on the vectors of `csv` selected by `filter` and checks that it returns whether
+/// the signature is valid. `verify` is given the vector and the scalars `(r, s)` of its signature.
+///
+/// `filter` is given the index of the vector among the well-formed ones. Signatures that are not
+/// valid DER encodings, or whose scalars do not fit in `num_bits` bits, cannot be given to the
+/// gadget and must be invalid.
+pub fn check_wycheproof_vectors(
+    csv: &str,
+    num_bits: usize,
+    filter: impl Fn(usize) -> bool,
+    verify: impl Fn(&WycheproofVector, &BigInt, &BigInt) -> bool,
+) {
+    let mut num_checked = 0;
+    for (i, v) in parse_wycheproof_vectors(csv).iter().enumerate() {
+        let rs = parse_der_signature(&v.sig)
+            .filter(|(r, s)| r.bits() <= num_bits as u64 && s.bits() <= num_bits as u64);
+        let Some((r, s)) = rs else {
+            assert!(!v.valid, "valid test vector {i} could not be parsed");
+            continue;
+        };
+        num_checked += 1;
+        if !filter(num_checked - 1) {
+            continue;
+        }
+        assert_eq!(verify(v, &r, &s), v.valid, "test vector {i}");
+    }
+}
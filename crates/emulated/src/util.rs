@@ -1,6 +1,6 @@
 use std::ops::Rem;
 
-use bellpepper_core::boolean::AllocatedBit;
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
 use bellpepper_core::num::{AllocatedNum, Num};
 use bellpepper_core::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
 use ff::{PrimeField, PrimeFieldBits};
@@ -149,6 +149,57 @@ pub fn alloc_num_equals_constant<F: PrimeField, CS: ConstraintSystem<F>>(
     Ok(r)
 }
 
+/// Enforce that little-endian bits represent an integer at most equal to `constant`
+///
+/// Based on `enforce_in_field` in `bellman/src/gadgets/boolean.rs`
+pub fn enforce_bits_le_at_most_constant<F, CS>(
+    cs: &mut CS,
+    bits: &[Boolean],
+    constant: &BigInt,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    assert!(!constant.is_negative());
+    if constant.bits() as usize > bits.len() {
+        // Every value representable by the bits is smaller than the constant
+        return Ok(());
+    }
+
+    // `last_run` is true if and only if the bits seen so far (starting from the
+    // most significant one) are equal to the corresponding bits of `constant`
+    let mut last_run = Boolean::constant(true);
+    let mut current_run: Vec<Boolean> = vec![];
+
+    for (i, b) in bits.iter().enumerate().rev() {
+        if constant.bit(i as u64) {
+            current_run.push(b.clone());
+        } else {
+            if !current_run.is_empty() {
+                for (j, r) in current_run.iter().enumerate() {
+                    last_run = Boolean::and(
+                        cs.namespace(|| format!("and of run bit {j} before bit {i}")),
+                        &last_run,
+                        r,
+                    )?;
+                }
+                current_run.clear();
+            }
+
+            // If `last_run` is true, `b` must be false or the value would exceed `constant`
+            cs.enforce(
+                || format!("bit {i} is zero if higher bits equal constant"),
+                |_| last_run.lc(CS::one(), F::ONE),
+                |_| b.lc(CS::one(), F::ONE),
+                |lc| lc,
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Convert a non-negative BigInt into a field element
 pub fn bigint_to_scalar<F>(value: &BigInt) -> F
 where
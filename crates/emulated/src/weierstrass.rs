@@ -0,0 +1,820 @@
+use std::marker::PhantomData;
+
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+use crate::field_element::{EmulatedFieldElement, EmulatedFieldParams};
+
+/// Parameters of an elliptic curve whose coordinates are of type `BaseElement`
+pub trait EmulatedCurveParams<BaseElement> {
+    fn a() -> BaseElement;
+    fn b() -> BaseElement;
+
+    fn generator() -> (BaseElement, BaseElement); // returns (x, y) coordinates of a generator point for the curve
+}
+
+/// Represents an affine point on the short Weierstrass curve `y² = x³ + ax + b` given by `C`,
+/// over the emulated base field given by `P`. Point at infinity is represented with (0, 0)
+pub struct WeierstrassPoint<F, P, C>
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    C: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+{
+    pub x: EmulatedFieldElement<F, P>,
+    pub y: EmulatedFieldElement<F, P>,
+    _c: PhantomData<C>,
+}
+
+impl<F, P, C> Clone for WeierstrassPoint<F, P, C>
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    C: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.x.clone(), self.y.clone())
+    }
+}
+
+impl<F, P, C> WeierstrassPoint<F, P, C>
+where
+    F: PrimeFieldBits,
+    P: EmulatedFieldParams,
+    C: EmulatedCurveParams<EmulatedFieldElement<F, P>>,
+{
+    pub fn new(x: EmulatedFieldElement<F, P>, y: EmulatedFieldElement<F, P>) -> Self {
+        Self {
+            x,
+            y,
+            _c: PhantomData,
+        }
+    }
+
+    pub fn identity() -> Self {
+        // (0,0) is the point at infinity
+        Self::new(EmulatedFieldElement::zero(), EmulatedFieldElement::zero())
+    }
+
+    pub fn generator() -> Self {
+        let (x, y) = C::generator();
+        Self::new(x, y)
+    }
+
+    /// Returns true if the `a` coefficient of the curve is zero, in which case the terms in `a`
+    /// are skipped
+    fn a_is_zero() -> bool {
+        BigInt::try_from(&C::a()).map_or(false, |a| a.is_zero())
+    }
+
+    /// Allocates a point from its affine coordinates, where `(0, 0)` is the point at infinity.
+    /// The point is not checked to be on the curve.
+    pub fn alloc_element<CS>(
+        cs: &mut CS,
+        value: &Option<(BigInt, BigInt)>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = value.as_ref().map(|v| v.0.clone());
+        let y = value.as_ref().map(|v| v.1.clone());
+        let x = EmulatedFieldElement::allocate_optional_field_element_unchecked(
+            &mut cs.namespace(|| "allocate x"),
+            &x,
+        )?;
+        let y = EmulatedFieldElement::allocate_optional_field_element_unchecked(
+            &mut cs.namespace(|| "allocate y"),
+            &y,
+        )?;
+
+        Ok(Self::new(x, y))
+    }
+
+    /// Returns the generator as an allocated point, constrained to be equal to `generator()`
+    pub fn alloc_generator<CS>(cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let g = Self::generator();
+        let g_value = BigInt::try_from(&g.x).ok().zip(BigInt::try_from(&g.y).ok());
+        let g_alloc = Self::alloc_element(&mut cs.namespace(|| "alloc G"), &g_value)?;
+        Self::assert_is_equal(&mut cs.namespace(|| "check G"), &g_alloc, &g)?;
+        Ok(g_alloc)
+    }
+
+    pub fn alloc_is_identity<CS>(&self, cs: &mut CS) -> Result<AllocatedBit, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = self.x.alloc_is_zero(&mut cs.namespace(|| "x =? 0"))?;
+        let y = self.y.alloc_is_zero(&mut cs.namespace(|| "y =? 0"))?;
+        AllocatedBit::and(&mut cs.namespace(|| "and(x, y)"), &x, &y)
+    }
+
+    /// Returns whether `self` is the point at infinity, as a constant if `self` is a constant
+    /// point, in which case nothing is allocated
+    fn is_identity<CS>(&self, cs: &mut CS) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if self.x.is_constant() && self.y.is_constant() {
+            let x = BigInt::try_from(&self.x)?;
+            let y = BigInt::try_from(&self.y)?;
+            return Ok(Boolean::constant(x.is_zero() && y.is_zero()));
+        }
+        Ok(Boolean::from(self.alloc_is_identity(cs)?))
+    }
+
+    pub fn assert_is_equal<CS>(cs: &mut CS, a: &Self, b: &Self) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        EmulatedFieldElement::assert_is_equal(&mut cs.namespace(|| "x =? x"), &a.x, &b.x)?;
+        EmulatedFieldElement::assert_is_equal(&mut cs.namespace(|| "y =? y"), &a.y, &b.y)?;
+        Ok(())
+    }
+
+    pub fn reduce<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x_reduced = self.x.reduce(&mut cs.namespace(|| "x mod P"))?;
+        let y_reduced = self.y.reduce(&mut cs.namespace(|| "y mod P"))?;
+        Ok(Self::new(x_reduced, y_reduced))
+    }
+
+    /// Returns the EC addition between `self` and `value`. Requires that `self != value` and that neither point is the identity
+    pub fn add<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let (p, q) = (self, value);
+        let cs = &mut cs.namespace(|| "Weierstrass::add(p, q)");
+        // compute λ = (q.y-p.y)/(q.x-p.x)
+        let qypy = q.y.sub(&mut cs.namespace(|| "qypy <- q.y - p.y"), &p.y)?;
+        let qxpx = q.x.sub(&mut cs.namespace(|| "qxpx <- q.x - p.x"), &p.x)?;
+        let lambda = qypy.divide(&mut cs.namespace(|| "lambda <- qypy div qxpx"), &qxpx)?;
+
+        // xr = λ²-p.x-q.x
+        let lambda_sq = lambda.mul(
+            &mut cs.namespace(|| "lambda_sq <- lambda.square()"),
+            &lambda,
+        )?;
+        let qxpx = p.x.add(&mut cs.namespace(|| "qxpx <- p.x + q.x"), &q.x)?;
+        let xr = lambda_sq.sub(&mut cs.namespace(|| "xr <- lambda_sq - qxpx"), &qxpx)?;
+
+        // p.y = λ(p.x-r.x) - p.y
+        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
+        let lambdapxrx = lambda.mul(&mut cs.namespace(|| "lambdapxrx <- lambda * pxrx"), &pxrx)?;
+        let yr = lambdapxrx.sub(&mut cs.namespace(|| "yr <- lambdapxrx - p.y"), &p.y)?;
+
+        Ok(Self::new(xr, yr))
+    }
+
+    /// Returns the EC addition between `self` and `value`. Supports `p == q`, either point can be the identity
+    /// and one of them can be a constant
+    /// It uses the unified formulas of Brier and Joye from [BriJoy02 (Corollary 1)](https://link.springer.com/content/pdf/10.1007/3-540-45664-3_24.pdf)
+    pub fn add_unified<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let (p, q) = (self, value);
+        let cs = &mut cs.namespace(|| "Weierstrass::add_unified(p, q)");
+        let sel1 = p.is_identity(&mut cs.namespace(|| "sel1 <- p.is_identity()"))?;
+        let sel2 = q.is_identity(&mut cs.namespace(|| "sel2 <- q.is_identity()"))?;
+
+        // λ = ((p.x+q.x)² - p.x*q.x + a)/(p.y + q.y)
+        let pxqx = p.x.mul(&mut cs.namespace(|| "pxqx <- p.x * q.x"), &q.x)?;
+        let pxplusqx =
+            p.x.add(&mut cs.namespace(|| "pxplusqx <- p.x + q.x"), &q.x)?;
+        let num = pxplusqx.mul(&mut cs.namespace(|| "num <- pxplusqx^2"), &pxplusqx)?;
+        let num = num.sub(&mut cs.namespace(|| "num <- num - pxqx"), &pxqx)?;
+        let num = if Self::a_is_zero() {
+            num
+        } else {
+            num.add(&mut cs.namespace(|| "num <- num + a"), &C::a())?
+        };
+        let denum = p.y.add(&mut cs.namespace(|| "denum <- p.y + q.y"), &q.y)?;
+        let sel3 = denum.alloc_is_zero(&mut cs.namespace(|| "sel3 <- denum.is_zero()"))?;
+        // if p.y + q.y = 0 and p.x != q.x, the unified formula is undefined, so use
+        // λ = (q.y - p.y)/(q.x - p.x) instead
+        let qypy = q.y.sub(&mut cs.namespace(|| "qypy <- q.y - p.y"), &p.y)?;
+        let qxpx = q.x.sub(&mut cs.namespace(|| "qxpx <- q.x - p.x"), &p.x)?;
+        let sel4 = qxpx.alloc_is_zero(&mut cs.namespace(|| "sel4 <- qxpx.is_zero()"))?;
+        let num = num.reduce(&mut cs.namespace(|| "num <- num.reduce()"))?;
+        let num = EmulatedFieldElement::conditionally_select(
+            &mut cs.namespace(|| "num <- select(num, qypy, sel3)"),
+            &num,
+            &qypy,
+            &Boolean::from(sel3.clone()),
+        )?;
+        let denum = EmulatedFieldElement::conditionally_select(
+            &mut cs.namespace(|| "denum <- select(denum, qxpx, sel3)"),
+            &denum,
+            &qxpx,
+            &Boolean::from(sel3.clone()),
+        )?;
+        // if p = -q, assign dummy 1 to denum and continue
+        let sel5 = AllocatedBit::and(
+            &mut cs.namespace(|| "sel5 <- and(sel3, sel4)"),
+            &sel3,
+            &sel4,
+        )?;
+        let denum = EmulatedFieldElement::conditionally_select(
+            &mut cs.namespace(|| "denum <- select(denum, 1, sel5)"),
+            &denum,
+            &EmulatedFieldElement::one(),
+            &Boolean::from(sel5.clone()),
+        )?;
+        let lambda = num.divide(&mut cs.namespace(|| "lamda <- num div denum"), &denum)?;
+
+        // x = λ^2 - p.x - q.x
+        let xr = lambda.mul(&mut cs.namespace(|| "xr <- lambda.square()"), &lambda)?;
+        let xr = xr.sub(&mut cs.namespace(|| "xr <- xr - pxplusqx"), &pxplusqx)?;
+
+        // y = λ(p.x - xr) - p.y
+        let yr = p.x.sub(&mut cs.namespace(|| "yr <- p.x - xr"), &xr)?;
+        let yr = yr.mul(&mut cs.namespace(|| "yr <- yr * lambda"), &lambda)?;
+        let yr = yr.sub(&mut cs.namespace(|| "yr <- yr - p.y"), &p.y)?;
+        let xr = xr.reduce(&mut cs.namespace(|| "xr <- xr.reduce()"))?;
+        let yr = yr.reduce(&mut cs.namespace(|| "yr <- yr.reduce()"))?;
+        let res = Self::new(xr, yr);
+
+        // if p=(0,0) return q
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, q, sel1)"),
+            &res,
+            q,
+            &sel1,
+        )?;
+        // if q=(0,0) return p
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, p, sel2)"),
+            &res,
+            p,
+            &sel2,
+        )?;
+        // if p = -q, return (0, 0)
+        let res = Self::conditionally_select(
+            &mut cs.namespace(|| "res <- select(res, 0, sel5)"),
+            &res,
+            &Self::identity(),
+            &Boolean::from(sel5),
+        )?;
+
+        Ok(res)
+    }
+
+    /// Returns `-P`
+    pub fn neg<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self::new(
+            self.x.clone(),
+            self.y.neg(&mut cs.namespace(|| "p <- p.neg()"))?,
+        ))
+    }
+
+    /// Returns `self - value`. Requires that `self != -value` and neither point is the identity since it calls `add`
+    pub fn sub<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let neg = value.neg(&mut cs.namespace(|| "q_neg <- q.neg()"))?;
+        let res = self.add(&mut cs.namespace(|| "p + q_neg"), &neg)?;
+        Ok(res)
+    }
+
+    /// Returns `3x² + a`, the numerator of the slope of the tangent at `x`
+    fn tangent_numerator<CS>(
+        cs: &mut CS,
+        x: &EmulatedFieldElement<F, P>,
+    ) -> Result<EmulatedFieldElement<F, P>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let xx3a = x.mul(&mut cs.namespace(|| "xx3a <- p.x.square()"), x)?;
+        let xx3a = xx3a.mul_const(&mut cs.namespace(|| "xx3a <- xx3a * 3"), &BigInt::from(3))?;
+        if Self::a_is_zero() {
+            Ok(xx3a)
+        } else {
+            xx3a.add(&mut cs.namespace(|| "xx3a <- xx3a + a"), &C::a())
+        }
+    }
+
+    /// Returns `self + self`
+    pub fn double<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let cs = &mut cs.namespace(|| "Weierstrass::double(p)");
+        let p = self.reduce(&mut cs.namespace(|| "p <- p.reduce()"))?;
+        // compute λ = (3p.x² + a)/2*p.y
+        let xx3a = Self::tangent_numerator(cs, &p.x)?;
+        let y2 = p.y.add(&mut cs.namespace(|| "y2 <- p.y.double()"), &p.y)?;
+        let lambda = xx3a.divide(&mut cs.namespace(|| "lambda <- xx3a div y2"), &y2)?;
+
+        // xr = λ²-2p.x
+        let x2 = p.x.add(&mut cs.namespace(|| "x2 <- p.x.double()"), &p.x)?;
+        let lambda_sq = lambda.mul(
+            &mut cs.namespace(|| "lambda_sq <- lambda.square()"),
+            &lambda,
+        )?;
+        let xr = lambda_sq.sub(&mut cs.namespace(|| "xr <- lambda_sq - x2"), &x2)?;
+
+        // yr = λ(p-xr) - p.y
+        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
+        let lambdapxrx = lambda.mul(&mut cs.namespace(|| "lambdapxrx <- lambda * pxrx"), &pxrx)?;
+        let yr = lambdapxrx.sub(&mut cs.namespace(|| "yr <- lambdapxrx - p.y"), &p.y)?;
+
+        Ok(Self::new(xr, yr))
+    }
+
+    /// Returns `self + self + self`
+    pub fn triple<CS>(&self, cs: &mut CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let p = self;
+        let cs = &mut cs.namespace(|| "Weierstrass::triple(p)");
+        // compute λ1 = (3p.x² + a)/2p.y
+        let xx = Self::tangent_numerator(cs, &p.x)?;
+        let y2 = p.y.add(&mut cs.namespace(|| "y2 <- p.y.double()"), &p.y)?;
+        let l1 = xx.divide(&mut cs.namespace(|| "l1 <- xx div y2"), &y2)?;
+
+        // xr = λ1²-2p.x
+        let x2 =
+            p.x.mul_const(&mut cs.namespace(|| "x2 <- p.x * 2"), &BigInt::from(2))?;
+        let l1l1 = l1.mul(&mut cs.namespace(|| "l1l1 <- l1 * l1"), &l1)?;
+        let x2 = l1l1.sub(&mut cs.namespace(|| "x2 <- l1l1 - x2"), &x2)?;
+
+        // ommit y2 computation, and
+        // compute λ2 = 2p.y/(x2 − p.x) − λ1.
+        let x1x2 = p.x.sub(&mut cs.namespace(|| "x1x2 <- p.x - x2"), &x2)?;
+        let l2 = y2.divide(&mut cs.namespace(|| "l2 <- y2 div x1x2"), &x1x2)?;
+        let l2 = l2.sub(&mut cs.namespace(|| "l2 <- l2 - l1"), &l1)?;
+
+        // xr = λ²-p.x-x2
+        let l2l2 = l2.mul(&mut cs.namespace(|| "l2l2 <- l2 * l2"), &l2)?;
+        let qxrx = x2.add(&mut cs.namespace(|| "qxrx <- x2 + p.x"), &p.x)?;
+        let xr = l2l2.sub(&mut cs.namespace(|| "xr <- l2l2 - qxrx"), &qxrx)?;
+
+        // yr = λ(p.x-xr) - p.y
+        let pxrx = p.x.sub(&mut cs.namespace(|| "pxrx <- p.x - xr"), &xr)?;
+        let l2pxrx = l2.mul(&mut cs.namespace(|| "l2pxrx <- l2 * pxrx"), &pxrx)?;
+        let yr = l2pxrx.sub(&mut cs.namespace(|| "yr <- l2pxrx - p.y"), &p.y)?;
+
+        Ok(Self::new(xr, yr))
+    }
+
+    /// Returns `2*self + value`
+    pub fn double_and_add<CS>(&self, cs: &mut CS, value: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let (p, q) = (self, value);
+        let cs = &mut cs.namespace(|| "Weierstrass::double_and_add(p, q)");
+        // compute λ1 = (q.y-p.y)/(q.x-p.x)
+        let yqyp = q.y.sub(&mut cs.namespace(|| "yqyp <- q.y - p.y"), &p.y)?;
+        let xqxp = q.x.sub(&mut cs.namespace(|| "xqxp <- q.x - p.x"), &p.x)?;
+        let l1 = yqyp.divide(&mut cs.namespace(|| "l1 <- yqyp div xqxp"), &xqxp)?;
+
+        // compute x2 = l1²-p.x-q.x
+        let l1l1 = l1.mul(&mut cs.namespace(|| "l1l1 <- l1.square()"), &l1)?;
+        let xqxp = p.x.add(&mut cs.namespace(|| "xqxp <- p.x + q.x"), &q.x)?;
+        let x2 = l1l1.sub(&mut cs.namespace(|| "x2 <- l1l1 - xqxp"), &xqxp)?;
+
+        // ommit y2 computation
+        // compute l2 = -l1-2*p.y/(x2-p.x)
+        let ypyp = p.y.add(&mut cs.namespace(|| "ypyp <- p.y + p.y"), &p.y)?;
+        let x2xp = x2.sub(&mut cs.namespace(|| "x2xp <- x2 - p.x"), &p.x)?;
+        let l2 = ypyp.divide(&mut cs.namespace(|| "l2 <- ypyp div x2xp"), &x2xp)?;
+        let l2 = l1.add(&mut cs.namespace(|| "l2 <- l1 + l2"), &l2)?;
+        let l2 = l2.neg(&mut cs.namespace(|| "l2 <- l2.neg()"))?;
+
+        // compute x3 =l2²-p.x-x3
+        let l2l2 = l2.mul(&mut cs.namespace(|| "l2l2 <- l2.square()"), &l2)?;
+        let x3 = l2l2.sub(&mut cs.namespace(|| "x3 <- l2l2 - p.x"), &p.x)?;
+        let x3 = x3.sub(&mut cs.namespace(|| "x3 <- x3 - x2"), &x2)?;
+
+        // compute y3 = l2*(p.x - x3)-p.y
+        let y3 = p.x.sub(&mut cs.namespace(|| "y3 <- p.x - x3"), &x3)?;
+        let y3 = l2.mul(&mut cs.namespace(|| "y3 <- l2 * y3"), &y3)?;
+        let y3 = y3.sub(&mut cs.namespace(|| "y3 <- y3 - p.y"), &p.y)?;
+
+        Ok(Self::new(x3, y3))
+    }
+
+    pub fn conditionally_select<CS>(
+        cs: &mut CS,
+        p0: &Self,
+        p1: &Self,
+        condition: &Boolean,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let x = EmulatedFieldElement::conditionally_select(
+            &mut cs.namespace(|| "cond x"),
+            &p0.x,
+            &p1.x,
+            condition,
+        )?;
+        let y = EmulatedFieldElement::conditionally_select(
+            &mut cs.namespace(|| "cond y"),
+            &p0.y,
+            &p1.y,
+            condition,
+        )?;
+        Ok(Self::new(x, y))
+    }
+
+    /// Selects an element of `inputs` using the big-endian `select_bits`
+    pub fn mux_tree<'a, CS>(
+        cs: &mut CS,
+        select_bits: impl Iterator<Item = &'a Boolean> + Clone,
+        inputs: &[Self],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let inputs_x = inputs.iter().map(|i| i.x.clone()).collect::<Vec<_>>();
+        let inputs_y = inputs.iter().map(|i| i.y.clone()).collect::<Vec<_>>();
+        let x = EmulatedFieldElement::mux_tree(
+            &mut cs.namespace(|| "mux x"),
+            select_bits.clone(),
+            &inputs_x,
+        )?;
+        let y =
+            EmulatedFieldElement::mux_tree(&mut cs.namespace(|| "mux y"), select_bits, &inputs_y)?;
+        Ok(Self::new(x, y))
+    }
+
+    /// Returns `[a]P + [b]Q` using the interleaved (Straus-Shamir) method, where `a` and `b` are
+    /// given as little-endian bits. Scalars of different lengths are padded with zeros.
+    ///
+    /// The doublings are shared between both scalars and every window of `window_size` bits
+    /// selects `[i]P + [j]Q` from a joint lookup table of `2^(2*window_size)` points. All the
+    /// additions use `add_unified`, so `P`, `Q` and any intermediate value can be the identity.
+    pub fn double_scalar_mul_windowed<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+        window_size: usize,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if window_size == 0 {
+            eprintln!("Window size must be positive");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        if a.is_empty() && b.is_empty() {
+            eprintln!("Scalar bit vectors cannot both be empty");
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut cs =
+            cs.namespace(|| format!("Weierstrass::double_scalar_mul(p, a, q, b, {window_size})"));
+        let p = p.reduce(&mut cs.namespace(|| "p <- p.reduce()"))?;
+        let q = q.reduce(&mut cs.namespace(|| "q <- q.reduce()"))?;
+
+        // the entry at index i + 2^window_size * j is [i]P + [j]Q
+        let table_size = 1usize << window_size;
+        let mut table: Vec<Self> = vec![Self::identity(), p.clone()];
+        for i in 2..table_size {
+            let point = table[i - 1].add_unified(&mut cs.namespace(|| format!("{i}P")), &p)?;
+            table.push(point);
+        }
+        for j in 1..table_size {
+            for i in 0..table_size {
+                if i == 0 && j == 1 {
+                    table.push(q.clone());
+                    continue;
+                }
+                let point = table[i + table_size * (j - 1)]
+                    .add_unified(&mut cs.namespace(|| format!("{i}P + {j}Q")), &q)?;
+                table.push(point);
+            }
+        }
+
+        let num_windows = (a.len().max(b.len()) + window_size - 1) / window_size;
+        let bit_or_zero = |bits: &[Boolean], i: usize| -> Boolean {
+            bits.get(i).cloned().unwrap_or(Boolean::constant(false))
+        };
+
+        let mut res: Option<Self> = None;
+        for w in (0..num_windows).rev() {
+            // index bits in little-endian order: the bits of `a` followed by the bits of `b`
+            let index_bits = (0..window_size)
+                .map(|k| bit_or_zero(a, w * window_size + k))
+                .chain((0..window_size).map(|k| bit_or_zero(b, w * window_size + k)))
+                .collect::<Vec<_>>();
+            let tmp = Self::mux_tree(
+                &mut cs.namespace(|| format!("tmp <- table[window {w}]")),
+                index_bits.iter().rev(), // mux_tree requires MSB first
+                &table,
+            )?;
+
+            res = match res {
+                None => Some(tmp),
+                Some(mut acc) => {
+                    for k in 0..window_size {
+                        acc = acc.add_unified(
+                            &mut cs.namespace(|| format!("res <- res + res ({w}, {k})")),
+                            &acc,
+                        )?;
+                    }
+                    Some(acc.add_unified(
+                        &mut cs.namespace(|| format!("res <- res + tmp ({w})")),
+                        &tmp,
+                    )?)
+                }
+            };
+        }
+
+        Ok(res.unwrap())
+    }
+
+    /// Returns `[a]P + [b]Q` using `double_scalar_mul_windowed` with windows of 2 bits
+    pub fn double_scalar_mul<CS>(
+        cs: &mut CS,
+        p: &Self,
+        a: &[Boolean],
+        q: &Self,
+        b: &[Boolean],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::double_scalar_mul_windowed(cs, p, a, q, b, 2)
+    }
+
+    /// Asserts that y^2 = x^3 + ax + b
+    pub fn assert_is_on_curve<CS>(&self, cs: &mut CS) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let y_2 = self
+            .y
+            .mul(&mut cs.namespace(|| "y_2 <- p.y.square()"), &self.y)?;
+        let x_2 = self
+            .x
+            .mul(&mut cs.namespace(|| "x_2 <- p.x.square()"), &self.x)?;
+        let x_3 = self.x.mul(&mut cs.namespace(|| "x_3 <- x * x_2"), &x_2)?;
+        let rhs = if Self::a_is_zero() {
+            x_3
+        } else {
+            let ax = self.x.mul(&mut cs.namespace(|| "ax <- x * a"), &C::a())?;
+            x_3.add(&mut cs.namespace(|| "rhs <- x_3 + ax"), &ax)?
+        };
+        let rhs = rhs.add(&mut cs.namespace(|| "rhs <- rhs + b"), &C::b())?;
+        EmulatedFieldElement::assert_is_equal(&mut cs.namespace(|| "y_2 == rhs"), &y_2, &rhs)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_element::PseudoMersennePrime;
+    use crate::test_utils::coordinates;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use ff::PrimeField;
+    use halo2curves::group::{Curve, Group};
+    use halo2curves::CurveAffine;
+    use pasta_curves::Fp;
+
+    struct Secp256k1FpParams;
+
+    impl EmulatedFieldParams for Secp256k1FpParams {
+        fn num_limbs() -> usize {
+            4
+        }
+
+        fn bits_per_limb() -> usize {
+            64
+        }
+
+        fn modulus() -> BigInt {
+            BigInt::parse_bytes(
+                b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+                16,
+            )
+            .unwrap()
+        }
+
+        fn is_modulus_pseudo_mersenne() -> bool {
+            false
+        }
+
+        fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+            None
+        }
+    }
+
+    struct Secp256k1Params;
+
+    impl<F: PrimeFieldBits> EmulatedCurveParams<EmulatedFieldElement<F, Secp256k1FpParams>>
+        for Secp256k1Params
+    {
+        fn a() -> EmulatedFieldElement<F, Secp256k1FpParams> {
+            EmulatedFieldElement::zero()
+        }
+
+        fn b() -> EmulatedFieldElement<F, Secp256k1FpParams> {
+            EmulatedFieldElement::from(&BigInt::from(7))
+        }
+
+        fn generator() -> (
+            EmulatedFieldElement<F, Secp256k1FpParams>,
+            EmulatedFieldElement<F, Secp256k1FpParams>,
+        ) {
+            let g = halo2curves::secp256k1::Secp256k1Affine::generator();
+            let (x, y) = coordinates(&g);
+            (
+                EmulatedFieldElement::from(&x),
+                EmulatedFieldElement::from(&y),
+            )
+        }
+    }
+
+    struct P256FpParams;
+
+    impl EmulatedFieldParams for P256FpParams {
+        fn num_limbs() -> usize {
+            4
+        }
+
+        fn bits_per_limb() -> usize {
+            64
+        }
+
+        fn modulus() -> BigInt {
+            BigInt::parse_bytes(
+                b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+                16,
+            )
+            .unwrap()
+        }
+
+        fn is_modulus_pseudo_mersenne() -> bool {
+            false
+        }
+
+        fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+            None
+        }
+    }
+
+    struct P256Params;
+
+    impl<F: PrimeFieldBits> EmulatedCurveParams<EmulatedFieldElement<F, P256FpParams>> for P256Params {
+        fn a() -> EmulatedFieldElement<F, P256FpParams> {
+            EmulatedFieldElement::from(&(P256FpParams::modulus() - 3))
+        }
+
+        fn b() -> EmulatedFieldElement<F, P256FpParams> {
+            EmulatedFieldElement::from(
+                &BigInt::parse_bytes(
+                    b"5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+                    16,
+                )
+                .unwrap(),
+            )
+        }
+
+        fn generator() -> (
+            EmulatedFieldElement<F, P256FpParams>,
+            EmulatedFieldElement<F, P256FpParams>,
+        ) {
+            let g = halo2curves::secp256r1::Secp256r1Affine::generator();
+            let (x, y) = coordinates(&g);
+            (
+                EmulatedFieldElement::from(&x),
+                EmulatedFieldElement::from(&y),
+            )
+        }
+    }
+
+    type Secp256k1Point = WeierstrassPoint<Fp, Secp256k1FpParams, Secp256k1Params>;
+    type P256Point = WeierstrassPoint<Fp, P256FpParams, P256Params>;
+
+    /// Checks `add`, `add_unified`, `double`, `triple`, `double_and_add` and `assert_is_on_curve`
+    /// against the native arithmetic of the curve `C`
+    fn check_arithmetic<C, P, CP>()
+    where
+        C: CurveAffine,
+        P: EmulatedFieldParams,
+        CP: EmulatedCurveParams<EmulatedFieldElement<Fp, P>>,
+    {
+        let mut rng = rand::thread_rng();
+        let a = C::CurveExt::random(&mut rng);
+        let b = C::CurveExt::random(&mut rng);
+        let cases = [
+            ("a+b", (a + b).to_affine()),
+            ("a+a", (a + a).to_affine()),
+            ("a+a+a", (a + a + a).to_affine()),
+            ("a+a+b", (a + a + b).to_affine()),
+        ];
+        let (a, b) = (a.to_affine(), b.to_affine());
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let alloc = |cs: &mut TestConstraintSystem<Fp>, name: &str, p: &C| {
+            WeierstrassPoint::<Fp, P, CP>::alloc_element(
+                &mut cs.namespace(|| format!("alloc {name}")),
+                &Some(coordinates(p)),
+            )
+            .unwrap()
+        };
+        let a_alloc = alloc(&mut cs, "a", &a);
+        let b_alloc = alloc(&mut cs, "b", &b);
+        let z_alloc = WeierstrassPoint::<Fp, P, CP>::alloc_element(
+            &mut cs.namespace(|| "alloc z"),
+            &Some((BigInt::zero(), BigInt::zero())),
+        )
+        .unwrap();
+        a_alloc
+            .assert_is_on_curve(&mut cs.namespace(|| "a on curve"))
+            .unwrap();
+
+        let sum = a_alloc.add(&mut cs.namespace(|| "a+b"), &b_alloc).unwrap();
+        let double = a_alloc.double(&mut cs.namespace(|| "a+a")).unwrap();
+        let triple = a_alloc.triple(&mut cs.namespace(|| "a+a+a")).unwrap();
+        let double_and_add = a_alloc
+            .double_and_add(&mut cs.namespace(|| "a+a+b"), &b_alloc)
+            .unwrap();
+        let res = [sum, double, triple, double_and_add];
+        for ((name, expected), res) in cases.iter().zip(res.iter()) {
+            let c_alloc = alloc(&mut cs, name, expected);
+            WeierstrassPoint::assert_is_equal(
+                &mut cs.namespace(|| format!("{name} = c")),
+                res,
+                &c_alloc,
+            )
+            .unwrap();
+        }
+        let unified = [
+            ("a+b (unified)", &a_alloc, &b_alloc, cases[0].1),
+            ("a+a (unified)", &a_alloc, &a_alloc, cases[1].1),
+            ("a+0 (unified)", &a_alloc, &z_alloc, a),
+            ("0+a (unified)", &z_alloc, &a_alloc, a),
+        ];
+        for (name, p, q, expected) in unified {
+            let res = p.add_unified(&mut cs.namespace(|| name), q).unwrap();
+            let c_alloc = alloc(&mut cs, name, &expected);
+            WeierstrassPoint::assert_is_equal(
+                &mut cs.namespace(|| format!("{name} = c")),
+                &res,
+                &c_alloc,
+            )
+            .unwrap();
+        }
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_secp256k1_arithmetic() {
+        check_arithmetic::<
+            halo2curves::secp256k1::Secp256k1Affine,
+            Secp256k1FpParams,
+            Secp256k1Params,
+        >();
+    }
+
+    #[test]
+    fn test_random_p256_arithmetic() {
+        check_arithmetic::<halo2curves::secp256r1::Secp256r1Affine, P256FpParams, P256Params>();
+    }
+
+    #[test]
+    fn test_random_not_on_curve() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let p = P256Point::alloc_element(
+            &mut cs.namespace(|| "alloc p"),
+            &Some((BigInt::from(1), BigInt::from(2))),
+        )
+        .unwrap();
+        p.assert_is_on_curve(&mut cs.namespace(|| "p on curve"))
+            .unwrap();
+        assert!(!cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let p = Secp256k1Point::alloc_element(
+            &mut cs.namespace(|| "alloc p"),
+            &Some((BigInt::from(1), BigInt::from(2))),
+        )
+        .unwrap();
+        p.assert_is_on_curve(&mut cs.namespace(|| "p on curve"))
+            .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+}
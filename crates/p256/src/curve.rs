@@ -0,0 +1,69 @@
+use bellpepper_emulated::field_element::EmulatedFieldParams;
+use bellpepper_emulated::weierstrass::{EmulatedCurveParams, WeierstrassPoint};
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+
+use crate::field::{P256Fp, P256FpParams};
+
+/// Parameters of the NIST P-256 curve `y² = x³ - 3x + b` from [SEC 2](https://www.secg.org/sec2-v2.pdf),
+/// also known as secp256r1
+pub struct P256CurveParams;
+
+impl<F: PrimeFieldBits> EmulatedCurveParams<P256Fp<F>> for P256CurveParams {
+    fn a() -> P256Fp<F> {
+        P256Fp::from(&(P256FpParams::modulus() - 3))
+    }
+
+    fn b() -> P256Fp<F> {
+        let b = BigInt::parse_bytes(
+            b"5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+            16,
+        )
+        .unwrap();
+        P256Fp::from(&b)
+    }
+
+    fn generator() -> (P256Fp<F>, P256Fp<F>) {
+        let x = BigInt::parse_bytes(
+            b"6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            16,
+        )
+        .unwrap();
+        let y = BigInt::parse_bytes(
+            b"4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            16,
+        )
+        .unwrap();
+        (P256Fp::from(&x), P256Fp::from(&y))
+    }
+}
+
+/// Represents an affine point on P-256. Point at infinity is represented with (0, 0)
+pub type P256Point<F> = WeierstrassPoint<F, P256FpParams, P256CurveParams>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::P256ScalarParams;
+    use bellpepper_emulated::test_utils::{
+        check_generator_is_on_curve, check_random_double_scalar_mul,
+    };
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::secp256r1::Secp256r1Affine;
+
+    #[test]
+    fn test_generator_is_on_curve() {
+        check_generator_is_on_curve::<Fp, P256FpParams, P256CurveParams, Secp256r1Affine>();
+    }
+
+    #[test]
+    fn test_random_double_scalar_mul() {
+        check_random_double_scalar_mul::<
+            Fp,
+            P256FpParams,
+            P256CurveParams,
+            Secp256r1Affine,
+            P256ScalarParams,
+        >();
+    }
+}
@@ -0,0 +1,159 @@
+use bellpepper::gadgets::sha256::sha256;
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::ecdsa::ecdsa_verify;
+use ff::PrimeFieldBits;
+
+use crate::curve::P256Point;
+use crate::scalar::P256ScalarParams;
+
+/// Number of bits in the encoding of a scalar in an ECDSA signature and of the message hash
+pub const SCALAR_ENCODING_BITS: usize = 256;
+
+/// Verifies an ECDSA signature `(r, s)` over P-256 of a message hashed with SHA-256, using the
+/// generic [`ecdsa_verify`](bellpepper_emulated::ecdsa::ecdsa_verify)
+///
+/// The bits of the message are given in the order expected by the SHA-256 gadget, i.e. the
+/// bytes of the message with the bits of each byte in big-endian order.
+pub fn ecdsa_verify_sha256<F, CS>(
+    cs: &mut CS,
+    pk: &P256Point<F>,
+    message_bits: &[Boolean],
+    r: &[Boolean],
+    s: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if message_bits.len() % 8 != 0 {
+        eprintln!("Message bit length must be a multiple of 8");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let digest = sha256(cs.namespace(|| "SHA-256(M)"), message_bits)?;
+    // the digest is the big-endian encoding of the message hash
+    let msg_hash = digest.into_iter().rev().collect::<Vec<_>>();
+    ecdsa_verify::<F, _, P256ScalarParams, _, _>(cs, pk, &msg_hash, r, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bellpepper_emulated::field_element::EmulatedFieldParams;
+    use bellpepper_emulated::test_utils::{alloc_bits, check_wycheproof_vectors, sign};
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::secp256r1::Secp256r1Affine;
+    use num_bigint::{BigInt, Sign};
+    use num_traits::Zero;
+    use sha2::{Digest, Sha256};
+
+    /// Wycheproof's `ecdsa_secp256r1_sha256_test.json`, with one line per test case
+    const WYCHEPROOF_VECTORS: &str =
+        include_str!("../test_vectors/wycheproof/ecdsa_secp256r1_sha256_test.csv");
+
+    fn sign_message(msg: &[u8]) -> ((BigInt, BigInt), BigInt, BigInt) {
+        let msg_hash = BigInt::from_bytes_be(Sign::Plus, &Sha256::digest(msg));
+        sign::<Secp256r1Affine, P256ScalarParams>(&msg_hash)
+    }
+
+    fn ecdsa_verify_helper(
+        pk: &(BigInt, BigInt),
+        message: &[u8],
+        r: &BigInt,
+        s: &BigInt,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+
+        let message_bits = message
+            .iter()
+            .enumerate()
+            .flat_map(|(i, byte)| (0..8).rev().map(move |j| (i, j, (byte >> j) & 1 == 1)))
+            .map(|(i, j, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(
+                        cs.namespace(|| format!("allocate bit {j} of message byte {i}")),
+                        Some(b),
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let pk =
+            P256Point::alloc_element(&mut cs.namespace(|| "alloc pk"), &Some(pk.clone())).unwrap();
+        let r = alloc_bits(&mut cs, "r", r, SCALAR_ENCODING_BITS);
+        let s = alloc_bits(&mut cs, "s", s, SCALAR_ENCODING_BITS);
+
+        let res = ecdsa_verify_sha256(
+            &mut cs.namespace(|| "ecdsa_verify_sha256"),
+            &pk,
+            &message_bits,
+            &r,
+            &s,
+        );
+        assert!(res.is_ok());
+        cs
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify() {
+        let (pk, r, s) = sign_message(b"sample");
+        let cs = ecdsa_verify_helper(&pk, b"sample", &r, &s);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_high_s() {
+        let (pk, r, s) = sign_message(b"sample");
+        // (r, n - s) is also a valid signature
+        let s = P256ScalarParams::modulus() - s;
+        let cs = ecdsa_verify_helper(&pk, b"sample", &r, &s);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_wrong_message() {
+        let (pk, r, s) = sign_message(b"sample");
+        let cs = ecdsa_verify_helper(&pk, b"test", &r, &s);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_zero_s() {
+        let (pk, r, _) = sign_message(b"sample");
+        for s in [BigInt::zero(), P256ScalarParams::modulus()] {
+            let cs = ecdsa_verify_helper(&pk, b"sample", &r, &s);
+            assert!(!cs.is_satisfied());
+            assert!(cs
+                .which_is_unsatisfied()
+                .unwrap()
+                .contains("check 0 < s < n"));
+        }
+    }
+
+    fn check_vectors(filter: impl Fn(usize) -> bool) {
+        check_wycheproof_vectors(
+            WYCHEPROOF_VECTORS,
+            SCALAR_ENCODING_BITS,
+            filter,
+            |v, r, s| ecdsa_verify_helper(&v.pk, &v.msg, r, s).is_satisfied(),
+        );
+    }
+
+    #[test]
+    fn test_wycheproof_vectors_sample() {
+        check_vectors(|i| i % 20 == 0);
+    }
+
+    #[test]
+    #[ignore = "synthesizes a signature verification for each of the 218 well-formed vectors"]
+    fn test_wycheproof_vectors() {
+        check_vectors(|_| true);
+    }
+}
@@ -0,0 +1,39 @@
+use bellpepper_emulated::field_element::{
+    EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
+};
+use num_bigint::BigInt;
+
+/// Parameters of the base field of P-256, i.e. integers modulo
+/// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+///
+/// The modulus is a generalized Mersenne prime, but `2^256 - p` is too large for the limb folding
+/// of `PseudoMersennePrime`, so the generic reduction is used.
+pub struct P256FpParams;
+
+impl EmulatedFieldParams for P256FpParams {
+    fn num_limbs() -> usize {
+        4
+    }
+
+    fn bits_per_limb() -> usize {
+        64
+    }
+
+    fn modulus() -> BigInt {
+        BigInt::parse_bytes(
+            b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn is_modulus_pseudo_mersenne() -> bool {
+        false
+    }
+
+    fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+        None
+    }
+}
+
+pub type P256Fp<F> = EmulatedFieldElement<F, P256FpParams>;
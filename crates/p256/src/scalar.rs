@@ -0,0 +1,36 @@
+use bellpepper_emulated::field_element::{
+    EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
+};
+use num_bigint::BigInt;
+
+/// Parameters of the scalar field of P-256, i.e. integers modulo the
+/// order n of the group of points
+pub struct P256ScalarParams;
+
+impl EmulatedFieldParams for P256ScalarParams {
+    fn num_limbs() -> usize {
+        4
+    }
+
+    fn bits_per_limb() -> usize {
+        64
+    }
+
+    fn modulus() -> BigInt {
+        BigInt::parse_bytes(
+            b"ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn is_modulus_pseudo_mersenne() -> bool {
+        false
+    }
+
+    fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+        None
+    }
+}
+
+pub type P256Scalar<F> = EmulatedFieldElement<F, P256ScalarParams>;
@@ -0,0 +1,266 @@
+//! Circuits for the hash functions used in Bitcoin, built from the [RIPEMD-160]
+//! and SHA-256 circuits, and for the proof-of-work check of a block header.
+//!
+//! As in the hash circuits, byte strings are given as sequences of bytes, each
+//! of them as 8 big-endian bits.
+//!
+//! [RIPEMD-160]: https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+
+use bellpepper::gadgets::sha256::sha256;
+use bellpepper_core::{boolean::Boolean, ConstraintSystem, SynthesisError};
+use ff::PrimeField;
+
+use crate::ripemd160::ripemd160;
+
+/// Number of bits in a SEC 1 compressed public key `0x02/0x03 || x`
+pub const COMPRESSED_PUBKEY_BITS: usize = 264;
+
+/// Number of bits in a block header
+pub const BLOCK_HEADER_BITS: usize = 640;
+
+/// Number of bits in a proof-of-work target
+pub const TARGET_BITS: usize = 256;
+
+/// Computes `RIPEMD160(SHA256(input))`, the hash used in P2PKH and P2WPKH
+/// addresses
+pub fn hash160<Scalar, CS>(mut cs: CS, input: &[Boolean]) -> Result<[Boolean; 160], SynthesisError>
+where
+    Scalar: PrimeField,
+    CS: ConstraintSystem<Scalar>,
+{
+    let digest = sha256(cs.namespace(|| "sha256"), input)?;
+    ripemd160(cs.namespace(|| "ripemd160"), &digest)
+}
+
+/// Computes `SHA256(SHA256(input))`, the hash used for block headers and
+/// transactions
+pub fn sha256d<Scalar, CS>(mut cs: CS, input: &[Boolean]) -> Result<Vec<Boolean>, SynthesisError>
+where
+    Scalar: PrimeField,
+    CS: ConstraintSystem<Scalar>,
+{
+    let digest = sha256(cs.namespace(|| "first sha256"), input)?;
+    sha256(cs.namespace(|| "second sha256"), &digest)
+}
+
+/// Computes the HASH160 of a compressed public key `0x02/0x03 || x`, enforcing
+/// that its prefix byte is either `0x02` or `0x03`
+pub fn hash160_compressed_pubkey<Scalar, CS>(
+    mut cs: CS,
+    pubkey: &[Boolean],
+) -> Result<[Boolean; 160], SynthesisError>
+where
+    Scalar: PrimeField,
+    CS: ConstraintSystem<Scalar>,
+{
+    if pubkey.len() != COMPRESSED_PUBKEY_BITS {
+        eprintln!("Compressed public key must have {COMPRESSED_PUBKEY_BITS} bits");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    // The last bit of the prefix is the parity of the y-coordinate
+    for (i, bit) in pubkey[..7].iter().enumerate() {
+        Boolean::enforce_equal(
+            cs.namespace(|| format!("prefix bit {i}")),
+            bit,
+            &Boolean::constant(i == 6),
+        )?;
+    }
+    hash160(cs.namespace(|| "hash160"), pubkey)
+}
+
+/// Enforces that the integer given by the little-endian bits `a` is at most
+/// the one given by the little-endian bits `b`
+fn enforce_bits_le_at_most<Scalar, CS>(
+    mut cs: CS,
+    a: &[Boolean],
+    b: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    Scalar: PrimeField,
+    CS: ConstraintSystem<Scalar>,
+{
+    assert_eq!(a.len(), b.len());
+
+    // After processing bit i, `is_le` is true if and only if the integer given
+    // by bits 0..=i of a is at most the one given by bits 0..=i of b
+    let mut is_le = Boolean::constant(true);
+    for (i, (a_i, b_i)) in a.iter().zip(b).enumerate() {
+        let mut cs = cs.namespace(|| format!("bit {i}"));
+        // If b_i is set, a is at most b unless a_i is set and the lower bits of
+        // a are greater than those of b
+        let is_gt_if_set = Boolean::and(cs.namespace(|| "a_i and not is_le"), a_i, &is_le.not())?;
+        // If b_i is unset, a is at most b if a_i is unset and the lower bits
+        // of a are at most those of b
+        let is_le_if_unset =
+            Boolean::and(cs.namespace(|| "not a_i and is_le"), &a_i.not(), &is_le)?;
+        is_le = Boolean::sha256_ch(
+            cs.namespace(|| "is_le <- b_i ? not is_gt_if_set : is_le_if_unset"),
+            b_i,
+            &is_gt_if_set.not(),
+            &is_le_if_unset,
+        )?;
+    }
+    Boolean::enforce_equal(cs.namespace(|| "a <= b"), &is_le, &Boolean::constant(true))
+}
+
+/// Verifies the proof-of-work of a block header, i.e. that its hash
+/// `SHA256(SHA256(header))`, read as a little-endian integer, is at most the
+/// witnessed `target`
+///
+/// The target is given as 256 little-endian bits. It is not checked against the
+/// compact encoding `nBits` of the header, which is left to the caller.
+pub fn verify_block_header_pow<Scalar, CS>(
+    mut cs: CS,
+    header: &[Boolean],
+    target: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    Scalar: PrimeField,
+    CS: ConstraintSystem<Scalar>,
+{
+    if header.len() != BLOCK_HEADER_BITS || target.len() != TARGET_BITS {
+        eprintln!("Block header must have {BLOCK_HEADER_BITS} bits and target {TARGET_BITS} bits");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let hash = sha256d(cs.namespace(|| "sha256d(header)"), header)?;
+    // The hash bytes are in little-endian order, with big-endian bits
+    let hash_bits_le = hash
+        .chunks(8)
+        .flat_map(|byte| byte.iter().rev().cloned())
+        .collect::<Vec<_>>();
+    enforce_bits_le_at_most(cs.namespace(|| "hash <= target"), &hash_bits_le, target)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bellpepper::gadgets::multipack::bytes_to_bits;
+    use bellpepper_core::{boolean::AllocatedBit, test_cs::TestConstraintSystem};
+    use hex_literal::hex;
+    use pasta_curves::Fp;
+
+    const GENESIS_HEADER: [u8; 80] = hex!(
+        "0100000000000000000000000000000000000000000000000000000000000000"
+        "000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa"
+        "4b1e5e4a29ab5f49ffff001d1dac2b7c"
+    );
+
+    fn alloc_bits(cs: &mut TestConstraintSystem<Fp>, name: &str, bits: &[bool]) -> Vec<Boolean> {
+        bits.iter()
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("{name} bit {i}")), Some(*b))
+                        .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn assert_bits_eq(bits: &[Boolean], expected: &[u8]) {
+        assert_eq!(bits.len(), expected.len() * 8);
+        let values = bits
+            .iter()
+            .map(|b| b.get_value().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, bytes_to_bits(expected));
+    }
+
+    /// Returns the little-endian bits of the 256-bit integer with big-endian
+    /// bytes `bytes`
+    fn target_bits(bytes: &[u8; 32]) -> Vec<bool> {
+        (0..256)
+            .map(|i| (bytes[31 - i / 8] >> (i % 8)) & 1 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_hash160() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let input = alloc_bits(&mut cs, "input", &bytes_to_bits(b"abc"));
+        let out = hash160(cs.namespace(|| "hash160"), &input).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_bits_eq(&out, &hex!("bb1be98c142444d7a56aa3981c3942a978e4dc33"));
+    }
+
+    #[test]
+    fn test_sha256d() {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let input = alloc_bits(&mut cs, "input", &bytes_to_bits(b"abc"));
+        let out = sha256d(cs.namespace(|| "sha256d"), &input).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_bits_eq(
+            &out,
+            &hex!("4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358"),
+        );
+    }
+
+    #[test]
+    fn test_hash160_compressed_pubkey() {
+        // The compressed public key of the private key 1
+        let pubkey = hex!("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let input = alloc_bits(&mut cs, "pubkey", &bytes_to_bits(&pubkey));
+        let out = hash160_compressed_pubkey(cs.namespace(|| "hash160"), &input).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_bits_eq(&out, &hex!("751e76e8199196d454941c45d1b3a323f1433bd6"));
+    }
+
+    #[test]
+    fn test_hash160_compressed_pubkey_wrong_prefix() {
+        let mut pubkey = hex!("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        for prefix in [0x00, 0x04, 0x06, 0x12] {
+            pubkey[0] = prefix;
+            let mut cs = TestConstraintSystem::<Fp>::new();
+            let input = alloc_bits(&mut cs, "pubkey", &bytes_to_bits(&pubkey));
+            hash160_compressed_pubkey(cs.namespace(|| "hash160"), &input).unwrap();
+
+            assert!(!cs.is_satisfied());
+            assert!(cs.which_is_unsatisfied().unwrap().contains("prefix bit"));
+        }
+    }
+
+    fn verify_block_header_pow_helper(target: &[u8; 32]) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let header = alloc_bits(&mut cs, "header", &bytes_to_bits(&GENESIS_HEADER));
+        let target = alloc_bits(&mut cs, "target", &target_bits(target));
+        verify_block_header_pow(cs.namespace(|| "verify pow"), &header, &target).unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_verify_block_header_pow() {
+        // The target given by nBits = 0x1d00ffff
+        let cs = verify_block_header_pow_helper(&hex!(
+            "00000000ffff0000000000000000000000000000000000000000000000000000"
+        ));
+        assert!(cs.is_satisfied());
+
+        // The block hash itself
+        let cs = verify_block_header_pow_helper(&hex!(
+            "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+        ));
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_verify_block_header_pow_target_too_low() {
+        for target in [
+            hex!("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26e"),
+            hex!("000000000009d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"),
+            [0u8; 32],
+        ] {
+            let cs = verify_block_header_pow_helper(&target);
+            assert!(!cs.is_satisfied());
+            assert!(cs
+                .which_is_unsatisfied()
+                .unwrap()
+                .contains("hash <= target"));
+        }
+    }
+}
@@ -1,2 +1,3 @@
+pub mod bitcoin;
 pub mod ripemd160;
 pub mod util;
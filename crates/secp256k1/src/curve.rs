@@ -0,0 +1,138 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::field_element::EmulatedFieldParams;
+use bellpepper_emulated::weierstrass::{EmulatedCurveParams, WeierstrassPoint};
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+use crate::field::{Secp256k1Fp, Secp256k1FpParams};
+
+/// Parameters of the secp256k1 curve `y² = x³ + 7` from [SEC 2](https://www.secg.org/sec2-v2.pdf)
+pub struct Secp256k1CurveParams;
+
+impl<F: PrimeFieldBits> EmulatedCurveParams<Secp256k1Fp<F>> for Secp256k1CurveParams {
+    fn a() -> Secp256k1Fp<F> {
+        Secp256k1Fp::zero()
+    }
+
+    fn b() -> Secp256k1Fp<F> {
+        Secp256k1Fp::from(&BigInt::from(7))
+    }
+
+    fn generator() -> (Secp256k1Fp<F>, Secp256k1Fp<F>) {
+        let x = BigInt::parse_bytes(
+            b"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            16,
+        )
+        .unwrap();
+        let y = BigInt::parse_bytes(
+            b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            16,
+        )
+        .unwrap();
+        (Secp256k1Fp::from(&x), Secp256k1Fp::from(&y))
+    }
+}
+
+/// Represents an affine point on secp256k1. Point at infinity is represented with (0, 0)
+pub type Secp256k1Point<F> = WeierstrassPoint<F, Secp256k1FpParams, Secp256k1CurveParams>;
+
+/// Returns the point `(x, y)` on the curve where `y` is the square root of `x³ + 7` whose
+/// parity is given by `y_is_odd`
+pub fn decompress<F, CS>(
+    cs: &mut CS,
+    x: &Secp256k1Fp<F>,
+    y_is_odd: &Boolean,
+) -> Result<Secp256k1Point<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    // invalid x-coordinates get y = 0, which does not satisfy the constraints below
+    let p = Secp256k1FpParams::modulus();
+    let y_value = BigInt::try_from(x)
+        .ok()
+        .zip(y_is_odd.get_value())
+        .map(|(x, is_odd)| {
+            let rhs = (x.modpow(&BigInt::from(3), &p) + 7u64) % &p;
+            // p = 3 mod 4, so a square root of rhs is rhs^((p+1)/4)
+            let y = rhs.modpow(&((&p + 1u64) >> 2), &p);
+            if (&y * &y) % &p != rhs {
+                BigInt::zero()
+            } else if y.bit(0) != is_odd {
+                &p - y
+            } else {
+                y
+            }
+        });
+    let y = Secp256k1Fp::allocate_optional_field_element_unchecked(
+        &mut cs.namespace(|| "alloc y"),
+        &y_value,
+    )?;
+    let res = Secp256k1Point::new(x.clone(), y);
+    res.assert_is_on_curve(&mut cs.namespace(|| "check (x, y) is on the curve"))?;
+
+    let y_bits = res.y.to_bits_le(&mut cs.namespace(|| "y to bits"))?;
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "y is odd == y_is_odd"),
+        &y_bits[0],
+        y_is_odd,
+    )?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Secp256k1ScalarParams;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bellpepper_emulated::test_utils::{
+        alloc_point, check_generator_is_on_curve, check_random_double_scalar_mul, coordinates,
+    };
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::group::{Curve, Group};
+    use halo2curves::secp256k1::{Secp256k1, Secp256k1Affine};
+
+    #[test]
+    fn test_generator_is_on_curve() {
+        check_generator_is_on_curve::<Fp, Secp256k1FpParams, Secp256k1CurveParams, Secp256k1Affine>(
+        );
+    }
+
+    #[test]
+    fn test_random_double_scalar_mul() {
+        check_random_double_scalar_mul::<
+            Fp,
+            Secp256k1FpParams,
+            Secp256k1CurveParams,
+            Secp256k1Affine,
+            Secp256k1ScalarParams,
+        >();
+    }
+
+    #[test]
+    fn test_random_decompress() {
+        let mut rng = rand::thread_rng();
+        let p = Secp256k1::random(&mut rng).to_affine();
+        let (x, y) = coordinates(&p);
+
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let p_alloc = alloc_point(&mut cs, "p", &p);
+        let y_is_odd = Boolean::from(
+            AllocatedBit::alloc(cs.namespace(|| "alloc y_is_odd"), Some(y.bit(0))).unwrap(),
+        );
+        let x_alloc = Secp256k1Fp::allocate_optional_field_element_unchecked(
+            &mut cs.namespace(|| "alloc x"),
+            &Some(x),
+        )
+        .unwrap();
+        let res = decompress(&mut cs.namespace(|| "decompress(x)"), &x_alloc, &y_is_odd).unwrap();
+        Secp256k1Point::assert_is_equal(&mut cs.namespace(|| "res = p"), &res, &p_alloc).unwrap();
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+}
@@ -0,0 +1,132 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::ecdsa;
+use ff::PrimeFieldBits;
+
+use crate::curve::Secp256k1Point;
+use crate::scalar::Secp256k1ScalarParams;
+
+/// Number of bits in the encoding of a scalar in an ECDSA signature and of the message hash
+pub const SCALAR_ENCODING_BITS: usize = 256;
+
+/// Verifies an ECDSA signature `(r, s)` over secp256k1 as specified in
+/// [SEC 1 (Section 4.1.4)](https://www.secg.org/sec1-v2.pdf), using the generic
+/// [`ecdsa_verify`](bellpepper_emulated::ecdsa::ecdsa_verify)
+///
+/// `msg_hash`, `r` and `s` are given as 256 little-endian bits. `msg_hash` is the integer whose
+/// big-endian encoding is the message digest, e.g. the SHA-256 hash of the message.
+pub fn ecdsa_verify<F, CS>(
+    cs: &mut CS,
+    pk: &Secp256k1Point<F>,
+    msg_hash: &[Boolean],
+    r: &[Boolean],
+    s: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    ecdsa::ecdsa_verify::<F, _, Secp256k1ScalarParams, _, _>(cs, pk, msg_hash, r, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use bellpepper_emulated::field_element::EmulatedFieldParams;
+    use bellpepper_emulated::test_utils::{alloc_bits, check_wycheproof_vectors, sign};
+    use halo2curves::bn256::Fq as Fp;
+    use halo2curves::secp256k1::Secp256k1Affine;
+    use num_bigint::{BigInt, Sign};
+    use num_traits::Zero;
+    use sha2::{Digest, Sha256};
+
+    /// Wycheproof's `ecdsa_secp256k1_sha256_test.json`, with one line per test case
+    const WYCHEPROOF_VECTORS: &str =
+        include_str!("../test_vectors/wycheproof/ecdsa_secp256k1_sha256_test.csv");
+
+    fn hash_message(msg: &[u8]) -> BigInt {
+        BigInt::from_bytes_be(Sign::Plus, &Sha256::digest(msg))
+    }
+
+    fn sign_message(msg: &[u8]) -> ((BigInt, BigInt), BigInt, BigInt) {
+        sign::<Secp256k1Affine, Secp256k1ScalarParams>(&hash_message(msg))
+    }
+
+    fn ecdsa_verify_helper(
+        pk: &(BigInt, BigInt),
+        msg_hash: &BigInt,
+        r: &BigInt,
+        s: &BigInt,
+    ) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let pk = Secp256k1Point::alloc_element(&mut cs.namespace(|| "alloc pk"), &Some(pk.clone()))
+            .unwrap();
+        let msg_hash = alloc_bits(&mut cs, "msg_hash", msg_hash, SCALAR_ENCODING_BITS);
+        let r = alloc_bits(&mut cs, "r", r, SCALAR_ENCODING_BITS);
+        let s = alloc_bits(&mut cs, "s", s, SCALAR_ENCODING_BITS);
+
+        let res = ecdsa_verify(&mut cs.namespace(|| "ecdsa_verify"), &pk, &msg_hash, &r, &s);
+        assert!(res.is_ok());
+        cs
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify() {
+        let (pk, r, s) = sign_message(b"sample");
+        let cs = ecdsa_verify_helper(&pk, &hash_message(b"sample"), &r, &s);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_high_s() {
+        let (pk, r, s) = sign_message(b"sample");
+        // (r, n - s) is also a valid signature
+        let s = Secp256k1ScalarParams::modulus() - s;
+        let cs = ecdsa_verify_helper(&pk, &hash_message(b"sample"), &r, &s);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_wrong_message() {
+        let (pk, r, s) = sign_message(b"sample");
+        let cs = ecdsa_verify_helper(&pk, &hash_message(b"test"), &r, &s);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_random_ecdsa_verify_zero_s() {
+        let (pk, r, _) = sign_message(b"sample");
+        for s in [BigInt::zero(), Secp256k1ScalarParams::modulus()] {
+            let cs = ecdsa_verify_helper(&pk, &hash_message(b"sample"), &r, &s);
+            assert!(!cs.is_satisfied());
+            assert!(cs
+                .which_is_unsatisfied()
+                .unwrap()
+                .contains("check 0 < s < n"));
+        }
+    }
+
+    fn check_vectors(filter: impl Fn(usize) -> bool) {
+        check_wycheproof_vectors(
+            WYCHEPROOF_VECTORS,
+            SCALAR_ENCODING_BITS,
+            filter,
+            |v, r, s| ecdsa_verify_helper(&v.pk, &hash_message(&v.msg), r, s).is_satisfied(),
+        );
+    }
+
+    #[test]
+    fn test_wycheproof_vectors_sample() {
+        check_vectors(|i| i % 20 == 0);
+    }
+
+    #[test]
+    #[ignore = "synthesizes a signature verification for each of the 221 well-formed vectors"]
+    fn test_wycheproof_vectors() {
+        check_vectors(|_| true);
+    }
+}
@@ -0,0 +1,381 @@
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use bellpepper_emulated::ecdsa::nonzero_scalar_from_bits;
+use bellpepper_emulated::field_element::EmulatedFieldParams;
+use bellpepper_emulated::util::enforce_bits_le_at_most_constant;
+use bellpepper_keccak::keccak256;
+use ff::PrimeFieldBits;
+use num_bigint::BigInt;
+use num_traits::One;
+
+use crate::curve::{decompress, Secp256k1Point};
+use crate::ecdsa::SCALAR_ENCODING_BITS;
+use crate::field::{Secp256k1Fp, Secp256k1FpParams};
+use crate::scalar::{Secp256k1Scalar, Secp256k1ScalarParams};
+
+/// Number of bits in an Ethereum address
+pub const ADDRESS_BITS: usize = 160;
+
+/// Number of bits in the SEC 1 compressed encoding `0x02/0x03 || x` of a public key
+pub const COMPRESSED_KEY_BITS: usize = 264;
+
+/// Number of bits in the SEC 1 uncompressed encoding `0x04 || x || y` of a public key
+pub const UNCOMPRESSED_KEY_BITS: usize = 520;
+
+/// Converts between the little-endian bits of a 256-bit integer and its big-endian byte
+/// encoding, where each byte is given by its little-endian bits as in the Keccak gadget
+fn reverse_bytes(bits: &[Boolean]) -> Vec<Boolean> {
+    bits.chunks(8).rev().flatten().cloned().collect()
+}
+
+/// Returns the base field element given by the big-endian byte encoding `bytes`, enforcing that
+/// it is less than the modulus
+fn fp_from_be_bytes<F, CS>(cs: &mut CS, bytes: &[Boolean]) -> Result<Secp256k1Fp<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let bits = reverse_bytes(bytes);
+    let p_minus_one = Secp256k1FpParams::modulus() - BigInt::one();
+    enforce_bits_le_at_most_constant(&mut cs.namespace(|| "x < p"), &bits, &p_minus_one)?;
+    Ok(Secp256k1Fp::from_bits_le(CS::one(), &bits))
+}
+
+/// Enforces that the 8 bits of `byte` (little-endian) encode the constant `value`, except for
+/// the bits in `mask`
+fn enforce_byte_equals<F, CS>(
+    cs: &mut CS,
+    byte: &[Boolean],
+    value: u8,
+    mask: u8,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    for (i, bit) in byte
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (mask >> i) & 1 == 0)
+    {
+        Boolean::enforce_equal(
+            &mut cs.namespace(|| format!("bit {i}")),
+            bit,
+            &Boolean::constant((value >> i) & 1 == 1),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the public key given by its [SEC 1 (Section 2.3.3)](https://www.secg.org/sec1-v2.pdf)
+/// encoding, which is either compressed (`0x02/0x03 || x`, 264 bits) or uncompressed
+/// (`0x04 || x || y`, 520 bits)
+///
+/// The encoding is given as a sequence of bytes, each of them as 8 little-endian bits. The
+/// prefix byte is checked, the coordinates are required to be less than the modulus and the
+/// point to be on the curve. A compressed key is decompressed in the circuit.
+pub fn public_key_from_sec1_bits<F, CS>(
+    cs: &mut CS,
+    bits: &[Boolean],
+) -> Result<Secp256k1Point<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    match bits.len() {
+        COMPRESSED_KEY_BITS => {
+            // the lowest bit of the prefix is the parity of y
+            enforce_byte_equals(&mut cs.namespace(|| "check prefix"), &bits[..8], 0x02, 0x01)?;
+            let x = fp_from_be_bytes(&mut cs.namespace(|| "x from bytes"), &bits[8..])?;
+            decompress(&mut cs.namespace(|| "decompress(x)"), &x, &bits[0])
+        }
+        UNCOMPRESSED_KEY_BITS => {
+            enforce_byte_equals(&mut cs.namespace(|| "check prefix"), &bits[..8], 0x04, 0x00)?;
+            let x = fp_from_be_bytes(&mut cs.namespace(|| "x from bytes"), &bits[8..264])?;
+            let y = fp_from_be_bytes(&mut cs.namespace(|| "y from bytes"), &bits[264..])?;
+            let pk = Secp256k1Point::new(x, y);
+            pk.assert_is_on_curve(&mut cs.namespace(|| "check pk is on the curve"))?;
+            Ok(pk)
+        }
+        _ => {
+            eprintln!("Public key must have {COMPRESSED_KEY_BITS} or {UNCOMPRESSED_KEY_BITS} bits");
+            Err(SynthesisError::Unsatisfiable)
+        }
+    }
+}
+
+/// Returns the Ethereum address of the public key `pk`, i.e. the last 20 bytes of the
+/// Keccak-256 hash of `x || y`, where the coordinates are encoded as 32 big-endian bytes
+///
+/// The address is returned as a sequence of bytes, each of them as 8 little-endian bits.
+pub fn eth_address<F, CS>(
+    cs: &mut CS,
+    pk: &Secp256k1Point<F>,
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    let x_bits = pk.x.to_bits_le(&mut cs.namespace(|| "x to bits"))?;
+    let y_bits = pk.y.to_bits_le(&mut cs.namespace(|| "y to bits"))?;
+    let mut preimage = reverse_bytes(&x_bits);
+    preimage.extend(reverse_bytes(&y_bits));
+
+    let hash = keccak256(cs.namespace(|| "keccak256(x || y)"), &preimage)?;
+    Ok(hash[256 - ADDRESS_BITS..].to_vec())
+}
+
+/// Recovers the public key from an ECDSA signature `(r, s)` of `msg_hash`, as done by the
+/// `ecrecover` precompile of Ethereum
+///
+/// `msg_hash`, `r` and `s` are given as 256 little-endian bits, as in
+/// [`ecdsa_verify`](crate::ecdsa::ecdsa_verify). `v` is the parity of the y-coordinate of the
+/// point `R` with x-coordinate `r`, i.e. the recovery id `v - 27`. Recovery ids for which
+/// `x(R) = r + n` are not supported. The scalars `r` and `s` are required to be in `[1, n-1]`
+/// and the recovered key `[s/r]R - [msg_hash/r]G` must not be the point at infinity.
+pub fn ecrecover<F, CS>(
+    cs: &mut CS,
+    msg_hash: &[Boolean],
+    v: &Boolean,
+    r: &[Boolean],
+    s: &[Boolean],
+) -> Result<Secp256k1Point<F>, SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if [msg_hash, r, s]
+        .iter()
+        .any(|bits| bits.len() != SCALAR_ENCODING_BITS)
+    {
+        eprintln!("Message hash and signature scalars must have {SCALAR_ENCODING_BITS} bits");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let (r_scalar, r_is_zero) = nonzero_scalar_from_bits::<F, Secp256k1ScalarParams, _>(
+        &mut cs.namespace(|| "check 0 < r < n"),
+        r,
+    )?;
+    let (s, _) = nonzero_scalar_from_bits::<F, Secp256k1ScalarParams, _>(
+        &mut cs.namespace(|| "check 0 < s < n"),
+        s,
+    )?;
+    // r < n < p, so its bits are also canonical in the base field
+    let r_x = Secp256k1Fp::from_bits_le(CS::one(), r);
+    let r_point = decompress(&mut cs.namespace(|| "R <- decompress(r)"), &r_x, v)?;
+
+    // the circuit is already unsatisfiable if r = 0, so replace it with 1 to be able to compute
+    // its inverse and finish the synthesis
+    let r_scalar = Secp256k1Scalar::conditionally_select(
+        &mut cs.namespace(|| "r <- select(r, 1, r_is_zero)"),
+        &r_scalar,
+        &Secp256k1Scalar::one(),
+        &r_is_zero,
+    )?;
+    let z = Secp256k1Scalar::from_bits_le(CS::one(), msg_hash);
+
+    let w = r_scalar.inverse(&mut cs.namespace(|| "w <- r^-1"))?;
+    let u1 = z.mul(&mut cs.namespace(|| "z * w"), &w)?;
+    let u1 = u1.neg(&mut cs.namespace(|| "u1 <- -z * w"))?;
+    let u2 = s.mul(&mut cs.namespace(|| "u2 <- s * w"), &w)?;
+    let u1_bits = u1.to_bits_le(&mut cs.namespace(|| "u1 to bits"))?;
+    let u2_bits = u2.to_bits_le(&mut cs.namespace(|| "u2 to bits"))?;
+
+    let g = Secp256k1Point::alloc_generator(&mut cs.namespace(|| "alloc G"))?;
+    let pk = Secp256k1Point::double_scalar_mul(
+        &mut cs.namespace(|| "pk <- [u1]G + [u2]R"),
+        &g,
+        &u1_bits,
+        &r_point,
+        &u2_bits,
+    )?;
+    let is_identity = pk.alloc_is_identity(&mut cs.namespace(|| "pk =? O"))?;
+    Boolean::enforce_equal(
+        &mut cs.namespace(|| "pk != O"),
+        &Boolean::from(is_identity),
+        &Boolean::constant(false),
+    )?;
+    Ok(pk)
+}
+
+/// Enforces that the public key recovered from the signature `(v, r, s)` of `msg_hash` has the
+/// Ethereum address `address`, given as in [`eth_address`]
+pub fn assert_ecrecover<F, CS>(
+    cs: &mut CS,
+    msg_hash: &[Boolean],
+    v: &Boolean,
+    r: &[Boolean],
+    s: &[Boolean],
+    address: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeFieldBits,
+    CS: ConstraintSystem<F>,
+{
+    if address.len() != ADDRESS_BITS {
+        eprintln!("Address must have {ADDRESS_BITS} bits");
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let pk = ecrecover(&mut cs.namespace(|| "ecrecover"), msg_hash, v, r, s)?;
+    let pk_address = eth_address(&mut cs.namespace(|| "address of pk"), &pk)?;
+    for (i, (a, b)) in pk_address.iter().zip(address).enumerate() {
+        Boolean::enforce_equal(&mut cs.namespace(|| format!("address bit {i}")), a, b)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellpepper_core::boolean::AllocatedBit;
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use halo2curves::bn256::Fq as Fp;
+
+    /// Private keys d6840b...dc71 and d3cc16...b68d with their public keys and addresses
+    const KEYS: [(&str, &str, &str); 2] = [
+        (
+            "bb45f489bea73ef400b0ef4cd65dcec3565b0fd75c6eb248f1fefc84dd216650",
+            "327e5a5c9b02ed7ce898f8ecb2e045cded87742a7723e7fddd9ac96c8aa70f46",
+            "604a95c9165bc95ae016a5299dd7d400dddbea9a",
+        ),
+        (
+            "733a61b47a124b93c554af6fcc748b5b5a4619a8c3f14a864ce02cd11401d204",
+            "3e232f9aa406e22db59afcf447420c50cb5b4153afbc1bda48150b37d0fba7d6",
+            "1c96099350f13d558464ec79b9be4445aa0ef579",
+        ),
+    ];
+
+    /// Signature `(v, r, s)` of the SHA-256 hash of "sample" by the first key
+    const SIGNATURE: (bool, &str, &str) = (
+        false,
+        "17f53289eac961e5adc858d3ca50dab056ddca7a1a906c0815a0369312d1aa49",
+        "dc991eae39595d05971b395db14f2216f86dbd255a9e0ac4c130fb7886024808",
+    );
+
+    const MSG_HASH: &str = "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf";
+
+    /// Allocates the bytes given in hexadecimal, each of them as 8 little-endian bits
+    fn alloc_bytes(cs: &mut TestConstraintSystem<Fp>, name: &str, bytes: &str) -> Vec<Boolean> {
+        hex::decode(bytes)
+            .unwrap()
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("alloc {name} bit {i}")), Some(b))
+                        .unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    /// Allocates the 256-bit integer given in hexadecimal as little-endian bits
+    fn alloc_scalar(cs: &mut TestConstraintSystem<Fp>, name: &str, value: &str) -> Vec<Boolean> {
+        reverse_bytes(&alloc_bytes(cs, name, value))
+    }
+
+    fn eth_address_helper(encoded_pk: &str, address: &str) -> TestConstraintSystem<Fp> {
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let pk_bits = alloc_bytes(&mut cs, "pk", encoded_pk);
+        let address_bits = alloc_bytes(&mut cs, "address", address);
+        let pk = public_key_from_sec1_bits(&mut cs.namespace(|| "decode pk"), &pk_bits).unwrap();
+        let res = eth_address(&mut cs.namespace(|| "eth_address"), &pk).unwrap();
+        for (i, (a, b)) in res.iter().zip(&address_bits).enumerate() {
+            Boolean::enforce_equal(&mut cs.namespace(|| format!("check bit {i}")), a, b).unwrap();
+        }
+        cs
+    }
+
+    #[test]
+    fn test_eth_address_uncompressed() {
+        for (x, y, address) in KEYS {
+            let cs = eth_address_helper(&format!("04{x}{y}"), address);
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_eth_address_compressed() {
+        // both y-coordinates are even
+        for (x, _, address) in KEYS {
+            let cs = eth_address_helper(&format!("02{x}"), address);
+            if !cs.is_satisfied() {
+                eprintln!("{:?}", cs.which_is_unsatisfied())
+            }
+            assert!(cs.is_satisfied());
+
+            // the other key with the same x-coordinate has a different address
+            let cs = eth_address_helper(&format!("03{x}"), address);
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_invalid_public_keys() {
+        let (x, y, address) = KEYS[0];
+        // wrong prefixes
+        for encoded_pk in [format!("05{x}{y}"), format!("06{x}")] {
+            let cs = eth_address_helper(&encoded_pk, address);
+            assert!(!cs.is_satisfied());
+            assert!(cs.which_is_unsatisfied().unwrap().contains("check prefix"));
+        }
+        // 5³ + 7 is not a square modulo p
+        let x = format!("{:064x}", 5);
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let pk_bits = alloc_bytes(&mut cs, "pk", &format!("02{x}"));
+        public_key_from_sec1_bits(&mut cs.namespace(|| "decode pk"), &pk_bits).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    fn assert_ecrecover_helper(v: bool, msg_hash: &str, address: &str) -> TestConstraintSystem<Fp> {
+        let (_, r, s) = SIGNATURE;
+        let mut cs = TestConstraintSystem::<Fp>::new();
+        let msg_hash = alloc_scalar(&mut cs, "msg_hash", msg_hash);
+        let v = Boolean::from(AllocatedBit::alloc(cs.namespace(|| "alloc v"), Some(v)).unwrap());
+        let r = alloc_scalar(&mut cs, "r", r);
+        let s = alloc_scalar(&mut cs, "s", s);
+        let address = alloc_bytes(&mut cs, "address", address);
+        let res = assert_ecrecover(
+            &mut cs.namespace(|| "assert_ecrecover"),
+            &msg_hash,
+            &v,
+            &r,
+            &s,
+            &address,
+        );
+        assert!(res.is_ok());
+        cs
+    }
+
+    #[test]
+    fn test_ecrecover() {
+        let cs = assert_ecrecover_helper(SIGNATURE.0, MSG_HASH, KEYS[0].2);
+        if !cs.is_satisfied() {
+            eprintln!("{:?}", cs.which_is_unsatisfied())
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_ecrecover_wrong_address() {
+        let cs = assert_ecrecover_helper(SIGNATURE.0, MSG_HASH, KEYS[1].2);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_ecrecover_wrong_recovery_id() {
+        let cs = assert_ecrecover_helper(!SIGNATURE.0, MSG_HASH, KEYS[0].2);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_ecrecover_wrong_message() {
+        let msg_hash = format!("{:064x}", 1);
+        let cs = assert_ecrecover_helper(SIGNATURE.0, &msg_hash, KEYS[0].2);
+        assert!(!cs.is_satisfied());
+    }
+}
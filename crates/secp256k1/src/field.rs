@@ -0,0 +1,36 @@
+use bellpepper_emulated::field_element::{
+    EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
+};
+use num_bigint::BigInt;
+
+/// Parameters of the base field of secp256k1, i.e. integers modulo
+/// p = 2^256 - 2^32 - 977
+pub struct Secp256k1FpParams;
+
+impl EmulatedFieldParams for Secp256k1FpParams {
+    fn num_limbs() -> usize {
+        4
+    }
+
+    fn bits_per_limb() -> usize {
+        64
+    }
+
+    fn modulus() -> BigInt {
+        BigInt::parse_bytes(
+            b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn is_modulus_pseudo_mersenne() -> bool {
+        false
+    }
+
+    fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+        None
+    }
+}
+
+pub type Secp256k1Fp<F> = EmulatedFieldElement<F, Secp256k1FpParams>;
@@ -0,0 +1,5 @@
+pub mod curve;
+pub mod ecdsa;
+pub mod ethereum;
+pub mod field;
+pub mod scalar;
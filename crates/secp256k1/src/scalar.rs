@@ -0,0 +1,36 @@
+use bellpepper_emulated::field_element::{
+    EmulatedFieldElement, EmulatedFieldParams, PseudoMersennePrime,
+};
+use num_bigint::BigInt;
+
+/// Parameters of the scalar field of secp256k1, i.e. integers modulo the
+/// order n of the group of points
+pub struct Secp256k1ScalarParams;
+
+impl EmulatedFieldParams for Secp256k1ScalarParams {
+    fn num_limbs() -> usize {
+        4
+    }
+
+    fn bits_per_limb() -> usize {
+        64
+    }
+
+    fn modulus() -> BigInt {
+        BigInt::parse_bytes(
+            b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn is_modulus_pseudo_mersenne() -> bool {
+        false
+    }
+
+    fn pseudo_mersenne_params() -> Option<PseudoMersennePrime> {
+        None
+    }
+}
+
+pub type Secp256k1Scalar<F> = EmulatedFieldElement<F, Secp256k1ScalarParams>;